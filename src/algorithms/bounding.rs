@@ -0,0 +1,494 @@
+use super::hull::convex_hull;
+use super::predicates::orient2d;
+use crate::geoms::Geometry;
+use crate::geoms::line::Line;
+use crate::geoms::multipolygon::MultiPolygon;
+use crate::geoms::point::Point;
+use crate::geoms::polygon::Polygon;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+/// A circle given by its center and radius
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Circle {
+    /// center of the circle
+    center: Point,
+    /// radius of the circle
+    radius: f64,
+}
+
+impl Circle {
+    /// create a circle with given center and radius
+    pub fn new(center: Point, radius: f64) -> Circle {
+        Circle { center, radius }
+    }
+
+    /// get the center of the circle
+    pub fn get_center(&self) -> Point {
+        self.center
+    }
+
+    /// get the radius of the circle
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    /// check if a point lies inside the circle or on its boundary
+    pub fn contains_point(&self, point: &Point) -> bool {
+        self.center.distance2D(point) <= self.radius
+    }
+
+    /// containment check with a small tolerance against rounding errors
+    fn covers(&self, point: &Point) -> bool {
+        self.center.distance2D(point) <= self.radius * (1.0 + 1e-12) + 1e-12
+    }
+
+    /// smallest circle through two points
+    fn from_diameter(a: &Point, b: &Point) -> Circle {
+        let center = Point::new((a.get_x() + b.get_x()) / 2.0, (a.get_y() + b.get_y()) / 2.0);
+        Circle::new(center, a.distance2D(b) / 2.0)
+    }
+
+    /// smallest circle containing three points: the circumcircle, or the circle over the farthest pair for
+    /// collinear points
+    fn from_three(a: &Point, b: &Point, c: &Point) -> Circle {
+        let (bx, by) = (b.get_x() - a.get_x(), b.get_y() - a.get_y());
+        let (cx, cy) = (c.get_x() - a.get_x(), c.get_y() - a.get_y());
+        let d = 2.0 * orient2d(a, b, c);
+        if d == 0.0 {
+            return [
+                Circle::from_diameter(a, b),
+                Circle::from_diameter(a, c),
+                Circle::from_diameter(b, c),
+            ]
+            .into_iter()
+            .max_by(|p, q| p.radius.total_cmp(&q.radius))
+            .unwrap();
+        }
+        let b_len = bx * bx + by * by;
+        let c_len = cx * cx + cy * cy;
+        let ux = (cy * b_len - by * c_len) / d;
+        let uy = (bx * c_len - cx * b_len) / d;
+        let center = Point::new(a.get_x() + ux, a.get_y() + uy);
+        let radius = center
+            .distance2D(a)
+            .max(center.distance2D(b))
+            .max(center.distance2D(c));
+        Circle::new(center, radius)
+    }
+}
+
+/// A rectangle which is not necessarily aligned with the axes
+#[derive(Debug, Clone)]
+pub struct RotatedRect {
+    /// the rectangle as closed counter-clockwise polygon
+    polygon: Polygon,
+    /// angle of the first rectangle side against the x axis in radians, in the range [0, pi)
+    angle: f64,
+    /// length of the first rectangle side
+    length: f64,
+    /// length of the second rectangle side
+    width: f64,
+}
+
+impl RotatedRect {
+    /// get the rectangle as a closed counter-clockwise polygon
+    pub fn get_polygon(&self) -> &Polygon {
+        &self.polygon
+    }
+
+    /// get the orientation of the rectangle: the angle of its first side against the x axis, in the range
+    /// [0, 180) degrees or [0, pi) radians, depending on the `use_radians` parameter
+    pub fn get_angle(&self, use_radians: bool) -> f64 {
+        if use_radians {
+            self.angle
+        } else {
+            self.angle.to_degrees()
+        }
+    }
+
+    /// get the length of the side along the orientation angle
+    pub fn get_length(&self) -> f64 {
+        self.length
+    }
+
+    /// get the length of the side perpendicular to the orientation angle
+    pub fn get_width(&self) -> f64 {
+        self.width
+    }
+
+    /// get the area of the rectangle
+    pub fn area(&self) -> f64 {
+        self.length * self.width
+    }
+}
+
+/// Compute the smallest circle containing all points with Welzl's algorithm.
+/// The points are shuffled (with a fixed seed) first, which gives an expected linear run time.
+/// Return None for an empty slice.
+pub fn minimum_enclosing_circle(points: &[Point]) -> Option<Circle> {
+    let mut shuffled = points.to_vec();
+    // use a pseudo random generator for reproducible results
+    shuffled.shuffle(&mut StdRng::seed_from_u64(0));
+
+    let mut circle = Circle::new(*shuffled.first()?, 0.0);
+    for i in 1..shuffled.len() {
+        if circle.covers(&shuffled[i]) {
+            continue;
+        }
+        // shuffled[i] lies on the boundary of the enclosing circle of the first i + 1 points
+        circle = Circle::new(shuffled[i], 0.0);
+        for j in 0..i {
+            if circle.covers(&shuffled[j]) {
+                continue;
+            }
+            // shuffled[i] and shuffled[j] both lie on the boundary
+            circle = Circle::from_diameter(&shuffled[i], &shuffled[j]);
+            for k in 0..j {
+                if !circle.covers(&shuffled[k]) {
+                    circle = Circle::from_three(&shuffled[i], &shuffled[j], &shuffled[k]);
+                }
+            }
+        }
+    }
+    Some(circle)
+}
+
+/// Compute the rotated rectangle of minimum area containing all points, using rotating calipers on the convex hull.
+/// Return None if the points do not span an area (fewer than three non-collinear points).
+pub fn minimum_area_rectangle(points: &[Point]) -> Option<RotatedRect> {
+    hull_rectangles(points)?
+        .into_iter()
+        .min_by(|a, b| a.area().total_cmp(&b.area()))
+}
+
+/// Compute the rotated rectangle of minimum width containing all points, using rotating calipers on the convex hull.
+/// The width is the smallest distance between two parallel lines enclosing the points.
+/// Return None if the points do not span an area (fewer than three non-collinear points).
+pub fn minimum_width_rectangle(points: &[Point]) -> Option<RotatedRect> {
+    hull_rectangles(points)?
+        .into_iter()
+        .min_by(|a, b| a.width.total_cmp(&b.width))
+}
+
+/// The enclosing rectangles with one side flush with a convex hull edge. An optimal rectangle for both area and
+/// width is among them. The three caliper indices only move forward, so all rectangles are found in linear time.
+fn hull_rectangles(points: &[Point]) -> Option<Vec<RotatedRect>> {
+    let hull = convex_hull(points);
+    let h = hull.len();
+    if h < 3 {
+        return None;
+    }
+    let dot = |p: &Point, ux: f64, uy: f64| p.get_x() * ux + p.get_y() * uy;
+
+    let mut rectangles = Vec::with_capacity(h);
+    let (mut far, mut right, mut left) = (1, 1, 0);
+    for i in 0..h {
+        let (p, q) = (&hull[i], &hull[(i + 1) % h]);
+        let length = p.distance2D(q);
+        let (ux, uy) = (
+            (q.get_x() - p.get_x()) / length,
+            (q.get_y() - p.get_y()) / length,
+        );
+        // the normal points to the inside of the counter-clockwise hull
+        let (nx, ny) = (-uy, ux);
+
+        // rightmost point along the edge direction
+        for _ in 0..h {
+            if dot(&hull[(right + 1) % h], ux, uy) >= dot(&hull[right], ux, uy) {
+                right = (right + 1) % h;
+            } else {
+                break;
+            }
+        }
+        // farthest point from the edge
+        if i == 0 {
+            far = right;
+        }
+        for _ in 0..h {
+            if dot(&hull[(far + 1) % h], nx, ny) >= dot(&hull[far], nx, ny) {
+                far = (far + 1) % h;
+            } else {
+                break;
+            }
+        }
+        // leftmost point along the edge direction
+        if i == 0 {
+            left = far;
+        }
+        for _ in 0..h {
+            if dot(&hull[(left + 1) % h], ux, uy) <= dot(&hull[left], ux, uy) {
+                left = (left + 1) % h;
+            } else {
+                break;
+            }
+        }
+
+        let min_u = dot(&hull[left], ux, uy) - dot(p, ux, uy);
+        let max_u = dot(&hull[right], ux, uy) - dot(p, ux, uy);
+        let height = dot(&hull[far], nx, ny) - dot(p, nx, ny);
+        let corner =
+            |s: f64, t: f64| Point::new(p.get_x() + s * ux + t * nx, p.get_y() + s * uy + t * ny);
+        let first = corner(min_u, 0.0);
+        let polygon = Polygon::new(
+            Line::new(vec![
+                first,
+                corner(max_u, 0.0),
+                corner(max_u, height),
+                corner(min_u, height),
+                first,
+            ]),
+            vec![],
+        );
+        let mut angle = uy.atan2(ux);
+        if angle < 0.0 {
+            angle += std::f64::consts::PI;
+        }
+        if angle >= std::f64::consts::PI {
+            angle -= std::f64::consts::PI;
+        }
+        rectangles.push(RotatedRect {
+            polygon,
+            angle,
+            length: max_u - min_u,
+            width: height,
+        });
+    }
+    Some(rectangles)
+}
+
+impl Line {
+    /// get the smallest circle containing all points of the line
+    pub fn minimum_enclosing_circle(&self) -> Circle {
+        minimum_enclosing_circle(self.get_points()).unwrap()
+    }
+
+    /// get the rotated rectangle of minimum area containing the line, None if the line is straight
+    pub fn minimum_area_rectangle(&self) -> Option<RotatedRect> {
+        minimum_area_rectangle(self.get_points())
+    }
+
+    /// get the rotated rectangle of minimum width containing the line, None if the line is straight
+    pub fn minimum_width_rectangle(&self) -> Option<RotatedRect> {
+        minimum_width_rectangle(self.get_points())
+    }
+}
+
+impl Polygon {
+    /// get the smallest circle containing the polygon
+    pub fn minimum_enclosing_circle(&self) -> Circle {
+        minimum_enclosing_circle(self.get_exterior().get_points()).unwrap()
+    }
+
+    /// get the rotated rectangle of minimum area containing the polygon, None if the polygon has no area
+    pub fn minimum_area_rectangle(&self) -> Option<RotatedRect> {
+        minimum_area_rectangle(self.get_exterior().get_points())
+    }
+
+    /// get the rotated rectangle of minimum width containing the polygon, None if the polygon has no area
+    pub fn minimum_width_rectangle(&self) -> Option<RotatedRect> {
+        minimum_width_rectangle(self.get_exterior().get_points())
+    }
+}
+
+impl MultiPolygon {
+    /// get the smallest circle containing all polygons, None if the multipolygon is empty
+    pub fn minimum_enclosing_circle(&self) -> Option<Circle> {
+        minimum_enclosing_circle(&exterior_points(self))
+    }
+
+    /// get the rotated rectangle of minimum area containing all polygons, None if the polygons have no area
+    pub fn minimum_area_rectangle(&self) -> Option<RotatedRect> {
+        minimum_area_rectangle(&exterior_points(self))
+    }
+
+    /// get the rotated rectangle of minimum width containing all polygons, None if the polygons have no area
+    pub fn minimum_width_rectangle(&self) -> Option<RotatedRect> {
+        minimum_width_rectangle(&exterior_points(self))
+    }
+}
+
+impl Geometry {
+    /// get the smallest circle containing the geometry, a circle of radius zero for a point and None for an empty
+    /// multipolygon
+    pub fn minimum_enclosing_circle(&self) -> Option<Circle> {
+        match self {
+            Geometry::Point(point) => Some(Circle::new(*point, 0.0)),
+            Geometry::Line(line) => Some(line.minimum_enclosing_circle()),
+            Geometry::Polygon(polygon) => Some(polygon.minimum_enclosing_circle()),
+            Geometry::MultiPolygon(multipolygon) => multipolygon.minimum_enclosing_circle(),
+        }
+    }
+
+    /// get the rotated rectangle of minimum area containing the geometry, None for points, straight lines and
+    /// geometries without area
+    pub fn minimum_area_rectangle(&self) -> Option<RotatedRect> {
+        match self {
+            Geometry::Point(_) => None,
+            Geometry::Line(line) => line.minimum_area_rectangle(),
+            Geometry::Polygon(polygon) => polygon.minimum_area_rectangle(),
+            Geometry::MultiPolygon(multipolygon) => multipolygon.minimum_area_rectangle(),
+        }
+    }
+
+    /// get the rotated rectangle of minimum width containing the geometry, None for points, straight lines and
+    /// geometries without area
+    pub fn minimum_width_rectangle(&self) -> Option<RotatedRect> {
+        match self {
+            Geometry::Point(_) => None,
+            Geometry::Line(line) => line.minimum_width_rectangle(),
+            Geometry::Polygon(polygon) => polygon.minimum_width_rectangle(),
+            Geometry::MultiPolygon(multipolygon) => multipolygon.minimum_width_rectangle(),
+        }
+    }
+}
+
+/// collect the points of the exteriors of all polygons, the holes lie inside them
+fn exterior_points(multipolygon: &MultiPolygon) -> Vec<Point> {
+    multipolygon
+        .get_polygons()
+        .iter()
+        .flat_map(|polygon| polygon.get_exterior().get_points())
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn test_circle_two_points() {
+        let circle =
+            minimum_enclosing_circle(&[Point::new(0.0, 0.0), Point::new(4.0, 0.0)]).unwrap();
+        assert_eq!(circle.get_center(), Point::new(2.0, 0.0));
+        assert_eq!(circle.get_radius(), 2.0);
+        assert!(minimum_enclosing_circle(&[]).is_none());
+    }
+
+    #[test]
+    fn test_circle_triangle_and_inner_points() {
+        // right triangle: the hypotenuse is the diameter
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(6.0, 0.0),
+            Point::new(0.0, 8.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 3.0),
+        ];
+        let circle = minimum_enclosing_circle(&points).unwrap();
+        assert!((circle.get_center().get_x() - 3.0).abs() < EPS);
+        assert!((circle.get_center().get_y() - 4.0).abs() < EPS);
+        assert!((circle.get_radius() - 5.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_circle_random_points() {
+        let points = Point::generate_random_points(1000, -50.0, -20.0, 30.0, 60.0, 9);
+        let circle = minimum_enclosing_circle(&points).unwrap();
+        assert!(points.iter().all(|p| circle.covers(p)));
+        // at least two points lie on the boundary of the minimum circle
+        let on_boundary = points
+            .iter()
+            .filter(|p| (circle.get_center().distance2D(p) - circle.get_radius()).abs() < 1e-9)
+            .count();
+        assert!(on_boundary >= 2);
+    }
+
+    #[test]
+    fn test_rotated_square() {
+        // square of side 2 rotated by 30 degrees
+        let mut points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        for point in points.iter_mut() {
+            point.rotate(&Point::origin(), 30.0, false);
+        }
+        let rectangle = minimum_area_rectangle(&points).unwrap();
+        assert!((rectangle.area() - 4.0).abs() < EPS);
+        let angle = rectangle.get_angle(false);
+        assert!((angle - 30.0).abs() < 1e-6 || (angle - 120.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_polygon_rectangles() {
+        // a thin rotated building footprint with a notch
+        let mut polygon =
+            Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 3, 6 3, 6 2, 4 2, 4 3, 0 3, 0 0))").unwrap();
+        let mut exterior = polygon.get_exterior().clone();
+        exterior.rotate(&Point::origin(), 45.0, false);
+        polygon = Polygon::new(exterior, vec![]);
+
+        let by_area = polygon.minimum_area_rectangle().unwrap();
+        assert!((by_area.area() - 30.0).abs() < 1e-6);
+        assert!(
+            (by_area.get_angle(false) - 45.0).abs() < 1e-6
+                || (by_area.get_angle(false) - 135.0).abs() < 1e-6
+        );
+
+        let by_width = polygon.minimum_width_rectangle().unwrap();
+        assert!((by_width.get_width() - 3.0).abs() < 1e-6);
+        assert!((by_width.get_length() - 10.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_rectangle_contains_all_points() {
+        let points = Point::generate_random_points(300, 0.0, 0.0, 10.0, 3.0, 21);
+        for rectangle in [
+            minimum_area_rectangle(&points).unwrap(),
+            minimum_width_rectangle(&points).unwrap(),
+        ] {
+            let corners = rectangle.get_polygon().get_exterior().get_points();
+            for k in 0..4 {
+                let (a, b) = (&corners[k], &corners[k + 1]);
+                for p in &points {
+                    let cross = (b.get_x() - a.get_x()) * (p.get_y() - a.get_y())
+                        - (b.get_y() - a.get_y()) * (p.get_x() - a.get_x());
+                    assert!(cross >= -1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_collinear_points_have_no_rectangle() {
+        let line = Line::new(vec![Point::new(0.0, 0.0), Point::new(3.0, 3.0)]);
+        assert!(line.minimum_area_rectangle().is_none());
+        assert!((line.minimum_enclosing_circle().get_radius() - 18.0_f64.sqrt() / 2.0).abs() < EPS);
+    }
+
+    #[test]
+    fn test_multipolygon_and_geometry() {
+        let multipolygon = MultiPolygon::new(vec![
+            Polygon::from_wkt("POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))").unwrap(),
+            Polygon::from_wkt("POLYGON ((8 0, 10 0, 10 2, 8 2, 8 0))").unwrap(),
+        ]);
+        let circle = multipolygon.minimum_enclosing_circle().unwrap();
+        assert!((circle.get_center().get_x() - 5.0).abs() < EPS);
+        assert!((circle.get_center().get_y() - 1.0).abs() < EPS);
+        assert!((circle.get_radius() - 26.0_f64.sqrt()).abs() < EPS);
+        let by_area = multipolygon.minimum_area_rectangle().unwrap();
+        assert!((by_area.area() - 20.0).abs() < EPS);
+        let by_width = multipolygon.minimum_width_rectangle().unwrap();
+        assert!((by_width.get_width() - 2.0).abs() < EPS);
+
+        let geometry = Geometry::MultiPolygon(multipolygon);
+        assert_eq!(geometry.minimum_enclosing_circle(), Some(circle));
+        assert!((geometry.minimum_area_rectangle().unwrap().area() - 20.0).abs() < EPS);
+        let empty = Geometry::MultiPolygon(MultiPolygon::new(vec![]));
+        assert_eq!(empty.minimum_enclosing_circle(), None);
+        assert!(empty.minimum_width_rectangle().is_none());
+        let point = Geometry::Point(Point::new(1.0, 2.0));
+        assert_eq!(
+            point.minimum_enclosing_circle(),
+            Some(Circle::new(Point::new(1.0, 2.0), 0.0))
+        );
+        assert!(point.minimum_area_rectangle().is_none());
+    }
+}
@@ -0,0 +1,313 @@
+use super::predicates::{incircle, orient2d};
+use crate::geoms::point::Point;
+use crate::geoms::rect::Rect;
+use std::collections::HashMap;
+
+/// Delaunay triangulation of a point set
+#[derive(Debug, Clone)]
+pub struct DelaunayTriangulation {
+    /// the triangulated points
+    points: Vec<Point>,
+    /// counter-clockwise triangles as index triples into `points`
+    triangles: Vec<[usize; 3]>,
+    /// for every triangle the neighbouring triangle opposite of each of its vertices
+    neighbors: Vec<[Option<usize>; 3]>,
+}
+
+impl DelaunayTriangulation {
+    /// Triangulate a point set with the Bowyer–Watson algorithm.
+    ///
+    /// Points are inserted one at a time: the triangle containing the new point is found by walking
+    /// through the triangulation, every triangle whose circumcircle contains the point is removed and
+    /// the resulting cavity is re-triangulated around the point. Duplicate points are ignored.
+    pub fn new(points: &[Point]) -> DelaunayTriangulation {
+        let Some(bounds) = Rect::from_points(points) else {
+            return DelaunayTriangulation {
+                points: vec![],
+                triangles: vec![],
+                neighbors: vec![],
+            };
+        };
+
+        // super triangle which contains all points, its vertices are stored after the input points
+        let n = points.len();
+        let center = bounds.center();
+        let size = bounds.get_width().max(bounds.get_height()).max(1.0) * 1e4;
+        let mut vertices = points.to_vec();
+        vertices.push(Point::new(
+            center.get_x() - 2.0 * size,
+            center.get_y() - size,
+        ));
+        vertices.push(Point::new(
+            center.get_x() + 2.0 * size,
+            center.get_y() - size,
+        ));
+        vertices.push(Point::new(center.get_x(), center.get_y() + 2.0 * size));
+
+        let mut mesh = Mesh {
+            vertices,
+            triangles: vec![[n, n + 1, n + 2]],
+            neighbors: vec![[None; 3]],
+            alive: vec![true],
+            last: 0,
+        };
+        for i in 0..n {
+            mesh.insert(i);
+        }
+
+        // drop every triangle connected to the super triangle and renumber the remaining ones
+        let mut new_index = vec![None; mesh.triangles.len()];
+        let mut triangles = Vec::new();
+        for (t, triangle) in mesh.triangles.iter().enumerate() {
+            if mesh.alive[t] && triangle.iter().all(|&v| v < n) {
+                new_index[t] = Some(triangles.len());
+                triangles.push(*triangle);
+            }
+        }
+        let neighbors = mesh
+            .triangles
+            .iter()
+            .enumerate()
+            .filter(|(t, _)| new_index[*t].is_some())
+            .map(|(t, _)| mesh.neighbors[t].map(|neighbor| neighbor.and_then(|o| new_index[o])))
+            .collect();
+
+        DelaunayTriangulation {
+            points: points.to_vec(),
+            triangles,
+            neighbors,
+        }
+    }
+
+    /// get the triangulated points
+    pub fn get_points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// get the counter-clockwise triangles as index triples into [`DelaunayTriangulation::get_points`]
+    pub fn get_triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// get the neighbouring triangles of a triangle. Entry k is the triangle sharing the edge opposite
+    /// of vertex k, or None if that edge is on the convex hull.
+    pub fn triangle_neighbors(&self, triangle: usize) -> [Option<usize>; 3] {
+        self.neighbors[triangle]
+    }
+
+    /// get the indices of all points connected to a point by a Delaunay edge, in ascending order
+    pub fn point_neighbors(&self, point: usize) -> Vec<usize> {
+        let mut neighbors: Vec<usize> = self
+            .triangles
+            .iter()
+            .filter(|triangle| triangle.contains(&point))
+            .flat_map(|triangle| triangle.iter().copied())
+            .filter(|&v| v != point)
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors
+    }
+
+    /// get the Delaunay neighbours of every point at once
+    pub(crate) fn all_point_neighbors(&self) -> Vec<Vec<usize>> {
+        let mut neighbors = vec![Vec::new(); self.points.len()];
+        for triangle in &self.triangles {
+            for k in 0..3 {
+                neighbors[triangle[k]].push(triangle[(k + 1) % 3]);
+                neighbors[triangle[k]].push(triangle[(k + 2) % 3]);
+            }
+        }
+        for list in neighbors.iter_mut() {
+            list.sort_unstable();
+            list.dedup();
+        }
+        neighbors
+    }
+}
+
+/// Working state of the Bowyer–Watson algorithm
+struct Mesh {
+    vertices: Vec<Point>,
+    triangles: Vec<[usize; 3]>,
+    neighbors: Vec<[Option<usize>; 3]>,
+    alive: Vec<bool>,
+    /// most recently created triangle, used as start of the next point location walk
+    last: usize,
+}
+
+impl Mesh {
+    fn insert(&mut self, v: usize) {
+        let p = self.vertices[v];
+        let Some(start) = self.locate(&p) else {
+            return;
+        };
+        if self.triangles[start].iter().any(|&w| self.vertices[w] == p) {
+            // duplicate point
+            return;
+        }
+
+        // collect the cavity: all connected triangles whose circumcircle contains the point
+        let mut bad = vec![start];
+        let mut is_bad: HashMap<usize, bool> = HashMap::from([(start, true)]);
+        let mut stack = vec![start];
+        while let Some(t) = stack.pop() {
+            for neighbor in self.neighbors[t].into_iter().flatten() {
+                if is_bad.contains_key(&neighbor) {
+                    continue;
+                }
+                let [a, b, c] = self.triangles[neighbor].map(|w| self.vertices[w]);
+                let inside = incircle(&a, &b, &c, &p) > 0.0;
+                is_bad.insert(neighbor, inside);
+                if inside {
+                    bad.push(neighbor);
+                    stack.push(neighbor);
+                }
+            }
+        }
+
+        // re-triangulate the cavity boundary with the new point
+        let mut starting_at: HashMap<usize, usize> = HashMap::new();
+        let mut ending_at: HashMap<usize, usize> = HashMap::new();
+        let mut created = Vec::new();
+        for &t in &bad {
+            self.alive[t] = false;
+            for k in 0..3 {
+                let outer = self.neighbors[t][k];
+                if outer.is_some_and(|o| is_bad.get(&o) == Some(&true)) {
+                    continue;
+                }
+                let a = self.triangles[t][(k + 1) % 3];
+                let b = self.triangles[t][(k + 2) % 3];
+                let new = self.triangles.len();
+                self.triangles.push([a, b, v]);
+                self.neighbors.push([None, None, outer]);
+                self.alive.push(true);
+                if let Some(o) = outer {
+                    for slot in self.neighbors[o].iter_mut() {
+                        if *slot == Some(t) {
+                            *slot = Some(new);
+                        }
+                    }
+                }
+                starting_at.insert(a, new);
+                ending_at.insert(b, new);
+                created.push(new);
+            }
+        }
+        for &t in &created {
+            let [a, b, _] = self.triangles[t];
+            // edge b -> v is shared with the triangle starting at b, edge v -> a with the one ending at a
+            self.neighbors[t][0] = starting_at.get(&b).copied();
+            self.neighbors[t][1] = ending_at.get(&a).copied();
+        }
+        self.last = *created.last().unwrap_or(&self.last);
+    }
+
+    /// find the triangle containing the point by walking towards it
+    fn locate(&self, p: &Point) -> Option<usize> {
+        let mut t = self.last;
+        if !self.alive[t] {
+            t = self.alive.iter().rposition(|&alive| alive)?;
+        }
+        // a visibility walk always terminates in a Delaunay triangulation, the bound is a safeguard
+        for _ in 0..self.triangles.len() {
+            let triangle = self.triangles[t];
+            let next = (0..3).find(|&k| {
+                let a = &self.vertices[triangle[(k + 1) % 3]];
+                let b = &self.vertices[triangle[(k + 2) % 3]];
+                orient2d(a, b, p) < 0.0
+            });
+            match next.and_then(|k| self.neighbors[t][k]) {
+                Some(neighbor) => t = neighbor,
+                None => return Some(t),
+            }
+        }
+        // fall back to a linear search
+        (0..self.triangles.len()).find(|&t| {
+            self.alive[t]
+                && (0..3).all(|k| {
+                    let a = &self.vertices[self.triangles[t][(k + 1) % 3]];
+                    let b = &self.vertices[self.triangles[t][(k + 2) % 3]];
+                    orient2d(a, b, p) >= 0.0
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::hull::convex_hull;
+
+    fn grid_points() -> Vec<Point> {
+        let mut points = Vec::new();
+        for i in 0..5 {
+            for j in 0..5 {
+                points.push(Point::new(i as f64, j as f64 + 0.01 * i as f64));
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn test_square() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.1),
+        ];
+        let delaunay = DelaunayTriangulation::new(&points);
+        assert_eq!(delaunay.get_triangles().len(), 2);
+        assert_eq!(delaunay.point_neighbors(0), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_empty_circumcircles() {
+        let points = Point::generate_random_points(200, 0.0, 0.0, 100.0, 100.0, 7);
+        let delaunay = DelaunayTriangulation::new(&points);
+        // a triangulation of n points with h points on the hull has 2n - 2 - h triangles, so none is missing
+        let h = convex_hull(&points).len();
+        assert_eq!(delaunay.get_triangles().len(), 2 * points.len() - 2 - h);
+        assert_eq!(delaunay.get_triangles().len(), 382);
+        for triangle in delaunay.get_triangles() {
+            let [a, b, c] = triangle.map(|i| points[i]);
+            assert!(orient2d(&a, &b, &c) > 0.0);
+            for (i, p) in points.iter().enumerate() {
+                if !triangle.contains(&i) {
+                    assert!(incircle(&a, &b, &c, p) <= 0.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_triangle_neighbors_are_symmetric() {
+        let delaunay = DelaunayTriangulation::new(&grid_points());
+        for t in 0..delaunay.get_triangles().len() {
+            for neighbor in delaunay.triangle_neighbors(t).into_iter().flatten() {
+                assert!(delaunay.triangle_neighbors(neighbor).contains(&Some(t)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicates_are_ignored() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 0.0),
+        ];
+        let delaunay = DelaunayTriangulation::new(&points);
+        assert_eq!(delaunay.get_triangles().len(), 1);
+        assert!(delaunay.point_neighbors(3).is_empty());
+    }
+
+    #[test]
+    fn test_empty_input() {
+        let delaunay = DelaunayTriangulation::new(&[]);
+        assert!(delaunay.get_triangles().is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+use super::intersection::{SegmentIntersection, segment_intersection};
+use super::locate::{Location, locate_point_in_polygon};
+use crate::geoms::Geometry;
+use crate::geoms::line::closest_point_on_segment;
+use crate::geoms::point::Point;
+use crate::geoms::polygon::Polygon;
+
+/// Compute the minimum distance between two geometries.
+/// The distance is zero if the geometries intersect or one contains the other.
+/// Return None if a geometry is empty.
+pub fn distance(a: &Geometry, b: &Geometry) -> Option<f64> {
+    nearest_points(a, b).map(|(p, q)| p.distance2D(&q))
+}
+
+/// Compute a pair of points, the first on `a` and the second on `b`, which realise the minimum distance between
+/// the geometries. If the geometries intersect or one contains the other, both points are the same common point.
+/// Return None if a geometry is empty.
+pub fn nearest_points(a: &Geometry, b: &Geometry) -> Option<(Point, Point)> {
+    let (parts_a, parts_b) = (Parts::new(a), Parts::new(b));
+    if parts_a.is_empty() || parts_b.is_empty() {
+        return None;
+    }
+
+    // crossing or touching boundaries
+    for (p, q) in &parts_a.segments {
+        for (r, s) in &parts_b.segments {
+            match segment_intersection(p, q, r, s) {
+                SegmentIntersection::None => {}
+                SegmentIntersection::Point(point) | SegmentIntersection::Overlap(point, _) => {
+                    return Some((point, point));
+                }
+            }
+        }
+    }
+    // a component lying inside a polygon of the other geometry, the boundaries are disjoint by now
+    if let Some(point) = parts_b.contained_representative(&parts_a) {
+        return Some((point, point));
+    }
+    if let Some(point) = parts_a.contained_representative(&parts_b) {
+        return Some((point, point));
+    }
+
+    // the closest pair involves an end point of a segment, or one of the single points
+    let mut best: Option<(f64, Point, Point)> = None;
+    let mut consider = |p: Point, q: Point| {
+        let d = p.distance2D(&q);
+        if best.is_none_or(|(best_distance, _, _)| d < best_distance) {
+            best = Some((d, p, q));
+        }
+    };
+    for (p, q) in parts_a.pieces() {
+        for (r, s) in parts_b.pieces() {
+            consider(p, closest_point_on_segment(&p, &r, &s));
+            consider(q, closest_point_on_segment(&q, &r, &s));
+            consider(closest_point_on_segment(&r, &p, &q), r);
+            consider(closest_point_on_segment(&s, &p, &q), s);
+        }
+    }
+    best.map(|(_, p, q)| (p, q))
+}
+
+/// The parts of a geometry which matter for distances
+struct Parts<'a> {
+    /// single points
+    points: Vec<Point>,
+    /// segments of lines and polygon rings
+    segments: Vec<(Point, Point)>,
+    /// polygons, whose interior counts as part of the geometry
+    polygons: Vec<&'a Polygon>,
+    /// one point of every connected component
+    representatives: Vec<Point>,
+}
+
+impl<'a> Parts<'a> {
+    fn new(geometry: &'a Geometry) -> Parts<'a> {
+        let mut parts = Parts {
+            points: Vec::new(),
+            segments: Vec::new(),
+            polygons: Vec::new(),
+            representatives: Vec::new(),
+        };
+        match geometry {
+            Geometry::Point(point) => {
+                parts.points.push(*point);
+                parts.representatives.push(*point);
+            }
+            Geometry::Line(line) => {
+                let points = line.get_points();
+                parts
+                    .segments
+                    .extend(points.windows(2).map(|pair| (pair[0], pair[1])));
+                parts.representatives.push(points[0]);
+            }
+            Geometry::Polygon(polygon) => parts.add_polygon(polygon),
+            Geometry::MultiPolygon(multipolygon) => {
+                for polygon in multipolygon.get_polygons() {
+                    parts.add_polygon(polygon);
+                }
+            }
+        }
+        parts
+    }
+
+    fn add_polygon(&mut self, polygon: &'a Polygon) {
+        for ring in std::iter::once(polygon.get_exterior()).chain(polygon.get_interiors()) {
+            let points = ring.get_points();
+            self.segments
+                .extend(points.windows(2).map(|pair| (pair[0], pair[1])));
+            // close rings given without their closing point
+            if points.first() != points.last() {
+                self.segments.push((points[points.len() - 1], points[0]));
+            }
+        }
+        self.polygons.push(polygon);
+        self.representatives
+            .push(polygon.get_exterior().get_points()[0]);
+    }
+
+    fn is_empty(&self) -> bool {
+        self.representatives.is_empty()
+    }
+
+    /// segments and single points, the latter as segments of zero length
+    fn pieces(&self) -> impl Iterator<Item = (Point, Point)> + '_ {
+        self.segments
+            .iter()
+            .copied()
+            .chain(self.points.iter().map(|p| (*p, *p)))
+    }
+
+    /// a representative point of a component lying inside a polygon of the other parts
+    fn contained_representative(&self, other: &Parts) -> Option<Point> {
+        self.representatives.iter().copied().find(|point| {
+            other
+                .polygons
+                .iter()
+                .any(|polygon| locate_point_in_polygon(point, polygon) != Location::Exterior)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geoms::line::Line;
+    use crate::geoms::multipolygon::MultiPolygon;
+
+    const EPS: f64 = 1e-10;
+
+    fn line(wkt: &str) -> Geometry {
+        Line::from_wkt(wkt).unwrap().into()
+    }
+
+    fn polygon(wkt: &str) -> Geometry {
+        Polygon::from_wkt(wkt).unwrap().into()
+    }
+
+    #[test]
+    fn test_points() {
+        let a: Geometry = Point::new(0.0, 0.0).into();
+        let b: Geometry = Point::new(3.0, 4.0).into();
+        assert_eq!(distance(&a, &b), Some(5.0));
+        assert_eq!(
+            nearest_points(&a, &b),
+            Some((Point::new(0.0, 0.0), Point::new(3.0, 4.0)))
+        );
+    }
+
+    #[test]
+    fn test_point_and_line() {
+        let point: Geometry = Point::new(1.0, 2.0).into();
+        let line = line("LINESTRING (0 0, 4 0, 4 4)");
+        assert_eq!(
+            nearest_points(&point, &line),
+            Some((Point::new(1.0, 2.0), Point::new(1.0, 0.0)))
+        );
+        assert_eq!(
+            nearest_points(&line, &point),
+            Some((Point::new(1.0, 0.0), Point::new(1.0, 2.0)))
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        let a = line("LINESTRING (0 0, 2 2)");
+        let crossing = line("LINESTRING (0 2, 2 0)");
+        assert_eq!(distance(&a, &crossing), Some(0.0));
+        assert_eq!(
+            nearest_points(&a, &crossing),
+            Some((Point::new(1.0, 1.0), Point::new(1.0, 1.0)))
+        );
+        let apart = line("LINESTRING (3 0, 5 -2)");
+        let (p, q) = nearest_points(&a, &apart).unwrap();
+        assert_eq!(q, Point::new(3.0, 0.0));
+        assert!(p.approx_eq(&Point::new(1.5, 1.5), EPS));
+        assert!((distance(&a, &apart).unwrap() - 4.5_f64.sqrt()).abs() < EPS);
+    }
+
+    #[test]
+    fn test_containment_gives_zero() {
+        let square = polygon("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (4 4, 6 4, 6 6, 4 6, 4 4))");
+        let inside = line("LINESTRING (1 1, 2 2)");
+        assert_eq!(distance(&square, &inside), Some(0.0));
+        assert_eq!(distance(&inside, &square), Some(0.0));
+        let small = polygon("POLYGON ((1 1, 2 1, 2 2, 1 2, 1 1))");
+        assert_eq!(distance(&small, &square), Some(0.0));
+        // a point in the hole is not contained, its nearest point lies on the hole ring
+        let in_hole: Geometry = Point::new(5.0, 4.5).into();
+        assert_eq!(
+            nearest_points(&square, &in_hole),
+            Some((Point::new(5.0, 4.0), Point::new(5.0, 4.5)))
+        );
+    }
+
+    #[test]
+    fn test_polygons_apart() {
+        let a = polygon("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))");
+        let b = polygon("POLYGON ((3 0.5, 4 0, 4 2, 3 0.5))");
+        assert_eq!(
+            nearest_points(&a, &b),
+            Some((Point::new(1.0, 0.5), Point::new(3.0, 0.5)))
+        );
+        assert_eq!(distance(&a, &b), Some(2.0));
+    }
+
+    #[test]
+    fn test_multipolygon() {
+        let multipolygon: Geometry = MultiPolygon::new(vec![
+            Polygon::from_wkt("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))").unwrap(),
+            Polygon::from_wkt("POLYGON ((10 0, 11 0, 11 1, 10 1, 10 0))").unwrap(),
+        ])
+        .into();
+        let point: Geometry = Point::new(8.0, 0.5).into();
+        assert_eq!(distance(&multipolygon, &point), Some(2.0));
+        let inside: Geometry = Point::new(10.5, 0.5).into();
+        assert_eq!(distance(&multipolygon, &inside), Some(0.0));
+        let empty: Geometry = MultiPolygon::new(vec![]).into();
+        assert_eq!(distance(&empty, &point), None);
+        assert_eq!(nearest_points(&point, &empty), None);
+    }
+}
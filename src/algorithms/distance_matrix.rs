@@ -0,0 +1,175 @@
+use crate::geoms::point::Point;
+use std::cmp::Ordering;
+
+/// Symmetric matrix of the distances between n points with zeros on the diagonal.
+///
+/// Only the n (n - 1) / 2 entries above the diagonal are stored, row by row in a single vector, which takes half the
+/// memory of a dense matrix and a single allocation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix {
+    size: usize,
+    /// entries (0, 1), (0, 2), ..., (0, n - 1), (1, 2), ..., (n - 2, n - 1)
+    condensed: Vec<f64>,
+}
+
+impl DistanceMatrix {
+    /// create a matrix of the given size from the entries above the diagonal, row by row
+    pub fn from_condensed(size: usize, condensed: Vec<f64>) -> DistanceMatrix {
+        if condensed.len() != size * size.saturating_sub(1) / 2 {
+            panic!("A condensed matrix of size n must have n (n - 1) / 2 entries.");
+        }
+        DistanceMatrix { size, condensed }
+    }
+
+    /// get the number of rows and columns
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// check if the matrix has no rows
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// get the entries above the diagonal, row by row
+    pub fn get_condensed(&self) -> &[f64] {
+        &self.condensed
+    }
+
+    /// get the distance between points i and j
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        if i >= self.size || j >= self.size {
+            panic!("Matrix index out of range.");
+        }
+        match i.cmp(&j) {
+            Ordering::Equal => 0.0,
+            Ordering::Less => self.condensed[self.condensed_index(i, j)],
+            Ordering::Greater => self.condensed[self.condensed_index(j, i)],
+        }
+    }
+
+    /// iterate over the distances from point i to all points, including the zero to itself
+    pub fn row(&self, i: usize) -> impl Iterator<Item = f64> + '_ {
+        if i >= self.size {
+            panic!("Matrix index out of range.");
+        }
+        (0..self.size).map(move |j| self.get(i, j))
+    }
+
+    /// iterate over all rows
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = f64> + '_> + '_ {
+        (0..self.size).map(move |i| self.row(i))
+    }
+
+    /// get the nearest other point to point i with its distance, the lowest index wins ties. None if there is no
+    /// other point.
+    pub fn row_argmin(&self, i: usize) -> Option<(usize, f64)> {
+        self.others(i)
+            .reduce(|best, entry| if entry.1 < best.1 { entry } else { best })
+    }
+
+    /// get the farthest other point from point i with its distance, the lowest index wins ties. None if there is no
+    /// other point.
+    pub fn row_argmax(&self, i: usize) -> Option<(usize, f64)> {
+        self.others(i)
+            .reduce(|best, entry| if entry.1 > best.1 { entry } else { best })
+    }
+
+    /// expand the matrix to all n x n entries
+    pub fn to_dense(&self) -> Vec<Vec<f64>> {
+        self.rows().map(|row| row.collect()).collect()
+    }
+
+    /// position of entry (i, j) with i < j in the condensed vector
+    fn condensed_index(&self, i: usize, j: usize) -> usize {
+        self.size * i - i * (i + 1) / 2 + (j - i - 1)
+    }
+
+    /// the entries of row i without the diagonal
+    fn others(&self, i: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        self.row(i).enumerate().filter(move |&(j, _)| j != i)
+    }
+}
+
+impl Point {
+    /// Compute the distances between all pairs of points as a condensed [`DistanceMatrix`], which needs half the
+    /// memory of [`Point::pairwise_distances`]
+    pub fn distance_matrix(points: &[Point]) -> DistanceMatrix {
+        let n = points.len();
+        let mut condensed = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+        for (i, point) in points.iter().enumerate() {
+            condensed.extend(points[i + 1..].iter().map(|other| point.distance2D(other)));
+        }
+        DistanceMatrix::from_condensed(n, condensed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_matrix() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 4.0),
+            Point::new(6.0, 0.0),
+            Point::new(0.0, 1.0),
+        ];
+        let matrix = Point::distance_matrix(&points);
+        assert_eq!(matrix.get_size(), 4);
+        assert_eq!(matrix.get_condensed().len(), 6);
+        assert_eq!(matrix.get(0, 1), 5.0);
+        assert_eq!(matrix.get(1, 0), 5.0);
+        assert_eq!(matrix.get(2, 2), 0.0);
+        assert_eq!(
+            matrix.row(2).collect::<Vec<f64>>(),
+            vec![6.0, 5.0, 0.0, 37f64.sqrt()]
+        );
+        assert_eq!(matrix.rows().count(), 4);
+        assert_eq!(matrix.to_dense(), Point::pairwise_distances(&points));
+    }
+
+    #[test]
+    fn test_argmin_and_argmax() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 4.0),
+            Point::new(6.0, 0.0),
+            Point::new(0.0, 1.0),
+        ];
+        let matrix = Point::distance_matrix(&points);
+        assert_eq!(matrix.row_argmin(0), Some((3, 1.0)));
+        assert_eq!(matrix.row_argmax(0), Some((2, 6.0)));
+        // ties go to the lowest index
+        assert_eq!(matrix.row_argmax(1), Some((0, 5.0)));
+        assert_eq!(matrix.row_argmin(1), Some((3, 18f64.sqrt())));
+        let single = Point::distance_matrix(&[Point::new(1.0, 1.0)]);
+        assert_eq!(single.row_argmin(0), None);
+        assert!(Point::distance_matrix(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_random_points() {
+        let points = Point::generate_random_points(50, -10.0, -10.0, 10.0, 10.0, 71);
+        let matrix = Point::distance_matrix(&points);
+        let dense = Point::pairwise_distances(&points);
+        assert_eq!(matrix.to_dense(), dense);
+        for i in 0..points.len() {
+            let (j, distance) = matrix.row_argmin(i).unwrap();
+            assert_eq!(distance, points[i].distance2D(&points[j]));
+            assert!(
+                dense[i]
+                    .iter()
+                    .enumerate()
+                    .all(|(k, &d)| k == i || d >= distance)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "A condensed matrix of size n must have n (n - 1) / 2 entries.")]
+    fn test_invalid_condensed_length() {
+        DistanceMatrix::from_condensed(3, vec![1.0, 2.0]);
+    }
+}
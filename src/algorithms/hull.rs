@@ -0,0 +1,111 @@
+use super::predicates::orient2d;
+use crate::geoms::point::Point;
+
+/// Compute the convex hull of a point set with Andrew's monotone chain algorithm.
+/// Return the hull as an open counter-clockwise ring without collinear points, starting at the leftmost point,
+/// the lowest one if several points share the minimum x.
+/// Fewer than three points are returned if all points are collinear or identical.
+pub fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by_key(Point::key);
+    sorted.dedup_by_key(|p| p.key());
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut hull: Vec<Point> = Vec::with_capacity(sorted.len() + 1);
+    // lower hull
+    for point in &sorted {
+        while hull.len() >= 2
+            && orient2d(&hull[hull.len() - 2], &hull[hull.len() - 1], point) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(*point);
+    }
+    // upper hull
+    let lower_len = hull.len() + 1;
+    for point in sorted.iter().rev().skip(1) {
+        while hull.len() >= lower_len
+            && orient2d(&hull[hull.len() - 2], &hull[hull.len() - 1], point) <= 0.0
+        {
+            hull.pop();
+        }
+        hull.push(*point);
+    }
+    // the last point equals the first one
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_square_with_inner_and_collinear_points() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.0, 2.0),
+        ];
+        assert_eq!(
+            convex_hull(&points),
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(2.0, 2.0),
+                Point::new(0.0, 2.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collinear_points() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(1.0, 1.0),
+        ];
+        assert_eq!(
+            convex_hull(&points),
+            vec![Point::new(0.0, 0.0), Point::new(2.0, 2.0)]
+        );
+    }
+
+    #[test]
+    fn test_start_point() {
+        // the hull starts at the leftmost point, not at one of the two lowest points
+        let points = vec![
+            Point::new(3.0, 0.0),
+            Point::new(2.0, 4.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.0, 3.0),
+        ];
+        assert_eq!(
+            convex_hull(&points),
+            vec![
+                Point::new(0.0, 2.0),
+                Point::new(1.0, 0.0),
+                Point::new(3.0, 0.0),
+                Point::new(2.0, 4.0),
+                Point::new(0.0, 3.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_random_points_inside_hull() {
+        let points = Point::generate_random_points(500, -10.0, -10.0, 10.0, 10.0, 5);
+        let hull = convex_hull(&points);
+        for i in 0..hull.len() {
+            let (a, b) = (&hull[i], &hull[(i + 1) % hull.len()]);
+            assert!(points.iter().all(|p| orient2d(a, b, p) >= 0.0));
+        }
+    }
+}
@@ -0,0 +1,331 @@
+use super::locate::{Location, locate_point_in_polygon};
+use crate::geoms::line::{Line, distance_point_segment};
+use crate::geoms::multipolygon::MultiPolygon;
+use crate::geoms::point::Point;
+use crate::geoms::polygon::Polygon;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// Square cell of the polylabel search grid
+struct Cell {
+    center: Point,
+    /// half of the side length
+    half: f64,
+    /// signed distance from the center to the polygon boundary, negative outside
+    distance: f64,
+    /// upper bound of the distance of any point in the cell
+    potential: f64,
+}
+
+impl Cell {
+    fn new(center: Point, half: f64, polygon: &Polygon) -> Cell {
+        let distance = signed_distance(&center, polygon);
+        Cell {
+            center,
+            half,
+            distance,
+            potential: distance + half * std::f64::consts::SQRT_2,
+        }
+    }
+}
+
+impl PartialEq for Cell {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cell {}
+
+impl PartialOrd for Cell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cell {
+    /// cells with the highest potential come first out of the heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.potential.total_cmp(&other.potential)
+    }
+}
+
+/// distance from a point to the polygon boundary, positive inside and negative outside
+fn signed_distance(point: &Point, polygon: &Polygon) -> f64 {
+    let distance = std::iter::once(polygon.get_exterior())
+        .chain(polygon.get_interiors())
+        .flat_map(|ring| ring_segments(ring.get_points()))
+        .map(|(a, b)| distance_point_segment(point, &a, &b))
+        .fold(f64::INFINITY, f64::min);
+    if locate_point_in_polygon(point, polygon) == Location::Interior {
+        distance
+    } else {
+        -distance
+    }
+}
+
+/// segments of a ring, which may be given closed or open
+fn ring_segments(points: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    let closing = (points.first() != points.last()).then(|| (points[points.len() - 1], points[0]));
+    points
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .chain(closing)
+}
+
+/// centroid of the area enclosed by a ring, or its first point if the area is zero
+fn ring_centroid(points: &[Point]) -> Point {
+    let (mut area, mut x, mut y) = (0.0, 0.0, 0.0);
+    for (a, b) in ring_segments(points) {
+        let cross = a.get_x() * b.get_y() - b.get_x() * a.get_y();
+        area += cross;
+        x += (a.get_x() + b.get_x()) * cross;
+        y += (a.get_y() + b.get_y()) * cross;
+    }
+    if area == 0.0 {
+        return points[0];
+    }
+    Point::new(x / (3.0 * area), y / (3.0 * area))
+}
+
+/// the midpoint of the widest interior section of a horizontal line through the polygon, and its width.
+/// The line is placed between vertex heights, so it never passes a vertex.
+fn widest_scan_section(polygon: &Polygon) -> Option<(Point, f64)> {
+    let rings: Vec<&Line> = std::iter::once(polygon.get_exterior())
+        .chain(polygon.get_interiors())
+        .collect();
+    let exterior = polygon.get_exterior().get_points();
+    let (min_y, max_y) = exterior
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+            (lo.min(p.get_y()), hi.max(p.get_y()))
+        });
+    let center_y = (min_y + max_y) / 2.0;
+    let (mut low, mut high) = (min_y, max_y);
+    for point in rings.iter().flat_map(|ring| ring.get_points()) {
+        let y = point.get_y();
+        if y <= center_y && y > low {
+            low = y;
+        } else if y > center_y && y < high {
+            high = y;
+        }
+    }
+    let scan_y = (low + high) / 2.0;
+
+    let mut crossings: Vec<f64> = rings
+        .iter()
+        .flat_map(|ring| ring_segments(ring.get_points()))
+        .filter(|(a, b)| (a.get_y() > scan_y) != (b.get_y() > scan_y))
+        .map(|(a, b)| {
+            a.get_x() + (scan_y - a.get_y()) * (b.get_x() - a.get_x()) / (b.get_y() - a.get_y())
+        })
+        .collect();
+    crossings.sort_by(f64::total_cmp);
+    // the line is inside the polygon between the first and second crossing, the third and fourth, and so on
+    crossings
+        .chunks_exact(2)
+        .map(|pair| {
+            (
+                Point::new((pair[0] + pair[1]) / 2.0, scan_y),
+                pair[1] - pair[0],
+            )
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .filter(|(_, width)| *width > 0.0)
+}
+
+impl Polygon {
+    /// Compute the pole of inaccessibility: the interior point farthest from the boundary, which is a good
+    /// position for a label. Uses the polylabel algorithm, which starts with a single square cell over the bounding
+    /// box and keeps splitting the cells which may still contain a better point. The result is within `precision` of
+    /// the optimal distance. Return the point and its distance to the boundary (zero for polygons without area).
+    /// Panics if the precision is not positive.
+    pub fn pole_of_inaccessibility(&self, precision: f64) -> (Point, f64) {
+        if precision.is_nan() || precision <= 0.0 {
+            panic!("Precision must be positive.");
+        }
+        let points = self.get_exterior().get_points();
+        let (min, max) = self.get_exterior().bounding_box();
+        let (width, height) = (max.get_x() - min.get_x(), max.get_y() - min.get_y());
+        if width.min(height) == 0.0 {
+            return (points[0], 0.0);
+        }
+
+        // a single cell covering the bounding box, so long and thin polygons do not start with a huge grid
+        let mut queue = BinaryHeap::new();
+        queue.push(Cell::new(
+            Point::new(min.get_x() + width / 2.0, min.get_y() + height / 2.0),
+            width.max(height) / 2.0,
+            self,
+        ));
+
+        // start with the better one of the centroid and the bounding box center
+        let mut best = Cell::new(ring_centroid(points), 0.0, self);
+        let center = Cell::new(
+            Point::new(min.get_x() + width / 2.0, min.get_y() + height / 2.0),
+            0.0,
+            self,
+        );
+        if center.distance > best.distance {
+            best = center;
+        }
+
+        while let Some(cell) = queue.pop() {
+            let (center, potential) = (cell.center, cell.potential);
+            let half = cell.half / 2.0;
+            if cell.distance > best.distance {
+                best = cell;
+            }
+            if potential - best.distance <= precision {
+                continue;
+            }
+            for (dx, dy) in [(-1.0, -1.0), (1.0, -1.0), (-1.0, 1.0), (1.0, 1.0)] {
+                let center = Point::new(center.get_x() + dx * half, center.get_y() + dy * half);
+                queue.push(Cell::new(center, half, self));
+            }
+        }
+        (best.center, best.distance.max(0.0))
+    }
+
+    /// get a point which is guaranteed to lie inside the polygon. This is much cheaper than
+    /// [`Polygon::pole_of_inaccessibility`]: it is the middle of the widest section of a horizontal line through the
+    /// middle of the polygon. Falls back to the first point for polygons without area.
+    pub fn point_on_surface(&self) -> Point {
+        widest_scan_section(self)
+            .map(|(point, _)| point)
+            .unwrap_or(self.get_exterior().get_points()[0])
+    }
+}
+
+impl MultiPolygon {
+    /// get a point which is guaranteed to lie inside one of the polygons, see [`Polygon::point_on_surface`].
+    /// The widest section over all polygons is used. Return None for an empty multipolygon.
+    pub fn point_on_surface(&self) -> Option<Point> {
+        self.get_polygons()
+            .iter()
+            .filter_map(widest_scan_section)
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(point, _)| point)
+            .or_else(|| self.get_polygons().first().map(Polygon::point_on_surface))
+    }
+}
+
+impl Line {
+    /// get a point which is guaranteed to lie on the line: the inner vertex closest to the centroid of the vertices,
+    /// or the middle of the line for a single segment
+    pub fn point_on_surface(&self) -> Point {
+        let points = self.get_points();
+        let centroid = self.centroid();
+        points[1..points.len() - 1]
+            .iter()
+            .min_by(|p, q| p.distance2D(&centroid).total_cmp(&q.distance2D(&centroid)))
+            .copied()
+            .unwrap_or_else(|| {
+                Point::new(
+                    (points[0].get_x() + points[1].get_x()) / 2.0,
+                    (points[0].get_y() + points[1].get_y()) / 2.0,
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a U shape whose centroid lies in the gap between its arms
+    fn u_shape() -> Polygon {
+        Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 10, 8 10, 8 2, 2 2, 2 10, 0 10, 0 0))").unwrap()
+    }
+
+    #[test]
+    fn test_pole_of_square() {
+        let square = Polygon::from_wkt("POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0))").unwrap();
+        let (point, distance) = square.pole_of_inaccessibility(1e-6);
+        assert!(point.approx_eq(&Point::new(2.0, 2.0), 1e-6));
+        assert!((distance - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_pole_of_thin_polygon() {
+        let strip = Polygon::from_wkt("POLYGON ((0 0, 1000 0, 1000 0.001, 0 0.001, 0 0))").unwrap();
+        let (point, distance) = strip.pole_of_inaccessibility(0.1);
+        assert_eq!(locate_point_in_polygon(&point, &strip), Location::Interior);
+        assert!(distance > 0.0 && distance <= 0.0005);
+    }
+
+    #[test]
+    fn test_pole_of_concave_polygon() {
+        let polygon = u_shape();
+        let centroid = ring_centroid(polygon.get_exterior().get_points());
+        assert_eq!(
+            locate_point_in_polygon(&centroid, &polygon),
+            Location::Exterior
+        );
+        let (point, distance) = polygon.pole_of_inaccessibility(0.01);
+        assert_eq!(
+            locate_point_in_polygon(&point, &polygon),
+            Location::Interior
+        );
+        // the largest inscribed circle sits in an outer corner and touches the opposite inner corner
+        assert!((distance - (4.0 - 2.0 * std::f64::consts::SQRT_2)).abs() <= 0.01);
+        assert!((signed_distance(&point, &polygon) - distance).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pole_avoids_hole() {
+        let polygon =
+            Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (1 1, 9 1, 9 6, 1 6, 1 1))")
+                .unwrap();
+        let (point, distance) = polygon.pole_of_inaccessibility(0.001);
+        // the widest part lies between the hole and the top edge
+        assert!((distance - 2.0).abs() <= 0.001);
+        assert!((point.get_y() - 8.0).abs() < 0.01);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_precision() {
+        u_shape().pole_of_inaccessibility(0.0);
+    }
+
+    #[test]
+    fn test_point_on_surface() {
+        let polygon = u_shape();
+        let point = polygon.point_on_surface();
+        assert_eq!(
+            locate_point_in_polygon(&point, &polygon),
+            Location::Interior
+        );
+        let star = Polygon::from_wkt(
+            "POLYGON ((0 -10, 2 -2, 10 0, 2 2, 0 10, -2 2, -10 0, -2 -2, 0 -10), (-1 -1, 1 -1, 1 1, -1 1, -1 -1))",
+        )
+        .unwrap();
+        assert_eq!(
+            locate_point_in_polygon(&star.point_on_surface(), &star),
+            Location::Interior
+        );
+    }
+
+    #[test]
+    fn test_multipolygon_point_on_surface() {
+        let multipolygon = MultiPolygon::new(vec![
+            Polygon::from_wkt("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))").unwrap(),
+            u_shape(),
+        ]);
+        let point = multipolygon.point_on_surface().unwrap();
+        assert_eq!(
+            locate_point_in_polygon(&point, &u_shape()),
+            Location::Interior
+        );
+        assert_eq!(MultiPolygon::new(vec![]).point_on_surface(), None);
+    }
+
+    #[test]
+    fn test_line_point_on_surface() {
+        let line = Line::from_wkt("LINESTRING (0 0, 1 5, 2 0, 4 1)").unwrap();
+        assert_eq!(line.point_on_surface(), Point::new(2.0, 0.0));
+        let segment = Line::from_wkt("LINESTRING (0 0, 2 4)").unwrap();
+        assert_eq!(segment.point_on_surface(), Point::new(1.0, 2.0));
+    }
+}
@@ -0,0 +1,178 @@
+use super::predicates::orient2d;
+use crate::geoms::point::Point;
+
+/// Result of intersecting two segments
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SegmentIntersection {
+    /// the segments do not intersect
+    None,
+    /// the segments intersect in a single point
+    Point(Point),
+    /// the segments are collinear and overlap between two points
+    Overlap(Point, Point),
+}
+
+/// check if a point collinear with the segment from `a` to `b` lies within the segment
+fn within_segment_box(point: &Point, a: &Point, b: &Point) -> bool {
+    point.get_x() >= a.get_x().min(b.get_x())
+        && point.get_x() <= a.get_x().max(b.get_x())
+        && point.get_y() >= a.get_y().min(b.get_y())
+        && point.get_y() <= a.get_y().max(b.get_y())
+}
+
+/// check if a point lies on the segment from `a` to `b` (end points included)
+pub fn point_on_segment(point: &Point, a: &Point, b: &Point) -> bool {
+    orient2d(a, b, point) == 0.0 && within_segment_box(point, a, b)
+}
+
+/// Intersect the segment from `a` to `b` with the segment from `c` to `d`.
+/// End points are returned exactly if the segments touch, proper crossings are computed.
+pub fn segment_intersection(a: &Point, b: &Point, c: &Point, d: &Point) -> SegmentIntersection {
+    let o1 = orient2d(a, b, c);
+    let o2 = orient2d(a, b, d);
+    let o3 = orient2d(c, d, a);
+    let o4 = orient2d(c, d, b);
+
+    if o1 == 0.0 && o2 == 0.0 && o3 == 0.0 && o4 == 0.0 {
+        return collinear_intersection(a, b, c, d);
+    }
+    if (o1 > 0.0 && o2 > 0.0)
+        || (o1 < 0.0 && o2 < 0.0)
+        || (o3 > 0.0 && o4 > 0.0)
+        || (o3 < 0.0 && o4 < 0.0)
+    {
+        return SegmentIntersection::None;
+    }
+    // touching end points are returned exactly
+    if o1 == 0.0 && within_segment_box(c, a, b) {
+        return SegmentIntersection::Point(*c);
+    }
+    if o2 == 0.0 && within_segment_box(d, a, b) {
+        return SegmentIntersection::Point(*d);
+    }
+    if o3 == 0.0 && within_segment_box(a, c, d) {
+        return SegmentIntersection::Point(*a);
+    }
+    if o4 == 0.0 && within_segment_box(b, c, d) {
+        return SegmentIntersection::Point(*b);
+    }
+    if o1 == 0.0 || o2 == 0.0 || o3 == 0.0 || o4 == 0.0 {
+        return SegmentIntersection::None;
+    }
+    let t = o3 / (o3 - o4);
+    SegmentIntersection::Point(Point::new(
+        a.get_x() + t * (b.get_x() - a.get_x()),
+        a.get_y() + t * (b.get_y() - a.get_y()),
+    ))
+}
+
+/// intersection of two segments lying on the same line
+fn collinear_intersection(a: &Point, b: &Point, c: &Point, d: &Point) -> SegmentIntersection {
+    let mut shared: Vec<Point> = [a, b, c, d]
+        .into_iter()
+        .filter(|p| within_segment_box(p, a, b) && within_segment_box(p, c, d))
+        .copied()
+        .collect();
+    // order the shared end points along the common line
+    let (dx, dy) = if a != b {
+        (b.get_x() - a.get_x(), b.get_y() - a.get_y())
+    } else {
+        (d.get_x() - c.get_x(), d.get_y() - c.get_y())
+    };
+    shared.sort_by(|p, q| {
+        (p.get_x() * dx + p.get_y() * dy).total_cmp(&(q.get_x() * dx + q.get_y() * dy))
+    });
+    shared.dedup();
+    match shared.len() {
+        0 => SegmentIntersection::None,
+        1 => SegmentIntersection::Point(shared[0]),
+        n => SegmentIntersection::Overlap(shared[0], shared[n - 1]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proper_crossing() {
+        let result = segment_intersection(
+            &Point::new(0.0, 0.0),
+            &Point::new(2.0, 2.0),
+            &Point::new(0.0, 2.0),
+            &Point::new(2.0, 0.0),
+        );
+        assert_eq!(result, SegmentIntersection::Point(Point::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_disjoint_and_parallel() {
+        let result = segment_intersection(
+            &Point::new(0.0, 0.0),
+            &Point::new(1.0, 0.0),
+            &Point::new(0.0, 1.0),
+            &Point::new(1.0, 1.0),
+        );
+        assert_eq!(result, SegmentIntersection::None);
+        let result = segment_intersection(
+            &Point::new(0.0, 0.0),
+            &Point::new(1.0, 1.0),
+            &Point::new(2.0, 0.0),
+            &Point::new(3.0, -5.0),
+        );
+        assert_eq!(result, SegmentIntersection::None);
+    }
+
+    #[test]
+    fn test_touching_end_point() {
+        let result = segment_intersection(
+            &Point::new(0.0, 0.0),
+            &Point::new(2.0, 0.0),
+            &Point::new(1.0, 0.0),
+            &Point::new(1.0, 5.0),
+        );
+        assert_eq!(result, SegmentIntersection::Point(Point::new(1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_collinear_overlap() {
+        let result = segment_intersection(
+            &Point::new(0.0, 0.0),
+            &Point::new(4.0, 0.0),
+            &Point::new(5.0, 0.0),
+            &Point::new(2.0, 0.0),
+        );
+        assert_eq!(
+            result,
+            SegmentIntersection::Overlap(Point::new(2.0, 0.0), Point::new(4.0, 0.0))
+        );
+        let result = segment_intersection(
+            &Point::new(0.0, 0.0),
+            &Point::new(1.0, 1.0),
+            &Point::new(1.0, 1.0),
+            &Point::new(3.0, 3.0),
+        );
+        assert_eq!(result, SegmentIntersection::Point(Point::new(1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_nearly_collinear_disjoint() {
+        // pieces of one segment after noding, the far end point is numerically on the other piece's line
+        let result = segment_intersection(
+            &Point::new(4.743589743589744, 4.948717948717949),
+            &Point::new(6.636363636363637, 1.6363636363636362),
+            &Point::new(3.0, 8.0),
+            &Point::new(4.714285714285714, 5.0),
+        );
+        assert_eq!(result, SegmentIntersection::None);
+    }
+
+    #[test]
+    fn test_point_on_segment() {
+        let a = Point::new(0.0, 0.0);
+        let b = Point::new(2.0, 4.0);
+        assert!(point_on_segment(&Point::new(1.0, 2.0), &a, &b));
+        assert!(point_on_segment(&b, &a, &b));
+        assert!(!point_on_segment(&Point::new(3.0, 6.0), &a, &b));
+    }
+}
@@ -0,0 +1,174 @@
+use super::intersection::point_on_segment;
+use super::predicates::orient2d;
+use crate::geoms::Geometry;
+use crate::geoms::line::Line;
+use crate::geoms::point::Point;
+use crate::geoms::polygon::Polygon;
+
+/// Location of a point relative to an areal geometry
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Location {
+    /// the point lies inside the geometry
+    Interior,
+    /// the point lies on the boundary of the geometry
+    Boundary,
+    /// the point lies outside the geometry
+    Exterior,
+}
+
+/// Locate a point relative to a ring. The ring may be given closed or open.
+pub fn locate_point_in_ring(point: &Point, ring: &[Point]) -> Location {
+    let n = ring.len();
+    if n == 0 {
+        return Location::Exterior;
+    }
+    let mut inside = false;
+    for i in 0..n {
+        let a = &ring[i];
+        let b = &ring[(i + 1) % n];
+        if point_on_segment(point, a, b) {
+            return Location::Boundary;
+        }
+        // crossing number: count edges crossed by a ray from the point to the right,
+        // which are the edges passing the point on its right side
+        if (a.get_y() > point.get_y()) != (b.get_y() > point.get_y()) {
+            let side = orient2d(a, b, point);
+            if (b.get_y() > a.get_y() && side > 0.0) || (b.get_y() < a.get_y() && side < 0.0) {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        Location::Interior
+    } else {
+        Location::Exterior
+    }
+}
+
+/// Locate a point relative to a polygon, taking its holes into account
+pub fn locate_point_in_polygon(point: &Point, polygon: &Polygon) -> Location {
+    match locate_point_in_ring(point, polygon.get_exterior().get_points()) {
+        Location::Interior => {}
+        location => return location,
+    }
+    for hole in polygon.get_interiors() {
+        match locate_point_in_ring(point, hole.get_points()) {
+            Location::Interior => return Location::Exterior,
+            Location::Boundary => return Location::Boundary,
+            Location::Exterior => {}
+        }
+    }
+    Location::Interior
+}
+
+/// Locate a point relative to a line. The end points of a line which is not closed form its boundary.
+pub fn locate_point_on_line(point: &Point, line: &Line) -> Location {
+    let points = line.get_points();
+    if points.first() != points.last()
+        && (points.first() == Some(point) || points.last() == Some(point))
+    {
+        return Location::Boundary;
+    }
+    if points
+        .windows(2)
+        .any(|pair| point_on_segment(point, &pair[0], &pair[1]))
+    {
+        Location::Interior
+    } else {
+        Location::Exterior
+    }
+}
+
+/// Locate a point relative to any geometry
+pub fn locate_point_in_geometry(point: &Point, geometry: &Geometry) -> Location {
+    match geometry {
+        Geometry::Point(other) => {
+            if other == point {
+                Location::Interior
+            } else {
+                Location::Exterior
+            }
+        }
+        Geometry::Line(line) => locate_point_on_line(point, line),
+        Geometry::Polygon(polygon) => locate_point_in_polygon(point, polygon),
+        Geometry::MultiPolygon(multipolygon) => {
+            let mut location = Location::Exterior;
+            for polygon in multipolygon.get_polygons() {
+                match locate_point_in_polygon(point, polygon) {
+                    Location::Interior => return Location::Interior,
+                    Location::Boundary => location = Location::Boundary,
+                    Location::Exterior => {}
+                }
+            }
+            location
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_in_ring() {
+        let ring = vec![
+            Point::new(0.0, 0.0),
+            Point::new(4.0, 0.0),
+            Point::new(4.0, 4.0),
+            Point::new(0.0, 4.0),
+        ];
+        assert_eq!(
+            locate_point_in_ring(&Point::new(2.0, 2.0), &ring),
+            Location::Interior
+        );
+        assert_eq!(
+            locate_point_in_ring(&Point::new(0.0, 2.0), &ring),
+            Location::Boundary
+        );
+        assert_eq!(
+            locate_point_in_ring(&Point::new(5.0, 4.0), &ring),
+            Location::Exterior
+        );
+    }
+
+    #[test]
+    fn test_locate_in_polygon_with_hole() {
+        let polygon =
+            Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 8, 8 8, 8 2, 2 2))")
+                .unwrap();
+        assert_eq!(
+            locate_point_in_polygon(&Point::new(1.0, 1.0), &polygon),
+            Location::Interior
+        );
+        assert_eq!(
+            locate_point_in_polygon(&Point::new(5.0, 5.0), &polygon),
+            Location::Exterior
+        );
+        assert_eq!(
+            locate_point_in_polygon(&Point::new(2.0, 5.0), &polygon),
+            Location::Boundary
+        );
+    }
+
+    #[test]
+    fn test_locate_on_line() {
+        let line = Line::from_wkt("LINESTRING (0 0, 2 0, 2 2)").unwrap();
+        assert_eq!(
+            locate_point_on_line(&Point::new(0.0, 0.0), &line),
+            Location::Boundary
+        );
+        assert_eq!(
+            locate_point_on_line(&Point::new(2.0, 1.0), &line),
+            Location::Interior
+        );
+        assert_eq!(
+            locate_point_on_line(&Point::new(1.0, 1.0), &line),
+            Location::Exterior
+        );
+        let ring = Line::from_wkt("LINESTRING (0 0, 2 0, 2 2, 0 0)").unwrap();
+        assert_eq!(
+            locate_point_on_line(&Point::new(0.0, 0.0), &ring),
+            Location::Interior
+        );
+    }
+}
@@ -0,0 +1,422 @@
+use super::locate::{Location, locate_point_in_ring};
+use super::noding::node_segments_snap_rounded_with_source;
+use super::precision::PrecisionModel;
+use super::predicates::{orient_directions, orient2d};
+use super::validity::build_even_odd_from_segments;
+use crate::geoms::line::{Line, ring_signed_area};
+use crate::geoms::multipolygon::MultiPolygon;
+use crate::geoms::point::{Point, PointKey};
+use crate::geoms::polygon::Polygon;
+use crate::geoms::rect::Rect;
+use crate::index::HasBoundingBox;
+use crate::index::rtree::RTree;
+use std::collections::{HashMap, HashSet};
+
+/// Compute the Minkowski sum of two convex rings in linear time, by merging the edges of both rings in the order of
+/// their angle. The rings may be given open or closed and in either direction.
+/// Return the sum as an open counter-clockwise ring without collinear points, starting at the lowest-leftmost point.
+pub fn convex_minkowski_sum(a: &[Point], b: &[Point]) -> Vec<Point> {
+    let (a, b) = (
+        counter_clockwise_from_lowest(a),
+        counter_clockwise_from_lowest(b),
+    );
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let (n, m) = (a.len(), b.len());
+    let (mut i, mut j) = (0, 0);
+    let mut sum: Vec<Point> = Vec::with_capacity(n + m);
+    while i < n || j < m {
+        let (p, q) = (&a[i % n], &b[j % m]);
+        let vertex = Point::new(p.get_x() + q.get_x(), p.get_y() + q.get_y());
+        while sum.len() >= 2 && orient2d(&sum[sum.len() - 2], &sum[sum.len() - 1], &vertex) == 0.0 {
+            sum.pop();
+        }
+        sum.push(vertex);
+        // take the edge which turns less, or both if they are parallel
+        let (p_next, q_next) = (&a[(i + 1) % n], &b[(j + 1) % m]);
+        let cross = orient_directions(p, p_next, q, q_next);
+        if cross >= 0.0 && i < n {
+            i += 1;
+        }
+        if cross <= 0.0 && j < m {
+            j += 1;
+        }
+    }
+    // the closing vertex may be collinear with its neighbours
+    while sum.len() >= 3 && orient2d(&sum[sum.len() - 2], &sum[sum.len() - 1], &sum[0]) == 0.0 {
+        sum.pop();
+    }
+    sum
+}
+
+/// Compute the Minkowski sum of two polygons: the set of all sums of a point of `a` and a point of `b`, e.g. the
+/// area swept by a robot footprint `b` moved over an obstacle `a`. Two convex polygons without holes are added in
+/// linear time with [`convex_minkowski_sum`]. Otherwise the polygons are triangulated, the triangles are added
+/// pairwise and the union of these convex pieces is returned, which may contain holes.
+pub fn minkowski_sum(a: &Polygon, b: &Polygon) -> MultiPolygon {
+    if let (Some(ring_a), Some(ring_b)) = (convex_ring(a), convex_ring(b)) {
+        let sum = convex_minkowski_sum(&ring_a, &ring_b);
+        if sum.len() < 3 {
+            return MultiPolygon::new(vec![]);
+        }
+        return MultiPolygon::new(vec![Polygon::new(closed_line(sum), vec![])]);
+    }
+    let (pieces_a, pieces_b) = (convex_pieces(a), convex_pieces(b));
+    let pieces: Vec<Vec<Point>> = pieces_a
+        .iter()
+        .flat_map(|p| pieces_b.iter().map(move |q| convex_minkowski_sum(p, q)))
+        .filter(|piece| piece.len() >= 3)
+        .collect();
+    union_of_convex_rings(&pieces)
+}
+
+/// Compute the Minkowski difference `a ⊕ (-b)`: the sum of `a` and `b` reflected at the origin. It contains the
+/// origin exactly if the polygons intersect, and in path planning it is the set of positions where the reference
+/// point of a robot with footprint `b` would collide with the obstacle `a`.
+pub fn minkowski_difference(a: &Polygon, b: &Polygon) -> MultiPolygon {
+    let reflect = |ring: &Line| {
+        Line::new(
+            ring.get_points()
+                .iter()
+                .map(|p| Point::new(-p.get_x(), -p.get_y()))
+                .collect(),
+        )
+    };
+    let reflected = Polygon::new(
+        reflect(b.get_exterior()),
+        b.get_interiors().iter().map(reflect).collect(),
+    );
+    minkowski_sum(a, &reflected)
+}
+
+/// the open ring of a convex polygon without holes, or None if the polygon is not convex
+fn convex_ring(polygon: &Polygon) -> Option<Vec<Point>> {
+    if polygon.get_number_of_interiors() > 0 {
+        return None;
+    }
+    let ring = counter_clockwise_from_lowest(polygon.get_exterior().get_points());
+    let n = ring.len();
+    if n < 3 {
+        return None;
+    }
+    // all turns go left, and only once around: a star polygon turns left at every vertex too
+    let mut turning = 0.0;
+    for i in 0..n {
+        let (previous, point, next) = (&ring[(i + n - 1) % n], &ring[i], &ring[(i + 1) % n]);
+        if orient2d(previous, point, next) < 0.0 {
+            return None;
+        }
+        let (ux, uy) = (
+            point.get_x() - previous.get_x(),
+            point.get_y() - previous.get_y(),
+        );
+        let (vx, vy) = (next.get_x() - point.get_x(), next.get_y() - point.get_y());
+        turning += (ux * vy - uy * vx).atan2(ux * vx + uy * vy);
+    }
+    (turning < 3.0 * std::f64::consts::PI).then_some(ring)
+}
+
+/// triangles of a polygon as open rings, leaving out triangles without area
+fn convex_pieces(polygon: &Polygon) -> Vec<Vec<Point>> {
+    if let Some(ring) = convex_ring(polygon) {
+        return vec![ring];
+    }
+    let vertices = polygon.vertices();
+    polygon
+        .triangulate()
+        .iter()
+        .map(|triangle| {
+            triangle
+                .iter()
+                .map(|&i| vertices[i])
+                .collect::<Vec<Point>>()
+        })
+        .filter(|triangle| ring_signed_area(triangle) != 0.0)
+        .collect()
+}
+
+/// convex ring which has been added to a union, indexed by its bounding box
+struct Piece {
+    ring: Vec<Point>,
+    envelope: Rect,
+}
+
+impl HasBoundingBox for Piece {
+    fn envelope(&self) -> Rect {
+        self.envelope
+    }
+}
+
+/// Union of convex counter-clockwise rings, built by adding one ring after the other. The boundary of the union so
+/// far is kept as directed edges with the union on their left in an R-tree, so every ring is only noded against the
+/// edges near it and the edges inside the union are dropped right away instead of piling up.
+///
+/// After noding, a boundary edge stays if it does not run inside the new ring, and an edge of the new ring is added
+/// if it does not run inside or along one of the rings added before. Edges shared in opposite directions separate
+/// the union from the new ring and both are dropped. The noding snap rounds to a grid far below the size of the
+/// rings, so an edge passing a vertex of the boundary within rounding noise is split there and runs along the
+/// boundary edges it overlaps instead of crossing them.
+fn union_of_convex_rings(rings: &[Vec<Point>]) -> MultiPolygon {
+    let all: Vec<Point> = rings.iter().flatten().copied().collect();
+    let Some(extent) = Rect::from_points(&all) else {
+        return MultiPolygon::new(vec![]);
+    };
+    let size = extent
+        .get_width()
+        .max(extent.get_height())
+        .max(f64::MIN_POSITIVE);
+    // a power of two keeps coordinates on coarser grids, like integers, exact
+    let grid_size = 2f64.powi(size.log2().ceil() as i32 - 40);
+    let precision = PrecisionModel::fixed(grid_size);
+
+    let mut edges: RTree<Line> = RTree::new();
+    let mut pieces: RTree<Piece> = RTree::new();
+    for ring in rings {
+        let mut ring: Vec<Point> = ring.iter().map(|p| precision.snap_point(p)).collect();
+        ring.dedup();
+        let Some(envelope) = Rect::from_points(&ring) else {
+            continue;
+        };
+        let nearby: Vec<Line> = edges
+            .query(&envelope.expand_by(grid_size))
+            .into_iter()
+            .cloned()
+            .collect();
+        for edge in &nearby {
+            edges.remove(edge);
+        }
+        let mut segments: Vec<(Point, Point)> = nearby
+            .iter()
+            .map(|edge| (edge.get_start(), edge.get_end()))
+            .collect();
+        let n = ring.len();
+        segments.extend((0..n).map(|i| (ring[i], ring[(i + 1) % n])));
+        let noded = node_segments_snap_rounded_with_source(&segments, &precision);
+
+        // whether every directed edge is part of the old boundary and whether it is an edge of the new ring
+        let mut sides: HashMap<(PointKey, PointKey), (bool, bool)> = HashMap::new();
+        for (p, q, source) in &noded {
+            let side = sides.entry((p.key(), q.key())).or_default();
+            if *source < nearby.len() {
+                side.0 = true;
+            } else {
+                side.1 = true;
+            }
+        }
+        let mut seen = HashSet::new();
+        for (p, q, _) in &noded {
+            let key = (p.key(), q.key());
+            if !seen.insert(key) || sides.contains_key(&(key.1, key.0)) {
+                continue;
+            }
+            let midpoint = Point::new((p.get_x() + q.get_x()) / 2.0, (p.get_y() + q.get_y()) / 2.0);
+            let covered = match sides[&key] {
+                // the union and the new ring lie on the same side
+                (true, true) => false,
+                (true, false) => locate_point_in_ring(&midpoint, &ring) == Location::Interior,
+                _ => pieces.query(&midpoint.envelope()).iter().any(|piece| {
+                    locate_point_in_ring(&midpoint, &piece.ring) != Location::Exterior
+                }),
+            };
+            if !covered {
+                edges.insert(Line::new(vec![*p, *q]));
+            }
+        }
+        pieces.insert(Piece { ring, envelope });
+    }
+
+    let boundary: Vec<(Point, Point)> = edges
+        .iter()
+        .map(|edge| (edge.get_start(), edge.get_end()))
+        .collect();
+    let union = build_even_odd_from_segments(&boundary, &PrecisionModel::floating());
+    MultiPolygon::new(
+        union
+            .get_polygons()
+            .iter()
+            .map(|polygon| {
+                Polygon::new(
+                    without_collinear_points(polygon.get_exterior()),
+                    polygon
+                        .get_interiors()
+                        .iter()
+                        .map(without_collinear_points)
+                        .collect(),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// the open ring without duplicate points, counter-clockwise and starting at its lowest-leftmost point
+fn counter_clockwise_from_lowest(points: &[Point]) -> Vec<Point> {
+    let mut ring = points.to_vec();
+    ring.dedup();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if ring_signed_area(&ring) < 0.0 {
+        ring.reverse();
+    }
+    if let Some(lowest) = (0..ring.len()).min_by(|&i, &j| {
+        let (p, q) = (&ring[i], &ring[j]);
+        p.get_y()
+            .total_cmp(&q.get_y())
+            .then(p.get_x().total_cmp(&q.get_x()))
+    }) {
+        ring.rotate_left(lowest);
+    }
+    ring
+}
+
+/// a closed ring without the vertices which lie on a straight line between their neighbours
+fn without_collinear_points(ring: &Line) -> Line {
+    let points = ring.get_points();
+    let open = &points[..points.len() - 1];
+    let n = open.len();
+    let kept: Vec<Point> = (0..n)
+        .filter(|&i| orient2d(&open[(i + n - 1) % n], &open[i], &open[(i + 1) % n]) != 0.0)
+        .map(|i| open[i])
+        .collect();
+    closed_line(kept)
+}
+
+fn closed_line(mut ring: Vec<Point>) -> Line {
+    ring.push(ring[0]);
+    Line::new(ring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::distance::distance;
+    use crate::algorithms::locate::locate_point_in_polygon;
+    use crate::algorithms::relate::equals;
+
+    fn polygon(wkt: &str) -> Polygon {
+        Polygon::from_wkt(wkt).unwrap()
+    }
+
+    fn assert_equals(result: MultiPolygon, expected: &str) {
+        assert!(
+            equals(&result.clone().into(), &polygon(expected).into()),
+            "{}",
+            result.to_wkt()
+        );
+    }
+
+    #[test]
+    fn test_convex_sum() {
+        let square = polygon("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))");
+        // given clockwise
+        let triangle = polygon("POLYGON ((0 0, 0 1, 1 0, 0 0))");
+        let sum = convex_minkowski_sum(
+            square.get_exterior().get_points(),
+            triangle.get_exterior().get_points(),
+        );
+        assert_eq!(
+            sum,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(2.0, 1.0),
+                Point::new(1.0, 2.0),
+                Point::new(0.0, 2.0),
+            ]
+        );
+        let result = minkowski_sum(&square, &triangle);
+        assert_eq!(result.get_polygons().len(), 1);
+        assert_equals(result, "POLYGON ((0 0, 2 0, 2 1, 1 2, 0 2, 0 0))");
+    }
+
+    #[test]
+    fn test_non_convex_sum() {
+        let l_shape = polygon("POLYGON ((0 0, 2 0, 2 1, 1 1, 1 2, 0 2, 0 0))");
+        let square = polygon("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))");
+        let result = minkowski_sum(&l_shape, &square);
+        assert_eq!(
+            result.get_polygons()[0]
+                .get_exterior()
+                .get_number_of_points(),
+            7
+        );
+        assert_equals(result, "POLYGON ((0 0, 3 0, 3 2, 2 2, 2 3, 0 3, 0 0))");
+    }
+
+    #[test]
+    fn test_sum_keeps_holes() {
+        let frame = polygon("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 8, 8 8, 8 2, 2 2))");
+        let square = polygon("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))");
+        assert_equals(
+            minkowski_sum(&frame, &square),
+            "POLYGON ((0 0, 11 0, 11 11, 0 11, 0 0), (3 3, 3 8, 8 8, 8 3, 3 3))",
+        );
+        // a large footprint closes the hole
+        let large = polygon("POLYGON ((0 0, 7 0, 7 7, 0 7, 0 0))");
+        assert_equals(
+            minkowski_sum(&frame, &large),
+            "POLYGON ((0 0, 17 0, 17 17, 0 17, 0 0))",
+        );
+    }
+
+    #[test]
+    fn test_star_is_not_convex() {
+        let star = polygon("POLYGON ((0 0, 2 6, 4 0, -1 4, 5 4, 0 0))");
+        assert!(convex_ring(&star).is_none());
+        assert!(convex_ring(&polygon("POLYGON ((0 0, 1 0, 2 0, 1 1, 0 0))")).is_some());
+    }
+
+    /// star with the given number of spikes around the origin, rotated by an angle in radians
+    fn star(spikes: usize, radius: f64, rotation: f64) -> Polygon {
+        let mut points: Vec<Point> = (0..2 * spikes)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::PI / spikes as f64 + rotation;
+                let r = if i % 2 == 0 { radius } else { 0.4 * radius };
+                Point::new(r * angle.cos(), r * angle.sin())
+            })
+            .collect();
+        points.push(points[0]);
+        Polygon::new(Line::new(points), vec![])
+    }
+
+    #[test]
+    fn test_sum_of_stars() {
+        // 38 x 38 triangles, adding up all their pairwise sums at once took more than half a minute
+        let (a, b) = (star(20, 10.0, 0.0), star(20, 3.0, 0.1));
+        let sum = minkowski_sum(&a, &b);
+        assert_eq!(sum.get_polygons().len(), 1);
+        assert_eq!(sum.get_polygons()[0].get_number_of_interiors(), 0);
+        // the sum contains a copy of a moved to every point of b, up to the rounding of the noding
+        let area = sum.into();
+        for q in b.get_exterior().get_points() {
+            for p in a.get_exterior().get_points() {
+                let moved = Point::new(p.get_x() + q.get_x(), p.get_y() + q.get_y());
+                assert!(distance(&area, &moved.into()).unwrap() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_difference() {
+        let square = polygon("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))");
+        assert_equals(
+            minkowski_difference(&square, &square),
+            "POLYGON ((-1 -1, 1 -1, 1 1, -1 1, -1 -1))",
+        );
+        // the difference contains the origin exactly if the polygons intersect
+        let origin = Point::new(0.0, 0.0);
+        let overlapping = polygon("POLYGON ((0.5 0.5, 3 0.5, 3 3, 0.5 0.5))");
+        let apart = polygon("POLYGON ((2 2, 3 2, 3 3, 2 2))");
+        let difference = minkowski_difference(&square, &overlapping);
+        assert_eq!(
+            locate_point_in_polygon(&origin, &difference.get_polygons()[0]),
+            Location::Interior
+        );
+        let difference = minkowski_difference(&square, &apart);
+        assert_eq!(
+            locate_point_in_polygon(&origin, &difference.get_polygons()[0]),
+            Location::Exterior
+        );
+    }
+}
@@ -0,0 +1,38 @@
+/// Minimum bounding geometries
+pub mod bounding;
+/// Delaunay triangulation of point sets
+pub mod delaunay;
+/// Minimum distance and nearest points between geometries
+pub mod distance;
+/// Condensed symmetric distance matrices of point sets
+pub mod distance_matrix;
+/// Convex hulls
+pub mod hull;
+/// Interior points of polygons and lines for labelling
+pub mod interior;
+/// Segment intersections
+pub mod intersection;
+/// Location of points relative to rings and polygons
+pub mod locate;
+/// Minkowski sums and differences of polygons
+pub mod minkowski;
+/// Nearest neighbour graphs of point sets
+pub mod neighbors;
+/// Splitting segments at their intersections
+pub mod noding;
+/// Precision models and snapping of coordinates to a grid
+pub mod precision;
+/// Robust geometric predicates
+pub mod predicates;
+/// DE-9IM spatial relations and named predicates
+pub mod relate;
+/// Hausdorff and Fréchet distances between lines and point sets
+pub mod similarity;
+/// Straight skeletons, inward offsets and medial axes of polygons
+pub mod skeleton;
+/// Polygon triangulation
+pub mod triangulation;
+/// Geometry validity checks and repair
+pub mod validity;
+/// Voronoi diagrams
+pub mod voronoi;
@@ -0,0 +1,217 @@
+use crate::geoms::point::Point;
+use crate::index::kdtree::KdTree;
+
+/// How the neighbour lists of a k-nearest neighbour graph relate to each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnnSymmetry {
+    /// every point keeps its own k nearest neighbours
+    Directed,
+    /// j is a neighbour of i if either one is among the k nearest of the other
+    Union,
+    /// j is a neighbour of i only if both are among the k nearest of each other (mutual kNN)
+    Mutual,
+}
+
+/// Options of a k-nearest neighbour graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KnnOptions {
+    /// leave out the point itself
+    pub exclude_self: bool,
+    /// leave out all points with exactly the same coordinates as the point
+    pub exclude_duplicates: bool,
+    /// whether neighbour relations are kept one-sided, made symmetric or only kept if mutual
+    pub symmetry: KnnSymmetry,
+}
+
+impl Default for KnnOptions {
+    /// by default the point itself is left out, duplicates are kept and the graph is directed
+    fn default() -> Self {
+        KnnOptions {
+            exclude_self: true,
+            exclude_duplicates: false,
+            symmetry: KnnSymmetry::Directed,
+        }
+    }
+}
+
+impl Point {
+    /// Get the nearest other point of every point as its index and distance, None if there is no other point.
+    /// Duplicates of a point count as its nearest neighbours at distance 0.
+    pub fn all_nearest_neighbors(points: &[Point]) -> Vec<Option<(usize, f64)>> {
+        Point::knn_graph(points, 1, KnnOptions::default())
+            .into_iter()
+            .map(|neighbors| neighbors.first().copied())
+            .collect()
+    }
+
+    /// Get the k nearest neighbours of every point as indices and distances, nearest first and ties ordered by
+    /// index. A KD-tree is used instead of the full distance matrix, so this takes O(n k log n) time and O(n k)
+    /// memory. With [`KnnSymmetry::Union`] lists may hold more than k neighbours, with [`KnnSymmetry::Mutual`]
+    /// fewer.
+    pub fn knn_graph(points: &[Point], k: usize, options: KnnOptions) -> Vec<Vec<(usize, f64)>> {
+        let tree = KdTree::new(points);
+        let mut graph: Vec<Vec<(usize, f64)>> = points
+            .iter()
+            .enumerate()
+            .map(|(i, point)| {
+                // ask for enough extra neighbours to make up for the excluded ones
+                let skipped = if options.exclude_duplicates {
+                    tree.within_radius(point, 0.0).len()
+                } else {
+                    options.exclude_self as usize
+                };
+                let mut neighbors: Vec<(usize, f64)> = tree
+                    .k_nearest(point, k.saturating_add(skipped))
+                    .into_iter()
+                    .filter(|&(j, distance)| {
+                        !(options.exclude_self && j == i
+                            || options.exclude_duplicates && j != i && distance == 0.0)
+                    })
+                    .collect();
+                neighbors.truncate(k);
+                neighbors
+            })
+            .collect();
+        match options.symmetry {
+            KnnSymmetry::Directed => {}
+            KnnSymmetry::Union => {
+                let mut reverse = vec![Vec::new(); points.len()];
+                for (i, neighbors) in graph.iter().enumerate() {
+                    for &(j, distance) in neighbors {
+                        if !graph[j].iter().any(|&(n, _)| n == i) {
+                            reverse[j].push((i, distance));
+                        }
+                    }
+                }
+                for (neighbors, mut extra) in graph.iter_mut().zip(reverse) {
+                    neighbors.append(&mut extra);
+                    neighbors.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+                }
+            }
+            KnnSymmetry::Mutual => {
+                let directed = graph.clone();
+                for (i, neighbors) in graph.iter_mut().enumerate() {
+                    neighbors.retain(|&(j, _)| directed[j].iter().any(|&(n, _)| n == i));
+                }
+            }
+        }
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// k nearest neighbours of every point from the full distance matrix
+    fn brute_force(points: &[Point], k: usize) -> Vec<Vec<(usize, f64)>> {
+        Point::pairwise_distances(points)
+            .into_iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let mut neighbors: Vec<(usize, f64)> = row
+                    .into_iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .collect();
+                neighbors.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+                neighbors.truncate(k);
+                neighbors
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_all_nearest_neighbors() {
+        let points = Point::generate_random_points(300, 0.0, 0.0, 50.0, 50.0, 21);
+        let expected: Vec<Option<(usize, f64)>> = brute_force(&points, 1)
+            .into_iter()
+            .map(|neighbors| neighbors.first().copied())
+            .collect();
+        assert_eq!(Point::all_nearest_neighbors(&points), expected);
+        assert_eq!(
+            Point::all_nearest_neighbors(&[Point::new(1.0, 1.0)]),
+            vec![None]
+        );
+        assert!(Point::all_nearest_neighbors(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_knn_graph() {
+        let points = Point::generate_random_points(300, 0.0, 0.0, 50.0, 50.0, 22);
+        let directed = Point::knn_graph(&points, 5, KnnOptions::default());
+        assert_eq!(directed, brute_force(&points, 5));
+
+        let union = Point::knn_graph(
+            &points,
+            5,
+            KnnOptions {
+                symmetry: KnnSymmetry::Union,
+                ..KnnOptions::default()
+            },
+        );
+        let mutual = Point::knn_graph(
+            &points,
+            5,
+            KnnOptions {
+                symmetry: KnnSymmetry::Mutual,
+                ..KnnOptions::default()
+            },
+        );
+        let has_edge = |graph: &Vec<Vec<(usize, f64)>>, i: usize, j: usize| {
+            graph[i].iter().any(|&(n, _)| n == j)
+        };
+        for i in 0..points.len() {
+            for j in 0..points.len() {
+                let (ij, ji) = (has_edge(&directed, i, j), has_edge(&directed, j, i));
+                assert_eq!(has_edge(&union, i, j), ij || ji);
+                assert_eq!(has_edge(&mutual, i, j), ij && ji);
+            }
+            assert!(union[i].windows(2).all(|w| w[0].1 <= w[1].1));
+        }
+    }
+
+    #[test]
+    fn test_knn_graph_duplicates() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 0.0),
+            Point::new(3.0, 0.0),
+        ];
+        let with_self = Point::knn_graph(
+            &points,
+            2,
+            KnnOptions {
+                exclude_self: false,
+                ..KnnOptions::default()
+            },
+        );
+        assert_eq!(with_self[2], vec![(2, 0.0), (0, 1.0)]);
+        assert_eq!(
+            Point::knn_graph(&points, 2, KnnOptions::default())[3],
+            vec![(0, 0.0), (1, 0.0)]
+        );
+        let without_duplicates = Point::knn_graph(
+            &points,
+            2,
+            KnnOptions {
+                exclude_duplicates: true,
+                ..KnnOptions::default()
+            },
+        );
+        assert_eq!(without_duplicates[1], vec![(2, 1.0), (4, 3.0)]);
+        assert_eq!(without_duplicates[4], vec![(2, 2.0), (0, 3.0)]);
+        // a k beyond the number of points connects every point to all others
+        let complete = Point::knn_graph(
+            &points,
+            usize::MAX,
+            KnnOptions {
+                exclude_duplicates: true,
+                ..KnnOptions::default()
+            },
+        );
+        assert_eq!(complete[4], vec![(2, 2.0), (0, 3.0), (1, 3.0), (3, 3.0)]);
+    }
+}
@@ -0,0 +1,298 @@
+use super::intersection::{SegmentIntersection, segment_intersection};
+use super::precision::PrecisionModel;
+use crate::geoms::point::Point;
+use std::collections::HashMap;
+
+/// Split segments at all their mutual intersections.
+///
+/// The result contains every input segment cut into pieces which only touch at their end points.
+/// Overlapping input segments produce identical pieces, these are kept so callers can count them.
+/// Zero length segments are dropped. Computed intersection points which differ from an end point or another
+/// intersection point only by floating-point noise are merged, so that several segments crossing in one
+/// location share a single node.
+pub fn node_segments(segments: &[(Point, Point)]) -> Vec<(Point, Point)> {
+    node_segments_with_source(segments)
+        .into_iter()
+        .map(|(a, b, _)| (a, b))
+        .collect()
+}
+
+/// Split segments at all their mutual intersections like [`node_segments`], and return the index of the
+/// input segment every piece was cut from. Pieces keep the direction of their input segment.
+pub fn node_segments_with_source(segments: &[(Point, Point)]) -> Vec<(Point, Point, usize)> {
+    let mut nodes = NodeSnapper::new(segments);
+    for (a, b) in segments {
+        nodes.insert(*a);
+        nodes.insert(*b);
+    }
+    let mut split_points: Vec<Vec<Point>> = segments.iter().map(|(a, b)| vec![*a, *b]).collect();
+    for i in 0..segments.len() {
+        let (a, b) = &segments[i];
+        for j in (i + 1)..segments.len() {
+            let (c, d) = &segments[j];
+            match segment_intersection(a, b, c, d) {
+                SegmentIntersection::None => {}
+                SegmentIntersection::Point(p) => {
+                    let p = nodes.insert(p);
+                    split_points[i].push(p);
+                    split_points[j].push(p);
+                }
+                SegmentIntersection::Overlap(p, q) => {
+                    split_points[i].extend([p, q]);
+                    split_points[j].extend([p, q]);
+                }
+            }
+        }
+    }
+
+    let mut noded = Vec::new();
+    for (source, ((a, b), mut points)) in segments.iter().zip(split_points).enumerate() {
+        let (dx, dy) = (b.get_x() - a.get_x(), b.get_y() - a.get_y());
+        // order the split points along the segment
+        points.sort_by(|p, q| {
+            let tp = (p.get_x() - a.get_x()) * dx + (p.get_y() - a.get_y()) * dy;
+            let tq = (q.get_x() - a.get_x()) * dx + (q.get_y() - a.get_y()) * dy;
+            tp.total_cmp(&tq)
+        });
+        points.dedup();
+        for pair in points.windows(2) {
+            noded.push((pair[0], pair[1], source));
+        }
+    }
+    noded
+}
+
+/// Split segments at all their mutual intersections with snap rounding.
+///
+/// Every end point and intersection point is rounded to the grid of the precision model, the grid cells containing
+/// them are "hot pixels". Each segment is then routed through the centres of all hot pixels it passes, so the
+/// result only has vertices on the grid and pieces which only touch at their end points, also after rounding.
+/// Segments which pass closer than half a grid cell to a node are bent onto it, which removes slivers.
+/// Like in [`node_segments`], overlapping pieces are kept. With a floating precision model this is [`node_segments`].
+pub fn node_segments_snap_rounded(
+    segments: &[(Point, Point)],
+    precision: &PrecisionModel,
+) -> Vec<(Point, Point)> {
+    node_segments_snap_rounded_with_source(segments, precision)
+        .into_iter()
+        .map(|(a, b, _)| (a, b))
+        .collect()
+}
+
+/// Split segments with snap rounding like [`node_segments_snap_rounded`], and return the index of the input segment
+/// every piece was cut from. Pieces keep the direction of their input segment.
+pub fn node_segments_snap_rounded_with_source(
+    segments: &[(Point, Point)],
+    precision: &PrecisionModel,
+) -> Vec<(Point, Point, usize)> {
+    let Some(grid_size) = precision.get_grid_size() else {
+        return node_segments_with_source(segments);
+    };
+    // the end points of the floating noding are the input end points and all intersection points
+    let mut hot_pixels: Vec<Point> = node_segments_with_source(segments)
+        .into_iter()
+        .flat_map(|(a, b, _)| [a, b])
+        .chain(segments.iter().flat_map(|(a, b)| [*a, *b]))
+        .map(|point| precision.snap_point(&point))
+        .collect();
+    hot_pixels.sort_by_key(Point::key);
+    hot_pixels.dedup_by_key(|p| p.key());
+
+    let half = grid_size / 2.0;
+    let mut noded = Vec::new();
+    for (source, (a, b)) in segments.iter().enumerate() {
+        let (dx, dy) = (b.get_x() - a.get_x(), b.get_y() - a.get_y());
+        let (min_x, max_x) = (
+            a.get_x().min(b.get_x()) - half,
+            a.get_x().max(b.get_x()) + half,
+        );
+        let (min_y, max_y) = (
+            a.get_y().min(b.get_y()) - half,
+            a.get_y().max(b.get_y()) + half,
+        );
+        let mut route: Vec<(f64, Point)> = hot_pixels
+            .iter()
+            .filter(|pixel| {
+                (min_x..=max_x).contains(&pixel.get_x())
+                    && (min_y..=max_y).contains(&pixel.get_y())
+                    && segment_intersects_pixel(a, b, pixel, half)
+            })
+            .map(|pixel| {
+                let t = (pixel.get_x() - a.get_x()) * dx + (pixel.get_y() - a.get_y()) * dy;
+                (t, *pixel)
+            })
+            .collect();
+        route.sort_by(|(s, _), (t, _)| s.total_cmp(t));
+        route.dedup_by(|(_, p), (_, q)| p == q);
+        for pair in route.windows(2) {
+            noded.push((pair[0].1, pair[1].1, source));
+        }
+    }
+    noded
+}
+
+/// check if the segment from `a` to `b` passes the closed square of half width `half` around `center`,
+/// by clipping the segment parameter range against the four sides
+fn segment_intersects_pixel(a: &Point, b: &Point, center: &Point, half: f64) -> bool {
+    let (dx, dy) = (b.get_x() - a.get_x(), b.get_y() - a.get_y());
+    let (mut t_min, mut t_max) = (0.0_f64, 1.0_f64);
+    for (direction, distance) in [
+        (-dx, a.get_x() - (center.get_x() - half)),
+        (dx, center.get_x() + half - a.get_x()),
+        (-dy, a.get_y() - (center.get_y() - half)),
+        (dy, center.get_y() + half - a.get_y()),
+    ] {
+        if direction == 0.0 {
+            if distance < 0.0 {
+                return false;
+            }
+        } else if direction < 0.0 {
+            t_min = t_min.max(distance / direction);
+        } else {
+            t_max = t_max.min(distance / direction);
+        }
+    }
+    t_min <= t_max
+}
+
+/// Merges points closer than a tolerance relative to the coordinate magnitude, using a hash grid
+struct NodeSnapper {
+    tolerance: f64,
+    cells: HashMap<(i64, i64), Vec<Point>>,
+}
+
+impl NodeSnapper {
+    fn new(segments: &[(Point, Point)]) -> NodeSnapper {
+        let scale = segments
+            .iter()
+            .flat_map(|(a, b)| [a.get_x(), a.get_y(), b.get_x(), b.get_y()])
+            .fold(1.0_f64, |scale, value| scale.max(value.abs()));
+        NodeSnapper {
+            tolerance: scale * 1e-12,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, point: &Point) -> (i64, i64) {
+        (
+            (point.get_x() / self.tolerance).floor() as i64,
+            (point.get_y() / self.tolerance).floor() as i64,
+        )
+    }
+
+    /// return an existing node within tolerance of the point, or register the point as a new node
+    fn insert(&mut self, point: Point) -> Point {
+        let (cx, cy) = self.cell(&point);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                if let Some(existing) = self.cells.get(&(cx + dx, cy + dy)).and_then(|nodes| {
+                    nodes.iter().find(|node| {
+                        (node.get_x() - point.get_x()).abs() <= self.tolerance
+                            && (node.get_y() - point.get_y()).abs() <= self.tolerance
+                    })
+                }) {
+                    return *existing;
+                }
+            }
+        }
+        self.cells.entry((cx, cy)).or_default().push(point);
+        point
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crossing_segments() {
+        let segments = vec![
+            (Point::new(0.0, 0.0), Point::new(2.0, 2.0)),
+            (Point::new(0.0, 2.0), Point::new(2.0, 0.0)),
+        ];
+        let noded = node_segments(&segments);
+        assert_eq!(noded.len(), 4);
+        assert_eq!(noded[0], (Point::new(0.0, 0.0), Point::new(1.0, 1.0)));
+        assert_eq!(noded[1], (Point::new(1.0, 1.0), Point::new(2.0, 2.0)));
+    }
+
+    #[test]
+    fn test_overlapping_segments() {
+        let segments = vec![
+            (Point::new(0.0, 0.0), Point::new(3.0, 0.0)),
+            (Point::new(1.0, 0.0), Point::new(2.0, 0.0)),
+        ];
+        let noded = node_segments(&segments);
+        assert_eq!(noded.len(), 4);
+        let shared = (Point::new(1.0, 0.0), Point::new(2.0, 0.0));
+        assert_eq!(noded.iter().filter(|s| **s == shared).count(), 2);
+    }
+
+    #[test]
+    fn test_nearly_coincident_crossings_share_node() {
+        // three segments crossing close to (6, 7/3), the pairwise intersections differ in the last bits
+        let segments = vec![
+            (Point::new(7.0, 1.0), Point::new(1.0, 9.0)),
+            (Point::new(7.0, 3.0), Point::new(4.0, 1.0)),
+            (Point::new(6.0, 8.0), Point::new(6.0, 2.0)),
+        ];
+        let noded = node_segments(&segments);
+        let mut nodes: Vec<Point> = noded.iter().flat_map(|(a, b)| [*a, *b]).collect();
+        nodes.sort_by_key(Point::key);
+        nodes.dedup_by_key(|p| p.key());
+        assert_eq!(nodes.len(), 7);
+    }
+
+    #[test]
+    fn test_zero_length_segment_dropped() {
+        let segments = vec![(Point::new(1.0, 1.0), Point::new(1.0, 1.0))];
+        assert!(node_segments(&segments).is_empty());
+    }
+
+    #[test]
+    fn test_snap_rounded_crossing() {
+        let segments = vec![
+            (Point::new(0.0, 0.0), Point::new(3.0, 1.0)),
+            (Point::new(0.0, 1.0), Point::new(3.0, 0.0)),
+        ];
+        let noded = node_segments_snap_rounded(&segments, &PrecisionModel::fixed(1.0));
+        // the crossing at (1.5, 0.5) is rounded to (2, 1), both segments are routed through it
+        assert_eq!(noded.len(), 4);
+        assert!(noded.contains(&(Point::new(0.0, 0.0), Point::new(2.0, 1.0))));
+        assert!(noded.contains(&(Point::new(2.0, 1.0), Point::new(3.0, 0.0))));
+        for (a, b) in &noded {
+            for p in [a, b] {
+                assert_eq!(p.get_x().fract(), 0.0);
+                assert_eq!(p.get_y().fract(), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_snap_rounded_sliver_collapses() {
+        // two nearly identical segments below the millimetre precision become identical pieces
+        let segments = vec![
+            (Point::new(0.0, 0.0), Point::new(10.0, 0.0)),
+            (Point::new(0.0, 0.0001), Point::new(10.0, -0.0002)),
+        ];
+        let noded = node_segments_snap_rounded(&segments, &PrecisionModel::fixed(0.001));
+        // both are split at their rounded crossing near (3.333, 0)
+        assert_eq!(noded.len(), 4);
+        assert_eq!(noded[0], noded[2]);
+        assert_eq!(noded[1], noded[3]);
+        assert_eq!(noded[0], (Point::new(0.0, 0.0), Point::new(3.333, 0.0)));
+    }
+
+    #[test]
+    fn test_snap_rounded_bends_segment_onto_nearby_node() {
+        // the vertex (5, 0.0004) is closer than half a millimetre to the other segment, which is routed through it
+        let segments = vec![
+            (Point::new(0.0, 0.0), Point::new(10.0, 0.0)),
+            (Point::new(5.0, 0.0004), Point::new(5.0, 3.0)),
+        ];
+        let noded = node_segments_snap_rounded(&segments, &PrecisionModel::fixed(0.001));
+        assert!(noded.contains(&(Point::new(0.0, 0.0), Point::new(5.0, 0.0))));
+        assert!(noded.contains(&(Point::new(5.0, 0.0), Point::new(5.0, 3.0))));
+        assert_eq!(noded.len(), 3);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::geoms::Geometry;
+use crate::geoms::line::Line;
+use crate::geoms::multipolygon::MultiPolygon;
+use crate::geoms::point::Point;
+use crate::geoms::polygon::Polygon;
+
+/// Precision model: either full floating-point precision, or coordinates rounded to a fixed grid
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PrecisionModel {
+    /// spacing of the grid, None for floating precision
+    grid_size: Option<f64>,
+}
+
+impl PrecisionModel {
+    /// create a precision model which keeps coordinates as they are
+    pub fn floating() -> Self {
+        PrecisionModel { grid_size: None }
+    }
+
+    /// create a precision model which rounds coordinates to multiples of the grid size, e.g. 0.001 for millimetres
+    pub fn fixed(grid_size: f64) -> Self {
+        if !(grid_size.is_finite() && grid_size > 0.0) {
+            panic!("Grid size must be positive and finite.");
+        }
+        PrecisionModel {
+            grid_size: Some(grid_size),
+        }
+    }
+
+    /// get the grid size, None for floating precision
+    pub fn get_grid_size(&self) -> Option<f64> {
+        self.grid_size
+    }
+
+    /// check if the precision model keeps full floating-point precision
+    pub fn is_floating(&self) -> bool {
+        self.grid_size.is_none()
+    }
+
+    /// round a single value to the precision model. Values rounded to zero are returned as positive zero.
+    pub fn make_precise(&self, value: f64) -> f64 {
+        let rounded = match self.grid_size {
+            None => value,
+            // dividing by the inverse keeps decimal grids like 0.001 exact where possible
+            Some(grid_size) if grid_size < 1.0 => {
+                let scale = 1.0 / grid_size;
+                (value * scale).round() / scale
+            }
+            Some(grid_size) => (value / grid_size).round() * grid_size,
+        };
+        rounded + 0.0
+    }
+
+    /// round both coordinates of a point to the precision model
+    pub fn snap_point(&self, point: &Point) -> Point {
+        Point::new(
+            self.make_precise(point.get_x()),
+            self.make_precise(point.get_y()),
+        )
+    }
+
+    /// round every point of a line to the precision model.
+    /// Neighbouring points may become identical, [`Polygon::make_valid_with_precision`] removes those.
+    pub fn snap_line(&self, line: &Line) -> Line {
+        Line::new(
+            line.get_points()
+                .iter()
+                .map(|p| self.snap_point(p))
+                .collect(),
+        )
+    }
+
+    /// round every ring of a polygon to the precision model
+    pub fn snap_polygon(&self, polygon: &Polygon) -> Polygon {
+        Polygon::new(
+            self.snap_line(polygon.get_exterior()),
+            polygon
+                .get_interiors()
+                .iter()
+                .map(|ring| self.snap_line(ring))
+                .collect(),
+        )
+    }
+
+    /// round the coordinates of any geometry to the precision model
+    pub fn snap_geometry(&self, geometry: &Geometry) -> Geometry {
+        match geometry {
+            Geometry::Point(point) => Geometry::Point(self.snap_point(point)),
+            Geometry::Line(line) => Geometry::Line(self.snap_line(line)),
+            Geometry::Polygon(polygon) => Geometry::Polygon(self.snap_polygon(polygon)),
+            Geometry::MultiPolygon(multipolygon) => Geometry::MultiPolygon(MultiPolygon::new(
+                multipolygon
+                    .get_polygons()
+                    .iter()
+                    .map(|polygon| self.snap_polygon(polygon))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+impl Default for PrecisionModel {
+    /// the default precision model is floating
+    fn default() -> Self {
+        PrecisionModel::floating()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_make_precise() {
+        let millimetres = PrecisionModel::fixed(0.001);
+        assert_eq!(millimetres.make_precise(1.23456), 1.235);
+        assert!(millimetres.make_precise(-0.0004).is_sign_positive());
+        let tens = PrecisionModel::fixed(10.0);
+        assert_eq!(tens.make_precise(1234.0), 1230.0);
+        assert_eq!(PrecisionModel::floating().make_precise(1.23456), 1.23456);
+        assert_eq!(PrecisionModel::default(), PrecisionModel::floating());
+    }
+
+    #[test]
+    fn test_snap_geometry() {
+        let precision = PrecisionModel::fixed(0.5);
+        let polygon = Polygon::from_wkt("POLYGON ((0.1 0.2, 3.9 0.3, 2.2 2.6, 0.1 0.2))").unwrap();
+        let snapped = precision.snap_geometry(&polygon.into());
+        assert_eq!(snapped.to_wkt(), "POLYGON ((0 0, 4 0.5, 2 2.5, 0 0))");
+        assert_eq!(precision.get_grid_size(), Some(0.5));
+        assert!(!precision.is_floating());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_grid_size() {
+        PrecisionModel::fixed(0.0);
+    }
+}
@@ -0,0 +1,353 @@
+use crate::geoms::point::Point;
+
+/// half the machine epsilon, the relative rounding error of a floating-point operation
+const EPSILON: f64 = f64::EPSILON / 2.0;
+/// relative error bound of the fast orientation determinant
+const CCW_ERROR_BOUND: f64 = (3.0 + 16.0 * EPSILON) * EPSILON;
+/// relative error bound of the fast incircle determinant
+const ICC_ERROR_BOUND: f64 = (10.0 + 96.0 * EPSILON) * EPSILON;
+
+/// Twice the signed area of the triangle a, b, c.
+/// Positive if c lies to the left of the directed line from a to b, negative if it lies to the right and zero if the
+/// points are collinear. The sign is exact: the fast floating-point result is only used if its error bound cannot
+/// change the sign, otherwise the determinant is evaluated with exact expansion arithmetic.
+pub fn orient2d(a: &Point, b: &Point, c: &Point) -> f64 {
+    let left = (a.get_x() - c.get_x()) * (b.get_y() - c.get_y());
+    let right = (a.get_y() - c.get_y()) * (b.get_x() - c.get_x());
+    let det = left - right;
+    if det.abs() >= CCW_ERROR_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+    orient2d_exact(a, b, c)
+}
+
+/// Positive if d lies inside the circle through the counter-clockwise triangle a, b, c, negative if it lies outside
+/// and zero if the four points are cocircular. The sign is reversed for a clockwise triangle.
+/// Like [`orient2d`] the sign is exact, falling back to exact arithmetic for nearly cocircular points.
+pub fn incircle(a: &Point, b: &Point, c: &Point, d: &Point) -> f64 {
+    let (adx, ady) = (a.get_x() - d.get_x(), a.get_y() - d.get_y());
+    let (bdx, bdy) = (b.get_x() - d.get_x(), b.get_y() - d.get_y());
+    let (cdx, cdy) = (c.get_x() - d.get_x(), c.get_y() - d.get_y());
+    let (bdxcdy, cdxbdy) = (bdx * cdy, cdx * bdy);
+    let (cdxady, adxcdy) = (cdx * ady, adx * cdy);
+    let (adxbdy, bdxady) = (adx * bdy, bdx * ady);
+    let alift = adx * adx + ady * ady;
+    let blift = bdx * bdx + bdy * bdy;
+    let clift = cdx * cdx + cdy * cdy;
+    let det = alift * (bdxcdy - cdxbdy) + blift * (cdxady - adxcdy) + clift * (adxbdy - bdxady);
+    let permanent = (bdxcdy.abs() + cdxbdy.abs()) * alift
+        + (cdxady.abs() + adxcdy.abs()) * blift
+        + (adxbdy.abs() + bdxady.abs()) * clift;
+    if det.abs() > ICC_ERROR_BOUND * permanent {
+        return det;
+    }
+    incircle_exact(a, b, c, d)
+}
+
+/// Cross product of the directions from a to b and from c to d.
+/// Positive if the second direction points to the left of the first one, negative if it points to the right and zero
+/// if they are parallel. Like [`orient2d`] the sign is exact, falling back to exact arithmetic for nearly parallel
+/// directions.
+pub fn orient_directions(a: &Point, b: &Point, c: &Point, d: &Point) -> f64 {
+    let left = (b.get_x() - a.get_x()) * (d.get_y() - c.get_y());
+    let right = (b.get_y() - a.get_y()) * (d.get_x() - c.get_x());
+    let det = left - right;
+    if det.abs() >= CCW_ERROR_BOUND * (left.abs() + right.abs()) {
+        return det;
+    }
+    orient_directions_exact(a, b, c, d)
+}
+
+/// evaluate the orientation determinant exactly, expanded into products of the input coordinates
+fn orient2d_exact(a: &Point, b: &Point, c: &Point) -> f64 {
+    let (ax, ay, bx, by, cx, cy) = (
+        a.get_x(),
+        a.get_y(),
+        b.get_x(),
+        b.get_y(),
+        c.get_x(),
+        c.get_y(),
+    );
+    let terms = [
+        two_product(ax, by),
+        two_product(-ax, cy),
+        two_product(-cx, by),
+        two_product(-ay, bx),
+        two_product(ay, cx),
+        two_product(bx, cy),
+    ];
+    let det = terms
+        .iter()
+        .fold(Vec::new(), |sum, term| expansion_sum(&sum, term));
+    most_significant(&det)
+}
+
+/// evaluate the incircle determinant exactly, with the coordinate differences kept as expansions
+fn incircle_exact(a: &Point, b: &Point, c: &Point, d: &Point) -> f64 {
+    let adx = two_sum(a.get_x(), -d.get_x());
+    let ady = two_sum(a.get_y(), -d.get_y());
+    let bdx = two_sum(b.get_x(), -d.get_x());
+    let bdy = two_sum(b.get_y(), -d.get_y());
+    let cdx = two_sum(c.get_x(), -d.get_x());
+    let cdy = two_sum(c.get_y(), -d.get_y());
+    let lift = |dx: &[f64], dy: &[f64]| {
+        expansion_sum(&expansion_product(dx, dx), &expansion_product(dy, dy))
+    };
+    let cross = |ux: &[f64], uy: &[f64], vx: &[f64], vy: &[f64]| {
+        expansion_sum(
+            &expansion_product(ux, vy),
+            &negate(&expansion_product(vx, uy)),
+        )
+    };
+    let a_term = expansion_product(&lift(&adx, &ady), &cross(&bdx, &bdy, &cdx, &cdy));
+    let b_term = expansion_product(&lift(&bdx, &bdy), &cross(&cdx, &cdy, &adx, &ady));
+    let c_term = expansion_product(&lift(&cdx, &cdy), &cross(&adx, &ady, &bdx, &bdy));
+    let det = expansion_sum(&expansion_sum(&a_term, &b_term), &c_term);
+    most_significant(&det)
+}
+
+/// evaluate the cross product of two directions exactly, with the coordinate differences kept as expansions
+fn orient_directions_exact(a: &Point, b: &Point, c: &Point, d: &Point) -> f64 {
+    let ux = two_sum(b.get_x(), -a.get_x());
+    let uy = two_sum(b.get_y(), -a.get_y());
+    let vx = two_sum(d.get_x(), -c.get_x());
+    let vy = two_sum(d.get_y(), -c.get_y());
+    let det = expansion_sum(
+        &expansion_product(&ux, &vy),
+        &negate(&expansion_product(&vx, &uy)),
+    );
+    most_significant(&det)
+}
+
+/// exact sum of two floats as a (small, large) pair of non-overlapping floats, without zero components
+fn two_sum(a: f64, b: f64) -> Vec<f64> {
+    let (sum, error) = two_sum_parts(a, b);
+    [error, sum].into_iter().filter(|v| *v != 0.0).collect()
+}
+
+/// exact product of two floats as a (small, large) pair of non-overlapping floats, without zero components
+fn two_product(a: f64, b: f64) -> Vec<f64> {
+    let (product, error) = two_product_parts(a, b);
+    [error, product].into_iter().filter(|v| *v != 0.0).collect()
+}
+
+/// sum and rounding error of a floating-point addition
+fn two_sum_parts(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let b_virtual = sum - a;
+    let a_virtual = sum - b_virtual;
+    (sum, (a - a_virtual) + (b - b_virtual))
+}
+
+/// sum and rounding error of a floating-point addition with |a| >= |b|
+fn fast_two_sum_parts(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    (sum, b - (sum - a))
+}
+
+/// product and rounding error of a floating-point multiplication, using a fused multiply-add
+fn two_product_parts(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+/// add a float to an expansion (a sum of non-overlapping floats of increasing magnitude)
+fn grow_expansion(expansion: &[f64], value: f64) -> Vec<f64> {
+    let mut result = Vec::with_capacity(expansion.len() + 1);
+    let mut carry = value;
+    for component in expansion {
+        let (sum, error) = two_sum_parts(carry, *component);
+        if error != 0.0 {
+            result.push(error);
+        }
+        carry = sum;
+    }
+    if carry != 0.0 || result.is_empty() {
+        result.push(carry);
+    }
+    result
+}
+
+/// exact sum of two expansions
+fn expansion_sum(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(e.to_vec(), |sum, component| {
+        grow_expansion(&sum, *component)
+    })
+}
+
+/// exact product of an expansion and a float
+fn scale_expansion(expansion: &[f64], factor: f64) -> Vec<f64> {
+    let Some((first, rest)) = expansion.split_first() else {
+        return Vec::new();
+    };
+    let mut result = Vec::with_capacity(2 * expansion.len());
+    let (mut carry, error) = two_product_parts(*first, factor);
+    if error != 0.0 {
+        result.push(error);
+    }
+    for component in rest {
+        let (product, product_error) = two_product_parts(*component, factor);
+        let (sum, error) = two_sum_parts(carry, product_error);
+        if error != 0.0 {
+            result.push(error);
+        }
+        let (sum, error) = fast_two_sum_parts(product, sum);
+        if error != 0.0 {
+            result.push(error);
+        }
+        carry = sum;
+    }
+    if carry != 0.0 || result.is_empty() {
+        result.push(carry);
+    }
+    result
+}
+
+/// exact product of two expansions
+fn expansion_product(e: &[f64], f: &[f64]) -> Vec<f64> {
+    f.iter().fold(Vec::new(), |sum, component| {
+        expansion_sum(&sum, &scale_expansion(e, *component))
+    })
+}
+
+/// negate every component of an expansion
+fn negate(expansion: &[f64]) -> Vec<f64> {
+    expansion.iter().map(|component| -component).collect()
+}
+
+/// the largest component of an expansion, which has the sign of the exact value and approximates it
+fn most_significant(expansion: &[f64]) -> f64 {
+    expansion.last().copied().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// naive floating-point orientation, which the robust predicate must improve on
+    fn naive_orient2d(a: &Point, b: &Point, c: &Point) -> f64 {
+        (a.get_x() - c.get_x()) * (b.get_y() - c.get_y())
+            - (a.get_y() - c.get_y()) * (b.get_x() - c.get_x())
+    }
+
+    /// sign of a value as -1, 0 or 1
+    fn sign(value: f64) -> i32 {
+        if value > 0.0 {
+            1
+        } else if value < 0.0 {
+            -1
+        } else {
+            0
+        }
+    }
+
+    #[test]
+    fn test_orient2d_simple() {
+        let (a, b) = (Point::new(0.0, 0.0), Point::new(1.0, 0.0));
+        assert!(orient2d(&a, &b, &Point::new(0.5, 1.0)) > 0.0);
+        assert!(orient2d(&a, &b, &Point::new(0.5, -1.0)) < 0.0);
+        assert_eq!(orient2d(&a, &b, &Point::new(7.0, 0.0)), 0.0);
+    }
+
+    #[test]
+    fn test_orient2d_near_collinear_grid() {
+        // points on a tiny grid around (0.5, 0.5) tested against the line through (12, 12) and (24, 24):
+        // the exact orientation is the sign of j - i, the naive formula gets many of them wrong
+        let step = f64::EPSILON;
+        let (b, c) = (Point::new(12.0, 12.0), Point::new(24.0, 24.0));
+        let mut naive_errors = 0;
+        for i in 0..64_i32 {
+            for j in 0..64 {
+                let a = Point::new(0.5 + i as f64 * step, 0.5 + j as f64 * step);
+                let expected = (j - i).signum();
+                assert_eq!(
+                    sign(orient2d(&a, &b, &c)),
+                    expected,
+                    "wrong orientation for i = {i}, j = {j}"
+                );
+                if sign(naive_orient2d(&a, &b, &c)) != expected {
+                    naive_errors += 1;
+                }
+            }
+        }
+        assert!(naive_errors > 0);
+    }
+
+    #[test]
+    fn test_orient2d_large_offset() {
+        // collinear points far from the origin, and a point moved off the line by one unit in the last place
+        let base = 2f64.powi(40);
+        let ulp = 2f64.powi(40 - 52);
+        let a = Point::new(base, base);
+        let b = Point::new(base + 3.0, base + 3.0);
+        assert_eq!(orient2d(&a, &b, &Point::new(base + 1.0, base + 1.0)), 0.0);
+        assert!(orient2d(&a, &b, &Point::new(base + 1.0, base + 1.0 + ulp)) > 0.0);
+        assert!(orient2d(&a, &b, &Point::new(base + 1.0 + ulp, base + 1.0)) < 0.0);
+    }
+
+    #[test]
+    fn test_orient_directions() {
+        let (a, b) = (Point::new(0.0, 0.0), Point::new(1.0, 0.0));
+        assert!(orient_directions(&a, &b, &Point::new(5.0, 5.0), &Point::new(6.0, 6.0)) > 0.0);
+        assert!(orient_directions(&a, &b, &Point::new(5.0, 5.0), &Point::new(6.0, 4.0)) < 0.0);
+        assert_eq!(
+            orient_directions(&a, &b, &Point::new(5.0, 5.0), &Point::new(3.0, 5.0)),
+            0.0
+        );
+        // directions along the nearly collinear grid of the orientation test turn like the orientation
+        let step = f64::EPSILON;
+        let (b, c) = (Point::new(12.0, 12.0), Point::new(24.0, 24.0));
+        for i in 0..64_i32 {
+            for j in 0..64 {
+                let a = Point::new(0.5 + i as f64 * step, 0.5 + j as f64 * step);
+                assert_eq!(sign(orient_directions(&a, &b, &b, &c)), (j - i).signum());
+            }
+        }
+    }
+
+    #[test]
+    fn test_incircle_simple() {
+        let (a, b, c) = (
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+        );
+        assert!(incircle(&a, &b, &c, &Point::new(0.5, 0.5)) > 0.0);
+        assert!(incircle(&a, &b, &c, &Point::new(2.0, 2.0)) < 0.0);
+        assert_eq!(incircle(&a, &b, &c, &Point::new(1.0, 1.0)), 0.0);
+        // a clockwise triangle reverses the sign
+        assert!(incircle(&a, &c, &b, &Point::new(0.5, 0.5)) < 0.0);
+    }
+
+    #[test]
+    fn test_incircle_nearly_cocircular() {
+        // a unit square far from the origin with its fourth corner moved by one unit in the last place
+        let base = 2f64.powi(30);
+        let ulp = 2f64.powi(30 - 52);
+        let a = Point::new(base, base);
+        let b = Point::new(base + 1.0, base);
+        let c = Point::new(base + 1.0, base + 1.0);
+        assert_eq!(incircle(&a, &b, &c, &Point::new(base, base + 1.0)), 0.0);
+        assert!(incircle(&a, &b, &c, &Point::new(base, base + 1.0 + ulp)) < 0.0);
+        assert!(incircle(&a, &b, &c, &Point::new(base + ulp, base + 1.0)) > 0.0);
+    }
+
+    #[test]
+    fn test_incircle_consistent_under_permutation() {
+        // nearly cocircular points: even permutations of the triangle must give the same sign
+        let step = f64::EPSILON;
+        let (a, b, c) = (
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(-1.0, 0.0),
+        );
+        for i in -8..=8_i32 {
+            let d = Point::new(0.0, -1.0 + i as f64 * step);
+            let expected = i.signum();
+            assert_eq!(sign(incircle(&a, &b, &c, &d)), expected);
+            assert_eq!(sign(incircle(&b, &c, &a, &d)), expected);
+            assert_eq!(sign(incircle(&c, &a, &b, &d)), expected);
+            assert_eq!(sign(incircle(&a, &c, &b, &d)), -expected);
+        }
+    }
+}
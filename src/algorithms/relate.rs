@@ -0,0 +1,555 @@
+use super::locate::{Location, locate_point_in_geometry, locate_point_in_polygon};
+use super::noding::node_segments_with_source;
+use crate::geoms::Geometry;
+use crate::geoms::line::ring_signed_area;
+use crate::geoms::point::{Point, PointKey};
+use crate::geoms::polygon::Polygon;
+use std::collections::HashMap;
+use std::fmt;
+
+/// DE-9IM intersection matrix of two geometries.
+/// Every entry holds the dimension of the intersection of the interior, boundary or exterior of the first
+/// geometry with the interior, boundary or exterior of the second one, or `None` if they do not intersect.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IntersectionMatrix {
+    /// dimensions indexed by the locations in the first and second geometry
+    entries: [[Option<u8>; 3]; 3],
+}
+
+impl IntersectionMatrix {
+    /// get the dimension of the intersection of a location in the first geometry with a location in the second one
+    pub fn get(&self, a: Location, b: Location) -> Option<u8> {
+        self.entries[index(a)][index(b)]
+    }
+
+    /// get the matrix of the geometries in swapped order
+    pub fn transpose(&self) -> IntersectionMatrix {
+        let mut entries = [[None; 3]; 3];
+        for (i, row) in self.entries.iter().enumerate() {
+            for (j, entry) in row.iter().enumerate() {
+                entries[j][i] = *entry;
+            }
+        }
+        IntersectionMatrix { entries }
+    }
+
+    /// check the matrix against a pattern of nine characters, e.g. `T*F**F***`.
+    /// `T` requires an intersection, `F` requires none, `0`, `1` and `2` require that dimension and `*` matches anything.
+    /// Panics if the pattern is not valid.
+    pub fn matches(&self, pattern: &str) -> bool {
+        let symbols: Vec<char> = pattern.chars().collect();
+        if symbols.len() != 9 {
+            panic!("A DE-9IM pattern must have nine characters.");
+        }
+        symbols.iter().enumerate().all(|(i, symbol)| {
+            let entry = self.entries[i / 3][i % 3];
+            match symbol.to_ascii_uppercase() {
+                '*' => true,
+                'T' => entry.is_some(),
+                'F' => entry.is_none(),
+                '0' => entry == Some(0),
+                '1' => entry == Some(1),
+                '2' => entry == Some(2),
+                _ => {
+                    panic!("A DE-9IM pattern may only contain the characters T, F, *, 0, 1 and 2.")
+                }
+            }
+        })
+    }
+
+    /// raise an entry to at least the given dimension
+    fn set_at_least(&mut self, a: Location, b: Location, dimension: u8) {
+        let entry = &mut self.entries[index(a)][index(b)];
+        if entry.is_none_or(|current| current < dimension) {
+            *entry = Some(dimension);
+        }
+    }
+}
+
+impl fmt::Display for IntersectionMatrix {
+    /// write the matrix as a string of nine characters, e.g. `212101212`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.entries {
+            for entry in row {
+                match entry {
+                    Some(dimension) => write!(f, "{}", dimension)?,
+                    None => write!(f, "F")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compute the DE-9IM intersection matrix of two geometries
+pub fn relate(a: &Geometry, b: &Geometry) -> IntersectionMatrix {
+    match (a, b) {
+        (Geometry::Point(point), _) => relate_point(point, b),
+        (_, Geometry::Point(point)) => relate_point(point, a).transpose(),
+        _ => relate_linework(a, b),
+    }
+}
+
+/// Check if the intersection matrix of two geometries matches a pattern, see [`IntersectionMatrix::matches`]
+pub fn relate_pattern(a: &Geometry, b: &Geometry, pattern: &str) -> bool {
+    relate(a, b).matches(pattern)
+}
+
+/// Check if two geometries are topologically equal
+pub fn equals(a: &Geometry, b: &Geometry) -> bool {
+    relate(a, b).matches("T*F**FFF*")
+}
+
+/// Check if two geometries have no point in common
+pub fn disjoint(a: &Geometry, b: &Geometry) -> bool {
+    relate(a, b).matches("FF*FF****")
+}
+
+/// Check if two geometries have at least one point in common
+pub fn intersects(a: &Geometry, b: &Geometry) -> bool {
+    !disjoint(a, b)
+}
+
+/// Check if two geometries have a common point but their interiors do not intersect
+pub fn touches(a: &Geometry, b: &Geometry) -> bool {
+    if a.dimension() == 0 && b.dimension() == 0 {
+        return false;
+    }
+    let matrix = relate(a, b);
+    matrix.matches("FT*******") || matrix.matches("F**T*****") || matrix.matches("F***T****")
+}
+
+/// Check if two geometries have some but not all interior points in common,
+/// and the intersection has a lower dimension than the larger geometry
+pub fn crosses(a: &Geometry, b: &Geometry) -> bool {
+    let matrix = relate(a, b);
+    match (a.dimension(), b.dimension()) {
+        (1, 1) => matrix.matches("0********"),
+        (dim_a, dim_b) if dim_a < dim_b => matrix.matches("T*T******"),
+        (dim_a, dim_b) if dim_a > dim_b => matrix.matches("T*****T**"),
+        _ => false,
+    }
+}
+
+/// Check if the first geometry lies inside the second one and their interiors intersect
+pub fn within(a: &Geometry, b: &Geometry) -> bool {
+    relate(a, b).matches("T*F**F***")
+}
+
+/// Check if the second geometry lies inside the first one and their interiors intersect
+pub fn contains(a: &Geometry, b: &Geometry) -> bool {
+    relate(a, b).matches("T*****FF*")
+}
+
+/// Check if two geometries of the same dimension intersect in a geometry of that dimension,
+/// and neither contains the other
+pub fn overlaps(a: &Geometry, b: &Geometry) -> bool {
+    let matrix = relate(a, b);
+    match (a.dimension(), b.dimension()) {
+        (1, 1) => matrix.matches("1*T***T**"),
+        (dim_a, dim_b) if dim_a == dim_b => matrix.matches("T*T***T**"),
+        _ => false,
+    }
+}
+
+/// Check if no point of the second geometry lies outside the first one
+pub fn covers(a: &Geometry, b: &Geometry) -> bool {
+    let matrix = relate(a, b);
+    ["T*****FF*", "*T****FF*", "***T**FF*", "****T*FF*"]
+        .iter()
+        .any(|pattern| matrix.matches(pattern))
+}
+
+/// Check if no point of the first geometry lies outside the second one
+pub fn covered_by(a: &Geometry, b: &Geometry) -> bool {
+    covers(b, a)
+}
+
+/// get the row or column of a location in the matrix
+fn index(location: Location) -> usize {
+    match location {
+        Location::Interior => 0,
+        Location::Boundary => 1,
+        Location::Exterior => 2,
+    }
+}
+
+/// relate a point to any geometry
+fn relate_point(point: &Point, other: &Geometry) -> IntersectionMatrix {
+    let mut matrix = IntersectionMatrix {
+        entries: [[None; 3]; 3],
+    };
+    matrix.set_at_least(Location::Exterior, Location::Exterior, 2);
+    let location = locate_point_in_geometry(point, other);
+    matrix.set_at_least(Location::Interior, location, 0);
+    match other {
+        Geometry::Point(other) => {
+            if other != point {
+                matrix.set_at_least(Location::Exterior, Location::Interior, 0);
+            }
+        }
+        Geometry::Line(line) => {
+            let points = line.get_points();
+            matrix.set_at_least(Location::Exterior, Location::Interior, 1);
+            if points.first() != points.last() {
+                matrix.set_at_least(Location::Exterior, Location::Boundary, 0);
+            }
+        }
+        Geometry::Polygon(_) => {
+            matrix.set_at_least(Location::Exterior, Location::Interior, 2);
+            matrix.set_at_least(Location::Exterior, Location::Boundary, 1);
+        }
+        Geometry::MultiPolygon(multipolygon) => {
+            if !multipolygon.is_empty() {
+                matrix.set_at_least(Location::Exterior, Location::Interior, 2);
+                matrix.set_at_least(Location::Exterior, Location::Boundary, 1);
+            }
+        }
+    }
+    matrix
+}
+
+/// Role of a noded piece of linework within one geometry
+#[derive(Debug, Default, Copy, Clone)]
+struct PieceLabel {
+    /// the piece lies on a line
+    on_line: bool,
+    /// the piece lies on a polygon ring
+    on_ring: bool,
+    /// the polygon interior lies left of the piece, in canonical direction
+    interior_left: bool,
+    /// the polygon interior lies right of the piece, in canonical direction
+    interior_right: bool,
+}
+
+/// Segment of a geometry with the side of the polygon interior for ring segments
+struct SourceSegment {
+    geometry: usize,
+    on_ring: bool,
+    interior_left: bool,
+}
+
+/// relate two geometries which are lines or polygons by noding their linework and labelling every piece and node
+fn relate_linework(a: &Geometry, b: &Geometry) -> IntersectionMatrix {
+    let mut segments = Vec::new();
+    let mut sources = Vec::new();
+    for (geometry, g) in [a, b].into_iter().enumerate() {
+        collect_segments(g, geometry, &mut segments, &mut sources);
+    }
+
+    // merge overlapping pieces and collect their labels
+    let mut pieces: HashMap<(PointKey, PointKey), (Point, Point, [PieceLabel; 2])> = HashMap::new();
+    for (p, q, source) in node_segments_with_source(&segments) {
+        let SourceSegment {
+            geometry,
+            on_ring,
+            interior_left,
+        } = sources[source];
+        let (start, end, forward) = if p.key() <= q.key() {
+            (p, q, true)
+        } else {
+            (q, p, false)
+        };
+        let entry = pieces.entry((start.key(), end.key())).or_insert((
+            start,
+            end,
+            [PieceLabel::default(); 2],
+        ));
+        let label = &mut entry.2[geometry];
+        if on_ring {
+            label.on_ring = true;
+            if interior_left == forward {
+                label.interior_left = true;
+            } else {
+                label.interior_right = true;
+            }
+        } else {
+            label.on_line = true;
+        }
+    }
+
+    let mut matrix = IntersectionMatrix {
+        entries: [[None; 3]; 3],
+    };
+    matrix.set_at_least(Location::Exterior, Location::Exterior, 2);
+    // for every node and geometry: whether the node lies on its linework, and the location of adjacent pieces
+    let mut nodes: HashMap<PointKey, (Point, [(bool, Location); 2])> = HashMap::new();
+    let geometries = [a, b];
+    for (start, end, labels) in pieces.values() {
+        let mut locations = [Location::Exterior; 2];
+        let mut sides = [[Location::Exterior; 2]; 2];
+        for geometry in 0..2 {
+            let label = &labels[geometry];
+            if label.on_ring {
+                let side = |interior| {
+                    if interior {
+                        Location::Interior
+                    } else {
+                        Location::Exterior
+                    }
+                };
+                sides[geometry] = [side(label.interior_left), side(label.interior_right)];
+                // a ring piece shared by two polygons of a collection lies inside their union
+                locations[geometry] = if label.interior_left && label.interior_right {
+                    Location::Interior
+                } else {
+                    Location::Boundary
+                };
+            } else if label.on_line {
+                locations[geometry] = Location::Interior;
+            } else if geometries[geometry].dimension() == 2 {
+                let middle = Point::new(
+                    (start.get_x() + end.get_x()) / 2.0,
+                    (start.get_y() + end.get_y()) / 2.0,
+                );
+                let location = locate_point_in_geometry(&middle, geometries[geometry]);
+                locations[geometry] = location;
+                sides[geometry] = [location; 2];
+            }
+        }
+        matrix.set_at_least(locations[0], locations[1], 1);
+        for (side_a, side_b) in sides[0].iter().zip(&sides[1]) {
+            matrix.set_at_least(*side_a, *side_b, 2);
+        }
+        for point in [start, end] {
+            let node = nodes
+                .entry(point.key())
+                .or_insert((*point, [(false, Location::Exterior); 2]));
+            for geometry in 0..2 {
+                let label = &labels[geometry];
+                if label.on_ring || label.on_line {
+                    node.1[geometry].0 = true;
+                } else {
+                    node.1[geometry].1 = locations[geometry];
+                }
+            }
+        }
+    }
+
+    for (point, node) in nodes.values() {
+        let mut locations = [Location::Exterior; 2];
+        for geometry in 0..2 {
+            let (on_linework, adjacent) = node[geometry];
+            locations[geometry] = match geometries[geometry] {
+                Geometry::Line(line) if on_linework => {
+                    let points = line.get_points();
+                    if points.first() != points.last()
+                        && (points.first() == Some(point) || points.last() == Some(point))
+                    {
+                        Location::Boundary
+                    } else {
+                        Location::Interior
+                    }
+                }
+                Geometry::Polygon(polygon) if on_linework => {
+                    ring_node_location(point, std::slice::from_ref(polygon))
+                }
+                Geometry::MultiPolygon(multipolygon) if on_linework => {
+                    ring_node_location(point, multipolygon.get_polygons())
+                }
+                _ => adjacent,
+            };
+        }
+        matrix.set_at_least(locations[0], locations[1], 0);
+    }
+    matrix
+}
+
+/// locate a node on the rings of polygons, which is in the interior if it only lies on rings shared by two polygons
+fn ring_node_location(point: &Point, polygons: &[Polygon]) -> Location {
+    if polygons
+        .iter()
+        .any(|polygon| locate_point_in_polygon(point, polygon) == Location::Interior)
+    {
+        Location::Interior
+    } else {
+        Location::Boundary
+    }
+}
+
+/// collect the segments of a line or the rings of polygons
+fn collect_segments(
+    geometry: &Geometry,
+    index: usize,
+    segments: &mut Vec<(Point, Point)>,
+    sources: &mut Vec<SourceSegment>,
+) {
+    let polygons = match geometry {
+        Geometry::Point(_) => &[][..],
+        Geometry::Line(line) => {
+            for pair in line.get_points().windows(2) {
+                segments.push((pair[0], pair[1]));
+                sources.push(SourceSegment {
+                    geometry: index,
+                    on_ring: false,
+                    interior_left: false,
+                });
+            }
+            &[][..]
+        }
+        Geometry::Polygon(polygon) => std::slice::from_ref(polygon),
+        Geometry::MultiPolygon(multipolygon) => multipolygon.get_polygons(),
+    };
+    for polygon in polygons {
+        let rings = std::iter::once(polygon.get_exterior()).chain(polygon.get_interiors());
+        for (ring_index, ring) in rings.enumerate() {
+            let points = ring.get_points();
+            let n = if points.first() == points.last() {
+                points.len() - 1
+            } else {
+                points.len()
+            };
+            let signed_area = ring_signed_area(&points[..n]);
+            // the interior lies left of a counter-clockwise shell and right of a counter-clockwise hole
+            let interior_left = (signed_area > 0.0) == (ring_index == 0);
+            for i in 0..n {
+                segments.push((points[i], points[(i + 1) % n]));
+                sources.push(SourceSegment {
+                    geometry: index,
+                    on_ring: true,
+                    interior_left,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geoms::line::Line;
+    use crate::geoms::multipolygon::MultiPolygon;
+
+    fn polygon(wkt: &str) -> Geometry {
+        Polygon::from_wkt(wkt).unwrap().into()
+    }
+
+    fn line(wkt: &str) -> Geometry {
+        Line::from_wkt(wkt).unwrap().into()
+    }
+
+    #[test]
+    fn test_overlapping_polygons() {
+        let a = polygon("POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))");
+        let b = polygon("POLYGON ((1 1, 3 1, 3 3, 1 3, 1 1))");
+        assert_eq!(relate(&a, &b).to_string(), "212101212");
+        assert!(overlaps(&a, &b));
+        assert!(intersects(&a, &b));
+        assert!(!touches(&a, &b));
+        assert!(!contains(&a, &b));
+    }
+
+    #[test]
+    fn test_polygons_sharing_an_edge() {
+        let a = polygon("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))");
+        let b = polygon("POLYGON ((1 0, 1 1, 2 1, 2 0, 1 0))");
+        assert_eq!(relate(&a, &b).to_string(), "FF2F11212");
+        assert!(touches(&a, &b));
+        assert!(!overlaps(&a, &b));
+    }
+
+    #[test]
+    fn test_polygon_with_hole_contains_polygon() {
+        let a = polygon("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (4 4, 6 4, 6 6, 4 6, 4 4))");
+        let b = polygon("POLYGON ((1 1, 3 1, 3 3, 1 3, 1 1))");
+        let hole = polygon("POLYGON ((4 4, 6 4, 6 6, 4 6, 4 4))");
+        assert_eq!(relate(&a, &b).to_string(), "212FF1FF2");
+        assert!(contains(&a, &b));
+        assert!(covers(&a, &b));
+        assert!(within(&b, &a));
+        assert!(covered_by(&b, &a));
+        assert_eq!(relate(&a, &hole).to_string(), "FF2F112F2");
+        assert!(touches(&a, &hole));
+    }
+
+    #[test]
+    fn test_equal_polygons_with_different_start_and_orientation() {
+        let a = polygon("POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))");
+        let b = polygon("POLYGON ((2 2, 2 0, 1 0, 0 0, 0 2, 2 2))");
+        assert_eq!(relate(&a, &b).to_string(), "2FFF1FFF2");
+        assert!(equals(&a, &b));
+        assert!(covers(&a, &b) && covered_by(&a, &b));
+    }
+
+    #[test]
+    fn test_line_and_polygon() {
+        let square = polygon("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0))");
+        let inside = line("LINESTRING (2 2, 8 8)");
+        assert_eq!(relate(&square, &inside).to_string(), "102FF1FF2");
+        assert!(contains(&square, &inside));
+        let crossing = line("LINESTRING (-1 5, 11 5)");
+        assert_eq!(relate(&crossing, &square).to_string(), "101FF0212");
+        assert!(crosses(&crossing, &square));
+        assert!(crosses(&square, &crossing));
+        let along_edge = line("LINESTRING (2 0, 8 0)");
+        assert_eq!(relate(&along_edge, &square).to_string(), "F1FF0F212");
+        assert!(touches(&along_edge, &square));
+        assert!(covered_by(&along_edge, &square));
+        assert!(!within(&along_edge, &square));
+    }
+
+    #[test]
+    fn test_lines() {
+        let a = line("LINESTRING (0 0, 2 2)");
+        let b = line("LINESTRING (0 2, 2 0)");
+        assert_eq!(relate(&a, &b).to_string(), "0F1FF0102");
+        assert!(crosses(&a, &b));
+        let c = line("LINESTRING (1 1, 3 3)");
+        assert_eq!(relate(&a, &c).to_string(), "1010F0102");
+        assert!(overlaps(&a, &c));
+        let d = line("LINESTRING (2 2, 3 0)");
+        assert_eq!(relate(&a, &d).to_string(), "FF1F00102");
+        assert!(touches(&a, &d));
+        let reversed = line("LINESTRING (2 2, 1 1, 0 0)");
+        assert!(equals(&a, &reversed));
+    }
+
+    #[test]
+    fn test_points() {
+        let point: Geometry = Point::new(0.0, 0.0).into();
+        let a = line("LINESTRING (0 0, 1 1)");
+        assert_eq!(relate(&point, &a).to_string(), "F0FFFF102");
+        assert_eq!(relate(&a, &point).to_string(), "FF10F0FF2");
+        assert!(touches(&point, &a));
+        let square = polygon("POLYGON ((-1 -1, 1 -1, 1 1, -1 1, -1 -1))");
+        assert!(within(&point, &square));
+        assert_eq!(relate(&point, &square).to_string(), "0FFFFF212");
+        assert!(equals(&point, &Point::new(0.0, 0.0).into()));
+        assert!(disjoint(&point, &Point::new(1.0, 0.0).into()));
+        assert!(!touches(&point, &point));
+    }
+
+    #[test]
+    fn test_multipolygon() {
+        let a: Geometry = MultiPolygon::new(vec![
+            Polygon::from_wkt("POLYGON ((0 0, 1 0, 1 1, 0 1, 0 0))").unwrap(),
+            Polygon::from_wkt("POLYGON ((2 0, 3 0, 3 1, 2 1, 2 0))").unwrap(),
+        ])
+        .into();
+        let b = line("LINESTRING (0.5 0.5, 2.5 0.5)");
+        assert_eq!(relate(&a, &b).to_string(), "1020F11F2");
+        assert!(crosses(&b, &a));
+        let empty: Geometry = MultiPolygon::new(vec![]).into();
+        assert_eq!(relate(&empty, &b).to_string(), "FFFFFF102");
+        assert!(disjoint(&empty, &b));
+    }
+
+    #[test]
+    fn test_pattern_matching() {
+        let a = polygon("POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))");
+        let b = polygon("POLYGON ((1 1, 3 1, 3 3, 1 3, 1 1))");
+        let matrix = relate(&a, &b);
+        assert!(matrix.matches("T*T***T**"));
+        assert!(matrix.matches("2121012t2"));
+        assert!(!matrix.matches("T*F**F***"));
+        assert_eq!(matrix.get(Location::Boundary, Location::Boundary), Some(0));
+        assert!(relate_pattern(&a, &b, "212101212"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_pattern() {
+        let a = polygon("POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))");
+        relate(&a, &a).matches("T*F");
+    }
+}
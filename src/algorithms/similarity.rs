@@ -0,0 +1,187 @@
+use crate::geoms::line::Line;
+use crate::geoms::point::Point;
+
+/// Compute the Hausdorff distance between two point sets: the largest distance from a point of one set to the
+/// nearest point of the other set. Return None if a set is empty.
+pub fn hausdorff_distance(a: &[Point], b: &[Point]) -> Option<f64> {
+    hausdorff_distance_with(a, b, Point::distance2D)
+}
+
+/// Compute the Hausdorff distance between two point sets like [`hausdorff_distance`], with a custom metric
+pub fn hausdorff_distance_with<F>(a: &[Point], b: &[Point], metric: F) -> Option<f64>
+where
+    F: Fn(&Point, &Point) -> f64,
+{
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    Some(directed_hausdorff(a, b, &metric).max(directed_hausdorff(b, a, &metric)))
+}
+
+/// largest distance from a point of `a` to the nearest point of `b`.
+/// The search for the nearest point stops as soon as a point closer than the current maximum is found.
+fn directed_hausdorff<F>(a: &[Point], b: &[Point], metric: &F) -> f64
+where
+    F: Fn(&Point, &Point) -> f64,
+{
+    let mut max_distance = 0.0_f64;
+    for p in a {
+        let mut min_distance = f64::INFINITY;
+        for q in b {
+            min_distance = min_distance.min(metric(p, q));
+            if min_distance <= max_distance {
+                break;
+            }
+        }
+        max_distance = max_distance.max(min_distance);
+    }
+    max_distance
+}
+
+/// Compute the discrete Fréchet distance between two point sequences: the shortest leash needed to walk both
+/// sequences from start to end, where each step moves forward on one or both sequences. Unlike the Hausdorff
+/// distance it takes the order of the points into account. Return None if a sequence is empty.
+pub fn frechet_distance(a: &[Point], b: &[Point]) -> Option<f64> {
+    frechet_distance_with(a, b, Point::distance2D)
+}
+
+/// Compute the discrete Fréchet distance between two point sequences like [`frechet_distance`], with a custom metric
+pub fn frechet_distance_with<F>(a: &[Point], b: &[Point], metric: F) -> Option<f64>
+where
+    F: Fn(&Point, &Point) -> f64,
+{
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    // dynamic programming over the coupling table, keeping only the previous row
+    let mut previous: Vec<f64> = Vec::with_capacity(b.len());
+    for (j, q) in b.iter().enumerate() {
+        let reachable = if j == 0 { 0.0 } else { previous[j - 1] };
+        previous.push(reachable.max(metric(&a[0], q)));
+    }
+    let mut current = vec![0.0; b.len()];
+    for p in &a[1..] {
+        current[0] = previous[0].max(metric(p, &b[0]));
+        for j in 1..b.len() {
+            let reachable = previous[j].min(previous[j - 1]).min(current[j - 1]);
+            current[j] = reachable.max(metric(p, &b[j]));
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous.last().copied()
+}
+
+/// split every segment of the points into `1 / fraction` (rounded up) parts of equal length
+fn densify(points: &[Point], fraction: f64) -> Vec<Point> {
+    let parts = (1.0 / fraction).ceil() as usize;
+    let mut densified = Vec::with_capacity((points.len() - 1) * parts + 1);
+    for pair in points.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        for k in 0..parts {
+            let t = k as f64 / parts as f64;
+            densified.push(Point::new(
+                a.get_x() + t * (b.get_x() - a.get_x()),
+                a.get_y() + t * (b.get_y() - a.get_y()),
+            ));
+        }
+    }
+    densified.extend(points.last());
+    densified
+}
+
+impl Line {
+    /// Compute the Hausdorff distance to another line, between the vertices of both lines.
+    /// With `densify_fraction`, every segment is first split into parts of at most this fraction of its length,
+    /// which brings the result closer to the exact distance between the lines at the cost of run time.
+    /// Panics if the fraction is not in the range (0, 1].
+    pub fn hausdorff_distance(&self, other: &Line, densify_fraction: Option<f64>) -> f64 {
+        match densify_fraction {
+            None => hausdorff_distance(self.get_points(), other.get_points()),
+            Some(fraction) => {
+                if !(fraction > 0.0 && fraction <= 1.0) {
+                    panic!("The densify fraction must be in the range (0, 1].");
+                }
+                hausdorff_distance(
+                    &densify(self.get_points(), fraction),
+                    &densify(other.get_points(), fraction),
+                )
+            }
+        }
+        .unwrap_or(0.0)
+    }
+
+    /// Compute the discrete Fréchet distance to another line, between the vertices of both lines
+    pub fn frechet_distance(&self, other: &Line) -> f64 {
+        frechet_distance(self.get_points(), other.get_points()).unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPS: f64 = 1e-10;
+
+    #[test]
+    fn test_hausdorff_point_sets() {
+        let a = vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0)];
+        let b = vec![
+            Point::new(0.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(5.0, 1.0),
+        ];
+        // (5, 1) is far from every point of a
+        let expected = Point::new(5.0, 1.0).distance2D(&Point::new(1.0, 0.0));
+        assert!((hausdorff_distance(&a, &b).unwrap() - expected).abs() < EPS);
+        assert!((hausdorff_distance(&b, &a).unwrap() - expected).abs() < EPS);
+        assert_eq!(hausdorff_distance(&a, &[]), None);
+    }
+
+    #[test]
+    fn test_custom_metric() {
+        let manhattan =
+            |p: &Point, q: &Point| (p.get_x() - q.get_x()).abs() + (p.get_y() - q.get_y()).abs();
+        let a = vec![Point::new(0.0, 0.0)];
+        let b = vec![Point::new(3.0, 4.0)];
+        assert_eq!(hausdorff_distance_with(&a, &b, manhattan), Some(7.0));
+        assert_eq!(frechet_distance_with(&a, &b, manhattan), Some(7.0));
+        assert_eq!(hausdorff_distance(&a, &b), Some(5.0));
+    }
+
+    #[test]
+    fn test_densified_hausdorff() {
+        // the vertices of the second line lie on the first one, but its middle runs far away from the vertices
+        let a = Line::from_wkt("LINESTRING (0 0, 100 0)").unwrap();
+        let b = Line::from_wkt("LINESTRING (0 0, 50 10, 100 0)").unwrap();
+        assert!((a.hausdorff_distance(&b, None) - 2600.0_f64.sqrt()).abs() < EPS);
+        let densified = a.hausdorff_distance(&b, Some(0.01));
+        assert!((densified - 10.0).abs() < EPS);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_densify_fraction() {
+        let a = Line::from_wkt("LINESTRING (0 0, 1 0)").unwrap();
+        a.hausdorff_distance(&a, Some(0.0));
+    }
+
+    #[test]
+    fn test_frechet_takes_order_into_account() {
+        let a = Line::from_wkt("LINESTRING (0 0, 1 0, 2 0, 3 0)").unwrap();
+        let b = Line::from_wkt("LINESTRING (0 1, 1 1, 2 1, 3 1)").unwrap();
+        assert!((a.frechet_distance(&b) - 1.0).abs() < EPS);
+        // the same vertices walked in reverse: the Hausdorff distance stays, the Fréchet distance grows
+        let reversed = Line::from_wkt("LINESTRING (3 1, 2 1, 1 1, 0 1)").unwrap();
+        assert!((a.hausdorff_distance(&reversed, None) - 1.0).abs() < EPS);
+        assert!((a.frechet_distance(&reversed) - 10.0_f64.sqrt()).abs() < EPS);
+    }
+
+    #[test]
+    fn test_frechet_at_least_hausdorff() {
+        let a = Point::generate_random_points(40, 0.0, 0.0, 10.0, 10.0, 1);
+        let b = Point::generate_random_points(30, 0.0, 0.0, 10.0, 10.0, 2);
+        let frechet = frechet_distance(&a, &b).unwrap();
+        assert!(frechet >= hausdorff_distance(&a, &b).unwrap());
+        assert_eq!(frechet_distance(&a, &a), Some(0.0));
+    }
+}
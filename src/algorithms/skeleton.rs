@@ -0,0 +1,917 @@
+use super::delaunay::DelaunayTriangulation;
+use super::locate::{Location, locate_point_in_polygon};
+use super::precision::PrecisionModel;
+use super::predicates::{orient_directions, orient2d};
+use super::validity::build_even_odd_from_segments;
+use crate::geoms::line::Line;
+use crate::geoms::multipolygon::MultiPolygon;
+use crate::geoms::point::{Point, PointKey};
+use crate::geoms::polygon::Polygon;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Supporting line of a polygon edge. It moves inwards with unit speed: at time `t` it is the line
+/// `nx * x + ny * y = c + t`, where (nx, ny) is the unit normal pointing into the polygon.
+#[derive(Debug, Clone, Copy)]
+struct EdgeLine {
+    nx: f64,
+    ny: f64,
+    c: f64,
+    /// end points of the edge, for exact tests of its direction
+    from: Point,
+    to: Point,
+}
+
+impl EdgeLine {
+    /// line through an edge from `a` to `b` with the interior on its left
+    fn new(a: &Point, b: &Point) -> EdgeLine {
+        let (dx, dy) = (b.get_x() - a.get_x(), b.get_y() - a.get_y());
+        let length = dx.hypot(dy);
+        let (nx, ny) = (-dy / length, dx / length);
+        EdgeLine {
+            nx,
+            ny,
+            c: nx * a.get_x() + ny * a.get_y(),
+            from: *a,
+            to: *b,
+        }
+    }
+}
+
+/// Vertex of the wavefront, it runs along the bisector of its two edges and traces an arc of the skeleton
+#[derive(Debug, Clone)]
+struct Vertex {
+    /// index of the line of the edge ending at the vertex
+    incoming: usize,
+    /// index of the line of the edge starting at the vertex
+    outgoing: usize,
+    /// skeleton node where the vertex was created
+    start: Point,
+    previous: usize,
+    next: usize,
+    active: bool,
+    /// whether the vertex may split the wavefront, only reflex vertices can
+    splits: bool,
+}
+
+/// Change of the structure of the wavefront
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum EventKind {
+    /// the edge from the first vertex to the second one shrinks to zero length
+    Edge(usize, usize),
+    /// the vertex runs into the part of the wavefront on the given edge line and splits the wavefront
+    Split(usize, usize),
+}
+
+/// Event at the time and point where it happens, if it is still valid by then. Ordered by time, so a
+/// [`BinaryHeap`] yields the earliest event first.
+#[derive(Debug, Clone, Copy)]
+struct Event {
+    time: f64,
+    point: Point,
+    kind: EventKind,
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Event {}
+
+impl PartialOrd for Event {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Event {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .time
+            .total_cmp(&self.time)
+            .then_with(|| other.kind.cmp(&self.kind))
+    }
+}
+
+/// The wavefront of a polygon shrinking with unit speed, tracing its straight skeleton
+struct Wavefront {
+    lines: Vec<EdgeLine>,
+    vertices: Vec<Vertex>,
+    /// the vertices starting a part of the wavefront on each edge line, inactive ones included
+    line_vertices: Vec<Vec<usize>>,
+    arcs: Vec<(Point, Point)>,
+    time: f64,
+    /// distance below which points and times are treated as equal, relative to the size of the polygon
+    tolerance: f64,
+    /// queued events, which are checked when they are due because the wavefront may have changed in between
+    edge_events: BinaryHeap<Event>,
+    split_events: BinaryHeap<Event>,
+}
+
+impl Wavefront {
+    fn new(polygon: &Polygon) -> Wavefront {
+        let mut normalized = polygon.clone();
+        normalized.normalize();
+        let (min, max) = normalized.get_exterior().bounding_box();
+        let size = (max.get_x() - min.get_x()).max(max.get_y() - min.get_y());
+        let mut wavefront = Wavefront {
+            lines: Vec::new(),
+            vertices: Vec::new(),
+            line_vertices: Vec::new(),
+            arcs: Vec::new(),
+            time: 0.0,
+            tolerance: size * 1e-9,
+            edge_events: BinaryHeap::new(),
+            split_events: BinaryHeap::new(),
+        };
+        // after normalization the interior lies left of every ring, also of the clockwise holes
+        for ring in std::iter::once(normalized.get_exterior()).chain(normalized.get_interiors()) {
+            let points = corners(ring.get_points());
+            let (n, first) = (points.len(), wavefront.vertices.len());
+            if n < 3 {
+                continue;
+            }
+            for i in 0..n {
+                wavefront
+                    .lines
+                    .push(EdgeLine::new(&points[i], &points[(i + 1) % n]));
+                wavefront.line_vertices.push(vec![first + i]);
+                wavefront.vertices.push(Vertex {
+                    incoming: first + (i + n - 1) % n,
+                    outgoing: first + i,
+                    start: points[i],
+                    previous: first + (i + n - 1) % n,
+                    next: first + (i + 1) % n,
+                    active: true,
+                    splits: false,
+                });
+            }
+        }
+        for vertex in 0..wavefront.vertices.len() {
+            wavefront.vertices[vertex].splits = wavefront.is_reflex(vertex);
+            wavefront.schedule_edge(vertex);
+            wavefront.schedule_splits(vertex);
+        }
+        wavefront
+    }
+
+    /// position of a vertex at a given time: the intersection of its two moving edge lines
+    fn position(&self, vertex: usize, time: f64) -> Point {
+        let v = &self.vertices[vertex];
+        let (a, b) = (&self.lines[v.incoming], &self.lines[v.outgoing]);
+        let det = a.nx * b.ny - a.ny * b.nx;
+        if det.abs() < 1e-12 {
+            // opposite edges which have met, the vertex does not move any more
+            return v.start;
+        }
+        let (ca, cb) = (a.c + time, b.c + time);
+        Point::new((ca * b.ny - cb * a.ny) / det, (a.nx * cb - b.nx * ca) / det)
+    }
+
+    /// check if the wavefront turns right at a vertex, such vertices can split the wavefront
+    fn is_reflex(&self, vertex: usize) -> bool {
+        let v = &self.vertices[vertex];
+        let (a, b) = (&self.lines[v.incoming], &self.lines[v.outgoing]);
+        orient_directions(&a.from, &a.to, &b.from, &b.to) < 0.0
+    }
+
+    /// Queue the collapse of the edge from a vertex to its successor, if the edge shrinks. An edge which already has
+    /// no length collapses at once, also when the lines next to it are parallel and never meet in a single point.
+    fn schedule_edge(&mut self, a: usize) {
+        let b = self.vertices[a].next;
+        let (va, vb) = (&self.vertices[a], &self.vertices[b]);
+        let meeting = meeting_point(
+            &self.lines[va.incoming],
+            &self.lines[va.outgoing],
+            &self.lines[vb.outgoing],
+        )
+        .or_else(|| {
+            let point = self.position(a, self.time);
+            (point.distance2D(&self.position(b, self.time)) <= self.tolerance)
+                .then_some((point, self.time))
+        });
+        if let Some((point, t)) = meeting
+            && t >= self.time - self.tolerance
+        {
+            self.edge_events.push(Event {
+                time: t.max(self.time),
+                point,
+                kind: EventKind::Edge(a, b),
+            });
+        }
+    }
+
+    /// queue the points where a reflex vertex reaches the lines of the other edges ahead of it
+    fn schedule_splits(&mut self, vertex: usize) {
+        let v = &self.vertices[vertex];
+        if !v.active || !v.splits {
+            return;
+        }
+        let current = self.position(vertex, self.time);
+        let (incoming, outgoing) = (&self.lines[v.incoming], &self.lines[v.outgoing]);
+        let events: Vec<Event> = self
+            .lines
+            .iter()
+            .enumerate()
+            .filter(|&(index, line)| {
+                index != v.incoming
+                    && index != v.outgoing
+                    && line.nx * current.get_x() + line.ny * current.get_y() - line.c - self.time
+                        >= -self.tolerance
+            })
+            .filter_map(|(index, line)| {
+                let (point, t) = meeting_point(incoming, outgoing, line)?;
+                (t >= self.time - self.tolerance).then(|| Event {
+                    time: t.max(self.time),
+                    point,
+                    kind: EventKind::Split(vertex, index),
+                })
+            })
+            .collect();
+        self.split_events.extend(events);
+    }
+
+    /// take the earliest queued event, preferring edge events over split events at the same time
+    fn pop_event(&mut self) -> Option<Event> {
+        let split_first = match (self.edge_events.peek(), self.split_events.peek()) {
+            (Some(edge), Some(split)) => split.time < edge.time - self.tolerance,
+            (None, Some(_)) => true,
+            _ => false,
+        };
+        if split_first {
+            self.split_events.pop()
+        } else {
+            self.edge_events.pop()
+        }
+    }
+
+    /// get the vertex starting the part of the wavefront on an edge line which a split event hits, None if the
+    /// vertex is gone or the point does not lie on any part which still exists at that time
+    fn split_target(&self, vertex: usize, line: usize, event: &Event) -> Option<usize> {
+        if !self.vertices[vertex].active {
+            return None;
+        }
+        self.line_vertices[line].iter().copied().find(|&u| {
+            let other = &self.vertices[u];
+            if !other.active || other.next == vertex {
+                return false;
+            }
+            let (p, q) = (
+                self.position(u, event.time),
+                self.position(other.next, event.time),
+            );
+            let (dx, dy) = (q.get_x() - p.get_x(), q.get_y() - p.get_y());
+            let length_squared = dx * dx + dy * dy;
+            if length_squared <= self.tolerance * self.tolerance {
+                return false;
+            }
+            let point = &event.point;
+            let s = ((point.get_x() - p.get_x()) * dx + (point.get_y() - p.get_y()) * dy)
+                / length_squared;
+            let slack = self.tolerance / length_squared.sqrt();
+            s >= -slack && s <= 1.0 + slack
+        })
+    }
+
+    /// Process all events up to the given time.
+    ///
+    /// This terminates because only vertices which may split do split, and their number never grows: a split
+    /// removes one and creates two convex vertices, and an edge event replaces two vertices by one, which may only
+    /// split if one of the two could. So there are at most as many split events as reflex corners. Every split adds
+    /// one vertex to the wavefront and every edge event removes one, which bounds the edge events, and every event
+    /// queues a bounded number of new ones.
+    fn run(&mut self, until: f64) {
+        while let Some(event) = self.pop_event() {
+            if event.time > until {
+                break;
+            }
+            match event.kind {
+                EventKind::Edge(a, b) => {
+                    let (va, vb) = (&self.vertices[a], &self.vertices[b]);
+                    if va.active && vb.active && va.next == b {
+                        self.time = self.time.max(event.time);
+                        self.edge_event(a, b, event.point);
+                    }
+                }
+                EventKind::Split(vertex, line) => {
+                    if let Some(u) = self.split_target(vertex, line, &event) {
+                        self.time = self.time.max(event.time);
+                        self.split_event(vertex, u, event.point);
+                    }
+                }
+            }
+        }
+    }
+
+    fn edge_event(&mut self, a: usize, b: usize, point: Point) {
+        let c = self.vertices[b].next;
+        if self.vertices[c].next == a {
+            // a triangle collapses into a single point
+            for v in [a, b, c] {
+                self.finish(v, point);
+            }
+            return;
+        }
+        self.finish(a, point);
+        self.finish(b, point);
+        let (previous, next) = (self.vertices[a].previous, self.vertices[b].next);
+        let vertex = self.add_vertex(
+            self.vertices[a].incoming,
+            self.vertices[b].outgoing,
+            point,
+            previous,
+            next,
+        );
+        let splits = self.vertices[a].splits || self.vertices[b].splits;
+        self.vertices[vertex].splits = splits && self.is_reflex(vertex);
+        self.tidy(vertex);
+    }
+
+    fn split_event(&mut self, vertex: usize, u: usize, point: Point) {
+        self.finish(vertex, point);
+        let v = self.vertices[vertex].clone();
+        let (edge, w) = (self.vertices[u].outgoing, self.vertices[u].next);
+        // one part continues with the rest of the edge towards w, the other one with the part starting at u
+        let first = self.add_vertex(v.incoming, edge, point, v.previous, w);
+        let second = self.add_vertex(edge, v.outgoing, point, u, v.next);
+        self.tidy(first);
+        self.tidy(second);
+    }
+
+    fn add_vertex(
+        &mut self,
+        incoming: usize,
+        outgoing: usize,
+        start: Point,
+        previous: usize,
+        next: usize,
+    ) -> usize {
+        let index = self.vertices.len();
+        self.vertices.push(Vertex {
+            incoming,
+            outgoing,
+            start,
+            previous,
+            next,
+            active: true,
+            splits: false,
+        });
+        self.line_vertices[outgoing].push(index);
+        self.vertices[previous].next = index;
+        self.vertices[next].previous = index;
+        index
+    }
+
+    /// end the arc of a vertex at a skeleton node
+    fn finish(&mut self, vertex: usize, point: Point) {
+        let v = &mut self.vertices[vertex];
+        if v.active {
+            v.active = false;
+            self.arcs.push((v.start, point));
+        }
+    }
+
+    /// Tidy up the wavefront around a new vertex and queue its events. A vertex between edges on the same line is
+    /// dropped, a vertex between opposite edges which have met closes the gap between them up to the nearer
+    /// neighbour, and a wavefront left with less than three vertices collapses.
+    fn tidy(&mut self, vertex: usize) {
+        if self.collapse_small_ring(vertex) {
+            return;
+        }
+        let v = self.vertices[vertex].clone();
+        let (a, b) = (&self.lines[v.incoming], &self.lines[v.outgoing]);
+        let dot = a.nx * b.nx + a.ny * b.ny;
+        if dot > 1.0 - 1e-12 {
+            self.vertices[vertex].active = false;
+            self.vertices[v.previous].next = v.next;
+            self.vertices[v.next].previous = v.previous;
+            if !self.collapse_small_ring(v.previous) {
+                self.schedule_edge(v.previous);
+            }
+        } else if dot < -1.0 + 1e-12 {
+            let (p, q) = (
+                self.position(v.previous, self.time),
+                self.position(v.next, self.time),
+            );
+            self.vertices[vertex].active = false;
+            let zipped = if v.start.distance2D(&q) <= v.start.distance2D(&p) {
+                self.arcs.push((v.start, q));
+                self.finish(v.next, q);
+                let next = &self.vertices[v.next];
+                let (outgoing, after, splits) = (next.outgoing, next.next, next.splits);
+                let zipped = self.add_vertex(v.incoming, outgoing, q, v.previous, after);
+                self.vertices[zipped].splits = splits && self.is_reflex(zipped);
+                zipped
+            } else {
+                self.arcs.push((v.start, p));
+                self.finish(v.previous, p);
+                let previous = &self.vertices[v.previous];
+                let (incoming, before, splits) =
+                    (previous.incoming, previous.previous, previous.splits);
+                let zipped = self.add_vertex(incoming, v.outgoing, p, before, v.next);
+                self.vertices[zipped].splits = splits && self.is_reflex(zipped);
+                zipped
+            };
+            self.tidy(zipped);
+        } else {
+            self.schedule_edge(v.previous);
+            self.schedule_edge(vertex);
+            self.schedule_splits(vertex);
+        }
+    }
+
+    /// end a wavefront left with less than three vertices, return whether it did
+    fn collapse_small_ring(&mut self, vertex: usize) -> bool {
+        let next = self.vertices[vertex].next;
+        if next == vertex {
+            self.vertices[vertex].active = false;
+            true
+        } else if self.vertices[next].next == vertex {
+            // the two vertices are connected by a wavefront without area
+            let end = self.vertices[next].start;
+            self.finish(vertex, end);
+            self.vertices[next].active = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// the rings of the wavefront at the given time, which must not be later than the next event
+    fn rings(&self, time: f64) -> Vec<Vec<Point>> {
+        let mut visited = vec![false; self.vertices.len()];
+        let mut rings = Vec::new();
+        for start in 0..self.vertices.len() {
+            if !self.vertices[start].active || visited[start] {
+                continue;
+            }
+            let mut ring = Vec::new();
+            let mut v = start;
+            while !visited[v] {
+                visited[v] = true;
+                ring.push(self.position(v, time));
+                v = self.vertices[v].next;
+            }
+            rings.push(ring);
+        }
+        rings
+    }
+}
+
+/// The point where three moving edge lines meet, and the time at which they meet. None if two lines are parallel.
+/// This constructs a point from the rounded unit normals of the lines instead of deciding a sign, so the exact
+/// predicates do not apply: nearly parallel lines are treated as parallel on purpose, as they would only meet far
+/// outside the polygon.
+fn meeting_point(a: &EdgeLine, b: &EdgeLine, c: &EdgeLine) -> Option<(Point, f64)> {
+    // solve nx * x + ny * y - t = c for all three lines with Cramer's rule
+    let det3 = |m: [[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let rows = [a, b, c].map(|l| [l.nx, l.ny, -1.0]);
+    let det = det3(rows);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let rhs = [a.c, b.c, c.c];
+    let solve = |column: usize| {
+        let mut m = rows;
+        for (row, value) in m.iter_mut().zip(rhs) {
+            row[column] = value;
+        }
+        det3(m) / det
+    };
+    Some((Point::new(solve(0), solve(1)), solve(2)))
+}
+
+/// the corners of a ring: without the closing point, duplicate points and vertices on a straight line
+fn corners(points: &[Point]) -> Vec<Point> {
+    let mut ring = points.to_vec();
+    ring.dedup();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    loop {
+        let n = ring.len();
+        let Some(straight) = (0..n).find(|&i| {
+            n > 2 && orient2d(&ring[(i + n - 1) % n], &ring[i], &ring[(i + 1) % n]) == 0.0
+        }) else {
+            return ring;
+        };
+        ring.remove(straight);
+    }
+}
+
+/// Undirected graph of skeleton candidates, split into polylines between its junctions and end points
+struct AxisGraph {
+    nodes: Vec<Point>,
+    neighbors: Vec<Vec<usize>>,
+}
+
+impl AxisGraph {
+    /// remove branches from an end point to a junction which are shorter than the given length, until none is left
+    fn prune(&mut self, min_branch_length: f64) {
+        loop {
+            // collect the short branches first, so several spurs meeting at one junction are removed together
+            let mut branches = Vec::new();
+            for leaf in (0..self.nodes.len()).filter(|&n| self.neighbors[n].len() == 1) {
+                let mut path = vec![leaf];
+                let mut length = 0.0;
+                let (mut previous, mut current) = (leaf, self.neighbors[leaf][0]);
+                loop {
+                    length += self.nodes[previous].distance2D(&self.nodes[current]);
+                    path.push(current);
+                    if self.neighbors[current].len() != 2 {
+                        break;
+                    }
+                    let next = self.neighbors[current]
+                        .iter()
+                        .copied()
+                        .find(|&n| n != previous)
+                        .unwrap();
+                    (previous, current) = (current, next);
+                }
+                if self.neighbors[current].len() >= 3 && length < min_branch_length {
+                    branches.push(path);
+                }
+            }
+            if branches.is_empty() {
+                return;
+            }
+            for pair in branches.iter().flat_map(|path| path.windows(2)) {
+                self.neighbors[pair[0]].retain(|&n| n != pair[1]);
+                self.neighbors[pair[1]].retain(|&n| n != pair[0]);
+            }
+        }
+    }
+
+    /// split the graph into lines between nodes which are not simply passed through, and closed lines for cycles
+    fn lines(&self) -> Vec<Line> {
+        let mut visited: HashSet<(usize, usize)> = HashSet::new();
+        let mut lines = Vec::new();
+        let ends = (0..self.nodes.len()).filter(|&n| self.neighbors[n].len() != 2);
+        let passes = (0..self.nodes.len()).filter(|&n| self.neighbors[n].len() == 2);
+        for start in ends.chain(passes) {
+            for &first in &self.neighbors[start] {
+                if visited.contains(&(start.min(first), start.max(first))) {
+                    continue;
+                }
+                let mut points = vec![self.nodes[start]];
+                let (mut previous, mut current) = (start, first);
+                loop {
+                    visited.insert((previous.min(current), previous.max(current)));
+                    points.push(self.nodes[current]);
+                    if current == start || self.neighbors[current].len() != 2 {
+                        break;
+                    }
+                    let next = self.neighbors[current]
+                        .iter()
+                        .copied()
+                        .find(|&n| n != previous)
+                        .unwrap();
+                    (previous, current) = (current, next);
+                }
+                lines.push(Line::new(points));
+            }
+        }
+        lines
+    }
+}
+
+/// circumcenter of a triangle, None for collinear points
+fn circumcenter(a: &Point, b: &Point, c: &Point) -> Option<Point> {
+    let (bx, by) = (b.get_x() - a.get_x(), b.get_y() - a.get_y());
+    let (cx, cy) = (c.get_x() - a.get_x(), c.get_y() - a.get_y());
+    let d = 2.0 * orient2d(a, b, c);
+    if d == 0.0 {
+        return None;
+    }
+    let (b_len, c_len) = (bx * bx + by * by, cx * cx + cy * cy);
+    Some(Point::new(
+        a.get_x() + (cy * b_len - by * c_len) / d,
+        a.get_y() + (bx * c_len - cx * b_len) / d,
+    ))
+}
+
+impl Polygon {
+    /// Compute the straight skeleton of the polygon, holes included. All edges move inwards with the same speed,
+    /// and the skeleton is traced by the vertices of this shrinking wavefront until it has vanished: it consists of
+    /// the bisector arcs between the nodes where edges shrink to nothing or reflex vertices split the wavefront.
+    /// Return every arc as a line with two points.
+    pub fn straight_skeleton(&self) -> Vec<Line> {
+        let mut wavefront = Wavefront::new(self);
+        wavefront.run(f64::INFINITY);
+        let tolerance = wavefront.tolerance;
+        wavefront
+            .arcs
+            .into_iter()
+            .filter(|(a, b)| a.distance2D(b) > tolerance)
+            .map(|(a, b)| Line::new(vec![a, b]))
+            .collect()
+    }
+
+    /// Shrink the polygon by moving all edges inwards by the given distance, with mitred corners.
+    /// The offset follows the straight skeleton, so parts of the polygon split off or vanish where it gets too
+    /// narrow. Panics if the distance is negative.
+    pub fn inward_offset(&self, distance: f64) -> MultiPolygon {
+        if distance.is_nan() || distance < 0.0 {
+            panic!("Offset distance must not be negative.");
+        }
+        let mut wavefront = Wavefront::new(self);
+        wavefront.run(distance);
+        let segments: Vec<(Point, Point)> = wavefront
+            .rings(distance)
+            .iter()
+            .flat_map(|ring| (0..ring.len()).map(move |i| (ring[i], ring[(i + 1) % ring.len()])))
+            .collect();
+        build_even_odd_from_segments(&segments, &PrecisionModel::floating())
+    }
+
+    /// Approximate the medial axis of the polygon, the centres of all circles touching its boundary in more than one
+    /// point, e.g. the centerline of a river or road. The boundary is sampled every `sample_distance`, and the
+    /// Voronoi vertices of the samples inside the polygon are connected along their Delaunay neighbours.
+    /// Branches from an end point to a junction shorter than `min_branch_length` are pruned, which removes the
+    /// spurs towards corners and boundary noise. Return the axis as lines between end points and junctions.
+    /// Panics if the sample distance is not positive.
+    pub fn medial_axis(&self, sample_distance: f64, min_branch_length: f64) -> Vec<Line> {
+        if sample_distance.is_nan() || sample_distance <= 0.0 {
+            panic!("Sample distance must be positive.");
+        }
+        let mut samples = Vec::new();
+        for ring in std::iter::once(self.get_exterior()).chain(self.get_interiors()) {
+            let points = ring.get_points();
+            let closing =
+                (points.first() != points.last()).then(|| [points[points.len() - 1], points[0]]);
+            for pair in points.windows(2).chain(closing.as_ref().map(|c| &c[..])) {
+                let (a, b) = (&pair[0], &pair[1]);
+                let parts = (a.distance2D(b) / sample_distance).ceil().max(1.0) as usize;
+                samples.extend((0..parts).map(|k| {
+                    let t = k as f64 / parts as f64;
+                    Point::new(
+                        a.get_x() + t * (b.get_x() - a.get_x()),
+                        a.get_y() + t * (b.get_y() - a.get_y()),
+                    )
+                }));
+            }
+        }
+
+        let triangulation = DelaunayTriangulation::new(&samples);
+        let points = triangulation.get_points();
+        let centers: Vec<Option<Point>> = triangulation
+            .get_triangles()
+            .iter()
+            .map(|[a, b, c]| {
+                circumcenter(&points[*a], &points[*b], &points[*c])
+                    .filter(|center| locate_point_in_polygon(center, self) == Location::Interior)
+            })
+            .collect();
+
+        // Voronoi edges between neighbouring triangles, with coinciding circumcenters merged into one node
+        let mut graph = AxisGraph {
+            nodes: Vec::new(),
+            neighbors: Vec::new(),
+        };
+        let mut node_ids: HashMap<PointKey, usize> = HashMap::new();
+        let mut node = |point: Point, graph: &mut AxisGraph| {
+            *node_ids.entry(point.key()).or_insert_with(|| {
+                graph.nodes.push(point);
+                graph.neighbors.push(Vec::new());
+                graph.nodes.len() - 1
+            })
+        };
+        for (t, center) in centers.iter().enumerate() {
+            let Some(center) = center else { continue };
+            for o in triangulation.triangle_neighbors(t).into_iter().flatten() {
+                let Some(other) = centers[o].filter(|_| o > t) else {
+                    continue;
+                };
+                let (u, v) = (node(*center, &mut graph), node(other, &mut graph));
+                if u != v && !graph.neighbors[u].contains(&v) {
+                    graph.neighbors[u].push(v);
+                    graph.neighbors[v].push(u);
+                }
+            }
+        }
+        graph.prune(min_branch_length);
+        graph.lines()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::relate::covers;
+    use crate::geoms::Geometry;
+
+    fn segment(a: (f64, f64), b: (f64, f64)) -> Line {
+        Line::new(vec![Point::new(a.0, a.1), Point::new(b.0, b.1)])
+    }
+
+    fn assert_arcs(skeleton: &[Line], expected: &[Line]) {
+        assert_eq!(skeleton.len(), expected.len(), "{skeleton:?}");
+        for arc in expected {
+            assert!(
+                skeleton.iter().any(|s| s.approx_eq(arc, 1e-9)
+                    || s.approx_eq(
+                        &Line::new(arc.get_points().iter().rev().copied().collect()),
+                        1e-9
+                    )),
+                "missing {} in {skeleton:?}",
+                arc.to_wkt()
+            );
+        }
+    }
+
+    fn area(multipolygon: &MultiPolygon) -> f64 {
+        multipolygon
+            .get_polygons()
+            .iter()
+            .flat_map(|p| std::iter::once(p.get_exterior()).chain(p.get_interiors()))
+            .map(|ring| ring.signed_area().unwrap())
+            .sum()
+    }
+
+    #[test]
+    fn test_skeleton_of_rectangle() {
+        let rectangle = Polygon::from_wkt("POLYGON ((0 0, 4 0, 4 2, 0 2, 0 0))").unwrap();
+        assert_arcs(
+            &rectangle.straight_skeleton(),
+            &[
+                segment((0.0, 0.0), (1.0, 1.0)),
+                segment((0.0, 2.0), (1.0, 1.0)),
+                segment((4.0, 0.0), (3.0, 1.0)),
+                segment((4.0, 2.0), (3.0, 1.0)),
+                segment((1.0, 1.0), (3.0, 1.0)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_skeleton_with_reflex_vertex() {
+        let l_shape = Polygon::from_wkt("POLYGON ((0 0, 4 0, 4 2, 2 2, 2 4, 0 4, 0 0))").unwrap();
+        assert_arcs(
+            &l_shape.straight_skeleton(),
+            &[
+                segment((0.0, 0.0), (1.0, 1.0)),
+                segment((2.0, 2.0), (1.0, 1.0)),
+                segment((4.0, 0.0), (3.0, 1.0)),
+                segment((4.0, 2.0), (3.0, 1.0)),
+                segment((2.0, 4.0), (1.0, 3.0)),
+                segment((0.0, 4.0), (1.0, 3.0)),
+                segment((1.0, 1.0), (3.0, 1.0)),
+                segment((1.0, 1.0), (1.0, 3.0)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_skeleton_with_hole() {
+        let frame =
+            Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (3 3, 7 3, 7 7, 3 7, 3 3))")
+                .unwrap();
+        let skeleton = frame.straight_skeleton();
+        // a diagonal arc from every corner and the square in the middle of the frame
+        assert_eq!(skeleton.len(), 12, "{skeleton:?}");
+        assert!(
+            skeleton
+                .iter()
+                .any(|arc| arc.approx_eq(&segment((0.0, 0.0), (1.5, 1.5)), 1e-9))
+        );
+        assert!(
+            skeleton
+                .iter()
+                .any(|arc| arc.approx_eq(&segment((3.0, 3.0), (1.5, 1.5)), 1e-9))
+        );
+        for arc in &skeleton {
+            let middle = arc.interpolate_line_percentage(0.5).unwrap();
+            assert_ne!(locate_point_in_polygon(&middle, &frame), Location::Exterior);
+        }
+    }
+
+    #[test]
+    fn test_skeleton_of_t_shape() {
+        let t_shape =
+            Polygon::from_wkt("POLYGON ((0 0, 6 0, 6 4, 10 4, 10 6, -4 6, -4 4, 0 4, 0 0))")
+                .unwrap();
+        assert_arcs(
+            &t_shape.straight_skeleton(),
+            &[
+                segment((-4.0, 4.0), (-3.0, 5.0)),
+                segment((-4.0, 6.0), (-3.0, 5.0)),
+                segment((-3.0, 5.0), (1.0, 5.0)),
+                segment((0.0, 4.0), (1.0, 5.0)),
+                segment((10.0, 4.0), (9.0, 5.0)),
+                segment((10.0, 6.0), (9.0, 5.0)),
+                segment((9.0, 5.0), (5.0, 5.0)),
+                segment((6.0, 4.0), (5.0, 5.0)),
+                segment((1.0, 5.0), (3.0, 3.0)),
+                segment((5.0, 5.0), (3.0, 3.0)),
+                segment((0.0, 0.0), (3.0, 3.0)),
+                segment((6.0, 0.0), (3.0, 3.0)),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_skeleton_lies_inside_polygon() {
+        for wkt in [
+            "POLYGON ((0 0, 4 0, 4 2, 2 2, 2 4, 0 4, 0 0))",
+            "POLYGON ((0 0, 6 0, 6 4, 10 4, 10 6, -4 6, -4 4, 0 4, 0 0))",
+            "POLYGON ((0 0, 9 0, 9 5, 8 5, 8 1, 6 1, 6 5, 5 5, 5 1, 3 1, 3 5, 2 5, 2 1, 1 1, 1 5, 0 5, 0 0))",
+            "POLYGON ((0 0, 10 0, 10 3, 7 3, 7 7, 10 7, 10 10, 0 10, 0 7, 3 7, 3 3, 0 3, 0 0))",
+            "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (3 3, 7 3, 7 7, 3 7, 3 3))",
+            "POLYGON ((0 0, 12 0, 12 8, 0 8, 0 0), (2 2, 2 6, 5 6, 5 2, 2 2), (7 2, 7 6, 10 6, 10 2, 7 2))",
+            "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 8, 8 8, 8 6, 4 6, 4 2, 2 2))",
+            // two reflex corners of a neck meet in one point and cut off the lobe below
+            "POLYGON ((4 0, 3 1, 4 3, 5 6, 1 3, 0 2, -1 6, -2 4, -2 2, -6 3, -7 1, -3 0, -4 -2, -1 -2, -2 -7, \
+             3 -7, 3 -4, 2 -2, 7 -2, 4 0))",
+        ] {
+            let polygon = Polygon::from_wkt(wkt).unwrap();
+            let skeleton = polygon.straight_skeleton();
+            assert!(!skeleton.is_empty(), "{wkt}");
+            let area: Geometry = polygon.clone().into();
+            for arc in &skeleton {
+                assert!(
+                    covers(&area, &arc.clone().into()),
+                    "{} leaves {wkt}",
+                    arc.to_wkt()
+                );
+            }
+            // every corner of the polygon starts an arc
+            for ring in std::iter::once(polygon.get_exterior()).chain(polygon.get_interiors()) {
+                for corner in ring.get_points() {
+                    assert!(
+                        skeleton
+                            .iter()
+                            .any(|arc| arc.get_start() == *corner || arc.get_end() == *corner),
+                        "{wkt}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_inward_offset() {
+        let square = Polygon::from_wkt("POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0))").unwrap();
+        let offset = square.inward_offset(1.0);
+        assert_eq!(offset.get_polygons().len(), 1);
+        assert!((area(&offset) - 4.0).abs() < 1e-9);
+        assert!(square.inward_offset(2.5).get_polygons().is_empty());
+
+        let l_shape = Polygon::from_wkt("POLYGON ((0 0, 2 0, 2 1, 1 1, 1 2, 0 2, 0 0))").unwrap();
+        assert!((area(&l_shape.inward_offset(0.25)) - 1.25).abs() < 1e-9);
+
+        // a dumbbell splits into two squares once the bar has vanished
+        let dumbbell = Polygon::from_wkt(
+            "POLYGON ((0 0, 4 0, 4 1.5, 6 1.5, 6 0, 10 0, 10 4, 6 4, 6 2.5, 4 2.5, 4 4, 0 4, 0 0))",
+        )
+        .unwrap();
+        let parts = dumbbell.inward_offset(1.0);
+        assert_eq!(parts.get_polygons().len(), 2);
+        assert!((area(&parts) - 8.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_negative_offset() {
+        Polygon::from_wkt("POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0))")
+            .unwrap()
+            .inward_offset(-1.0);
+    }
+
+    #[test]
+    fn test_medial_axis_of_road() {
+        let road = Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 2, 0 2, 0 0))").unwrap();
+        let axis = road.medial_axis(0.1, 1.5);
+        assert_eq!(axis.len(), 1, "{axis:?}");
+        let points = axis[0].get_points();
+        assert!(points.iter().all(|p| (p.get_y() - 1.0).abs() < 1e-9));
+        let (start, end) = (axis[0].get_start().get_x(), axis[0].get_end().get_x());
+        assert!((start.min(end) - 1.0).abs() < 1e-9 && (start.max(end) - 9.0).abs() < 1e-9);
+        // without pruning the spurs towards the corners remain
+        assert!(road.medial_axis(0.1, 0.0).len() > 1);
+    }
+
+    #[test]
+    fn test_medial_axis_around_hole() {
+        let frame =
+            Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (3 3, 7 3, 7 7, 3 7, 3 3))")
+                .unwrap();
+        let axis = frame.medial_axis(0.1, 2.5);
+        assert_eq!(axis.len(), 1, "{axis:?}");
+        assert_eq!(axis[0].get_start(), axis[0].get_end());
+        assert!(axis[0].get_length() > 20.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_invalid_sample_distance() {
+        Polygon::from_wkt("POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0))")
+            .unwrap()
+            .medial_axis(0.0, 1.0);
+    }
+}
@@ -0,0 +1,641 @@
+use super::predicates::orient2d;
+use crate::geoms::point::Point;
+
+/// Node of the circular doubly linked list used while clipping ears
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    /// index of the vertex in the input vertex list
+    i: usize,
+    /// x coordinate of the vertex
+    x: f64,
+    /// y coordinate of the vertex
+    y: f64,
+    /// index of the previous node
+    prev: usize,
+    /// index of the next node
+    next: usize,
+    /// true for single point holes, these must never be filtered out
+    steiner: bool,
+}
+
+/// Ear clipping state: all nodes live in one arena and are linked by index
+struct EarClipper {
+    nodes: Vec<Node>,
+    triangles: Vec<[usize; 3]>,
+}
+
+/// Triangulate a polygon by ear clipping.
+///
+/// `vertices` holds the exterior ring followed by all holes, every ring without its closing point.
+/// `hole_starts` holds the index of the first vertex of every hole. Holes are connected to the
+/// exterior ring through bridge edges before the ears are clipped. Collinear and duplicate
+/// vertices are tolerated. The returned triangles are index triples into `vertices` and are
+/// oriented counter-clockwise.
+pub fn ear_clipping(vertices: &[Point], hole_starts: &[usize]) -> Vec<[usize; 3]> {
+    let mut clipper = EarClipper {
+        nodes: Vec::with_capacity(vertices.len() * 3 / 2),
+        triangles: Vec::with_capacity(vertices.len().saturating_sub(2)),
+    };
+
+    let outer_end = hole_starts.first().copied().unwrap_or(vertices.len());
+    let Some(mut outer) = clipper.linked_list(vertices, 0, outer_end, true) else {
+        return clipper.triangles;
+    };
+    if clipper.nodes[outer].next == clipper.nodes[outer].prev {
+        return clipper.triangles;
+    }
+    if !hole_starts.is_empty() {
+        outer = clipper.eliminate_holes(vertices, hole_starts, outer);
+    }
+    clipper.earcut_linked(Some(outer), 0);
+    clipper.triangles
+}
+
+/// twice the signed area of the ring `vertices[start..end]`, positive for counter-clockwise rings
+fn signed_area(vertices: &[Point], start: usize, end: usize) -> f64 {
+    let ring = &vertices[start..end];
+    let mut sum = 0.0;
+    for (i, point) in ring.iter().enumerate() {
+        let next = &ring[(i + 1) % ring.len()];
+        sum += point.get_x() * next.get_y() - next.get_x() * point.get_y();
+    }
+    sum
+}
+
+impl EarClipper {
+    fn node(&self, n: usize) -> &Node {
+        &self.nodes[n]
+    }
+
+    fn next(&self, n: usize) -> usize {
+        self.nodes[n].next
+    }
+
+    fn prev(&self, n: usize) -> usize {
+        self.nodes[n].prev
+    }
+
+    /// create a circular linked list from a ring, forcing counter-clockwise (exterior) or
+    /// clockwise (holes) orientation. Returns the last inserted node.
+    fn linked_list(
+        &mut self,
+        vertices: &[Point],
+        start: usize,
+        end: usize,
+        counter_clockwise: bool,
+    ) -> Option<usize> {
+        if start >= end {
+            return None;
+        }
+        let mut last = None;
+        if counter_clockwise == (signed_area(vertices, start, end) > 0.0) {
+            for (i, point) in vertices.iter().enumerate().take(end).skip(start) {
+                last = Some(self.insert_node(i, point, last));
+            }
+        } else {
+            for (i, point) in vertices.iter().enumerate().take(end).skip(start).rev() {
+                last = Some(self.insert_node(i, point, last));
+            }
+        }
+        if let Some(l) = last {
+            let next = self.next(l);
+            if self.equals(l, next) {
+                self.remove_node(l);
+                last = Some(next);
+            }
+        }
+        last
+    }
+
+    fn insert_node(&mut self, i: usize, point: &Point, last: Option<usize>) -> usize {
+        let n = self.nodes.len();
+        let mut node = Node {
+            i,
+            x: point.get_x(),
+            y: point.get_y(),
+            prev: n,
+            next: n,
+            steiner: false,
+        };
+        if let Some(l) = last {
+            node.next = self.next(l);
+            node.prev = l;
+            let l_next = self.next(l);
+            self.nodes[l_next].prev = n;
+            self.nodes[l].next = n;
+        }
+        self.nodes.push(node);
+        n
+    }
+
+    fn remove_node(&mut self, n: usize) {
+        let Node { prev, next, .. } = self.nodes[n];
+        self.nodes[next].prev = prev;
+        self.nodes[prev].next = next;
+    }
+
+    fn equals(&self, a: usize, b: usize) -> bool {
+        self.node(a).x == self.node(b).x && self.node(a).y == self.node(b).y
+    }
+
+    /// signed area of the triangle p, q, r. Negative for a left (convex) turn.
+    fn area(&self, p: usize, q: usize, r: usize) -> f64 {
+        let (p, q, r) = (self.node(p), self.node(q), self.node(r));
+        -orient2d(
+            &Point::new(p.x, p.y),
+            &Point::new(q.x, q.y),
+            &Point::new(r.x, r.y),
+        )
+    }
+
+    /// remove duplicate and collinear points between `start` and `end`
+    fn filter_points(&mut self, start: usize, end: Option<usize>) -> usize {
+        let mut end = end.unwrap_or(start);
+        let mut p = start;
+        loop {
+            let mut again = false;
+            let next = self.next(p);
+            if !self.node(p).steiner
+                && (self.equals(p, next) || self.area(self.prev(p), p, next) == 0.0)
+            {
+                self.remove_node(p);
+                p = self.prev(p);
+                end = p;
+                if p == self.next(p) {
+                    break;
+                }
+                again = true;
+            } else {
+                p = next;
+            }
+            if !again && p == end {
+                break;
+            }
+        }
+        end
+    }
+
+    /// main ear slicing loop which triangulates the polygon given as a linked list
+    fn earcut_linked(&mut self, ear: Option<usize>, pass: u8) {
+        let Some(mut ear) = ear else {
+            return;
+        };
+        let mut stop = ear;
+        while self.prev(ear) != self.next(ear) {
+            let prev = self.prev(ear);
+            let next = self.next(ear);
+            if self.is_ear(ear) {
+                self.triangles
+                    .push([self.node(prev).i, self.node(ear).i, self.node(next).i]);
+                self.remove_node(ear);
+                // skipping the next vertex leads to less sliver triangles
+                ear = self.next(next);
+                stop = ear;
+                continue;
+            }
+            ear = next;
+            // no ears found in a full loop, try to recover from degenerate input
+            if ear == stop {
+                match pass {
+                    0 => {
+                        let filtered = self.filter_points(ear, None);
+                        self.earcut_linked(Some(filtered), 1);
+                    }
+                    1 => {
+                        let filtered = self.filter_points(ear, None);
+                        let cured = self.cure_local_intersections(filtered);
+                        self.earcut_linked(Some(cured), 2);
+                    }
+                    _ => self.split_earcut(ear),
+                }
+                break;
+            }
+        }
+    }
+
+    /// check whether a polygon node forms a valid ear with its neighbours
+    fn is_ear(&self, ear: usize) -> bool {
+        let a = self.prev(ear);
+        let c = self.next(ear);
+        if self.area(a, ear, c) >= 0.0 {
+            // reflex, can't be an ear
+            return false;
+        }
+        let (na, nb, nc) = (self.node(a), self.node(ear), self.node(c));
+        let min_x = na.x.min(nb.x).min(nc.x);
+        let min_y = na.y.min(nb.y).min(nc.y);
+        let max_x = na.x.max(nb.x).max(nc.x);
+        let max_y = na.y.max(nb.y).max(nc.y);
+
+        // make sure no other point lies inside the potential ear
+        let mut p = self.next(c);
+        while p != a {
+            let np = self.node(p);
+            if np.x >= min_x
+                && np.x <= max_x
+                && np.y >= min_y
+                && np.y <= max_y
+                && !(np.x == na.x && np.y == na.y)
+                && point_in_triangle(na, nb, nc, np.x, np.y)
+                && self.area(self.prev(p), p, self.next(p)) >= 0.0
+            {
+                return false;
+            }
+            p = self.next(p);
+        }
+        true
+    }
+
+    /// go through all polygon nodes and cure small local self-intersections
+    fn cure_local_intersections(&mut self, start: usize) -> usize {
+        let mut start = start;
+        let mut p = start;
+        loop {
+            let a = self.prev(p);
+            let b = self.next(self.next(p));
+            if !self.equals(a, b)
+                && self.intersects(a, p, self.next(p), b)
+                && self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+            {
+                self.triangles
+                    .push([self.node(a).i, self.node(p).i, self.node(b).i]);
+                let p_next = self.next(p);
+                self.remove_node(p);
+                self.remove_node(p_next);
+                p = b;
+                start = b;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        self.filter_points(p, None)
+    }
+
+    /// try splitting the polygon into two and triangulate them independently
+    fn split_earcut(&mut self, start: usize) {
+        let mut a = start;
+        loop {
+            let mut b = self.next(self.next(a));
+            while b != self.prev(a) {
+                if self.node(a).i != self.node(b).i && self.is_valid_diagonal(a, b) {
+                    let c = self.split_polygon(a, b);
+                    let a_next = self.next(a);
+                    let a = self.filter_points(a, Some(a_next));
+                    let c_next = self.next(c);
+                    let c = self.filter_points(c, Some(c_next));
+                    self.earcut_linked(Some(a), 0);
+                    self.earcut_linked(Some(c), 0);
+                    return;
+                }
+                b = self.next(b);
+            }
+            a = self.next(a);
+            if a == start {
+                break;
+            }
+        }
+    }
+
+    /// link every hole into the outer loop, producing a single ring polygon without holes
+    fn eliminate_holes(
+        &mut self,
+        vertices: &[Point],
+        hole_starts: &[usize],
+        outer: usize,
+    ) -> usize {
+        let mut queue = Vec::with_capacity(hole_starts.len());
+        for (k, &start) in hole_starts.iter().enumerate() {
+            let end = hole_starts.get(k + 1).copied().unwrap_or(vertices.len());
+            if let Some(list) = self.linked_list(vertices, start, end, false) {
+                if list == self.next(list) {
+                    self.nodes[list].steiner = true;
+                }
+                queue.push(self.leftmost(list));
+            }
+        }
+        queue.sort_by(|&a, &b| {
+            let (na, nb) = (self.node(a), self.node(b));
+            na.x.total_cmp(&nb.x).then(na.y.total_cmp(&nb.y))
+        });
+
+        let mut outer = outer;
+        for hole in queue {
+            outer = self.eliminate_hole(hole, outer);
+        }
+        outer
+    }
+
+    fn eliminate_hole(&mut self, hole: usize, outer: usize) -> usize {
+        let Some(bridge) = self.find_hole_bridge(hole, outer) else {
+            return outer;
+        };
+        let bridge_reverse = self.split_polygon(bridge, hole);
+        let bridge_reverse_next = self.next(bridge_reverse);
+        self.filter_points(bridge_reverse, Some(bridge_reverse_next));
+        let bridge_next = self.next(bridge);
+        self.filter_points(bridge, Some(bridge_next))
+    }
+
+    /// find a vertex of the outer ring that can be connected to the leftmost point of a hole
+    fn find_hole_bridge(&self, hole: usize, outer: usize) -> Option<usize> {
+        let hx = self.node(hole).x;
+        let hy = self.node(hole).y;
+        let mut qx = f64::NEG_INFINITY;
+        let mut m = None;
+
+        // find a segment intersected by a ray from the hole's leftmost point to the left;
+        // the segment's endpoint with lesser x will be a potential connection point
+        let mut p = outer;
+        loop {
+            let (np, nn) = (self.node(p), self.node(self.next(p)));
+            if hy <= np.y && hy >= nn.y && nn.y != np.y {
+                let x = np.x + (hy - np.y) * (nn.x - np.x) / (nn.y - np.y);
+                if x <= hx && x > qx {
+                    qx = x;
+                    m = Some(if np.x < nn.x { p } else { self.next(p) });
+                    if x == hx {
+                        // the hole touches the outer segment, pick the leftmost endpoint
+                        return m;
+                    }
+                }
+            }
+            p = self.next(p);
+            if p == outer {
+                break;
+            }
+        }
+        let mut m = m?;
+
+        // look for points inside the triangle of hole point, segment intersection and endpoint;
+        // if there are none, m is fine, otherwise take the point with the minimum angle
+        let stop = m;
+        let (mx, my) = (self.node(m).x, self.node(m).y);
+        let mut tan_min = f64::INFINITY;
+        let (ax, cx) = if hy < my { (hx, qx) } else { (qx, hx) };
+        let a = (ax, hy);
+        let b = (mx, my);
+        let c = (cx, hy);
+        p = m;
+        loop {
+            let np = self.node(p);
+            if hx >= np.x && np.x >= mx && hx != np.x && point_in_triangle_xy(a, b, c, np.x, np.y) {
+                let tan = (hy - np.y).abs() / (hx - np.x);
+                if self.locally_inside(p, hole)
+                    && (tan < tan_min
+                        || (tan == tan_min
+                            && (np.x > self.node(m).x
+                                || (np.x == self.node(m).x && self.sector_contains_sector(m, p)))))
+                {
+                    m = p;
+                    tan_min = tan;
+                }
+            }
+            p = self.next(p);
+            if p == stop {
+                break;
+            }
+        }
+        Some(m)
+    }
+
+    /// whether sector in vertex m contains sector in vertex p in the same coordinates
+    fn sector_contains_sector(&self, m: usize, p: usize) -> bool {
+        self.area(self.prev(m), m, self.prev(p)) < 0.0
+            && self.area(self.next(p), m, self.next(m)) < 0.0
+    }
+
+    fn leftmost(&self, start: usize) -> usize {
+        let mut p = start;
+        let mut leftmost = start;
+        loop {
+            let (np, nl) = (self.node(p), self.node(leftmost));
+            if np.x < nl.x || (np.x == nl.x && np.y < nl.y) {
+                leftmost = p;
+            }
+            p = self.next(p);
+            if p == start {
+                break;
+            }
+        }
+        leftmost
+    }
+
+    /// check if a diagonal between two polygon nodes is valid (lies in the polygon interior)
+    fn is_valid_diagonal(&self, a: usize, b: usize) -> bool {
+        let (a_next, a_prev) = (self.next(a), self.prev(a));
+        let (b_next, b_prev) = (self.next(b), self.prev(b));
+        self.node(a_next).i != self.node(b).i
+            && self.node(a_prev).i != self.node(b).i
+            && !self.intersects_polygon(a, b)
+            && ((self.locally_inside(a, b)
+                && self.locally_inside(b, a)
+                && self.middle_inside(a, b)
+                // does not create opposite-facing sectors
+                && (self.area(a_prev, a, b_prev) != 0.0 || self.area(a, b_prev, b) != 0.0))
+                // special zero-length case
+                || (self.equals(a, b)
+                    && self.area(a_prev, a, a_next) > 0.0
+                    && self.area(b_prev, b, b_next) > 0.0))
+    }
+
+    /// check if two segments p1-q1 and p2-q2 intersect
+    fn intersects(&self, p1: usize, q1: usize, p2: usize, q2: usize) -> bool {
+        let o1 = sign(self.area(p1, q1, p2));
+        let o2 = sign(self.area(p1, q1, q2));
+        let o3 = sign(self.area(p2, q2, p1));
+        let o4 = sign(self.area(p2, q2, q1));
+
+        (o1 != o2 && o3 != o4)
+            || (o1 == 0 && self.on_segment(p1, p2, q1))
+            || (o2 == 0 && self.on_segment(p1, q2, q1))
+            || (o3 == 0 && self.on_segment(p2, p1, q2))
+            || (o4 == 0 && self.on_segment(p2, q1, q2))
+    }
+
+    /// for collinear points p, q, r check if point q lies on segment pr
+    fn on_segment(&self, p: usize, q: usize, r: usize) -> bool {
+        let (p, q, r) = (self.node(p), self.node(q), self.node(r));
+        q.x <= p.x.max(r.x) && q.x >= p.x.min(r.x) && q.y <= p.y.max(r.y) && q.y >= p.y.min(r.y)
+    }
+
+    /// check if a polygon diagonal intersects any polygon segments
+    fn intersects_polygon(&self, a: usize, b: usize) -> bool {
+        let (ai, bi) = (self.node(a).i, self.node(b).i);
+        let mut p = a;
+        loop {
+            let next = self.next(p);
+            let (pi, ni) = (self.node(p).i, self.node(next).i);
+            if pi != ai && ni != ai && pi != bi && ni != bi && self.intersects(p, next, a, b) {
+                return true;
+            }
+            p = next;
+            if p == a {
+                break;
+            }
+        }
+        false
+    }
+
+    /// check if a polygon diagonal is locally inside the polygon
+    fn locally_inside(&self, a: usize, b: usize) -> bool {
+        let (a_prev, a_next) = (self.prev(a), self.next(a));
+        if self.area(a_prev, a, a_next) < 0.0 {
+            self.area(a, b, a_next) >= 0.0 && self.area(a, a_prev, b) >= 0.0
+        } else {
+            self.area(a, b, a_prev) < 0.0 || self.area(a, a_next, b) < 0.0
+        }
+    }
+
+    /// check if the middle point of a polygon diagonal is inside the polygon
+    fn middle_inside(&self, a: usize, b: usize) -> bool {
+        let px = (self.node(a).x + self.node(b).x) / 2.0;
+        let py = (self.node(a).y + self.node(b).y) / 2.0;
+        let mut inside = false;
+        let mut p = a;
+        loop {
+            let (np, nn) = (self.node(p), self.node(self.next(p)));
+            if ((np.y > py) != (nn.y > py))
+                && nn.y != np.y
+                && px < (nn.x - np.x) * (py - np.y) / (nn.y - np.y) + np.x
+            {
+                inside = !inside;
+            }
+            p = self.next(p);
+            if p == a {
+                break;
+            }
+        }
+        inside
+    }
+
+    /// link two polygon vertices with a bridge. If the vertices belong to the same ring, the
+    /// polygon is split in two, if they belong to different rings, they are merged into one.
+    fn split_polygon(&mut self, a: usize, b: usize) -> usize {
+        let mut a2 = self.nodes[a];
+        let mut b2 = self.nodes[b];
+        let a2_index = self.nodes.len();
+        let b2_index = a2_index + 1;
+        let a_next = self.next(a);
+        let b_prev = self.prev(b);
+
+        self.nodes[a].next = b;
+        self.nodes[b].prev = a;
+
+        a2.next = a_next;
+        a2.prev = b2_index;
+        b2.next = a2_index;
+        b2.prev = b_prev;
+        self.nodes.push(a2);
+        self.nodes.push(b2);
+
+        self.nodes[a_next].prev = a2_index;
+        self.nodes[b_prev].next = b2_index;
+        b2_index
+    }
+}
+
+fn sign(value: f64) -> i8 {
+    if value > 0.0 {
+        1
+    } else if value < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+/// check if a point lies within a convex triangle (boundary included)
+fn point_in_triangle(a: &Node, b: &Node, c: &Node, px: f64, py: f64) -> bool {
+    point_in_triangle_xy((a.x, a.y), (b.x, b.y), (c.x, c.y), px, py)
+}
+
+fn point_in_triangle_xy(a: (f64, f64), b: (f64, f64), c: (f64, f64), px: f64, py: f64) -> bool {
+    let [a, b, c, p] = [a, b, c, (px, py)].map(|(x, y)| Point::new(x, y));
+    orient2d(&c, &a, &p) >= 0.0 && orient2d(&a, &b, &p) >= 0.0 && orient2d(&b, &c, &p) >= 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn triangles_area(vertices: &[Point], triangles: &[[usize; 3]]) -> f64 {
+        triangles
+            .iter()
+            .map(|t| {
+                let (a, b, c) = (&vertices[t[0]], &vertices[t[1]], &vertices[t[2]]);
+                ((b.get_x() - a.get_x()) * (c.get_y() - a.get_y())
+                    - (c.get_x() - a.get_x()) * (b.get_y() - a.get_y()))
+                    / 2.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_square() {
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+        let triangles = ear_clipping(&vertices, &[]);
+        assert_eq!(triangles.len(), 2);
+        assert!((triangles_area(&vertices, &triangles) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_clockwise_input_gives_ccw_triangles() {
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, 2.0),
+            Point::new(3.0, 2.0),
+            Point::new(3.0, 0.0),
+        ];
+        let triangles = ear_clipping(&vertices, &[]);
+        assert_eq!(triangles.len(), 2);
+        // every triangle has a positive area, so the sum equals the polygon area
+        assert!((triangles_area(&vertices, &triangles) - 6.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_square_with_hole() {
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            Point::new(10.0, 10.0),
+            Point::new(0.0, 10.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 8.0),
+            Point::new(8.0, 8.0),
+            Point::new(8.0, 2.0),
+        ];
+        let triangles = ear_clipping(&vertices, &[4]);
+        assert_eq!(triangles.len(), 8);
+        assert!((triangles_area(&vertices, &triangles) - 64.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_collinear_and_duplicate_vertices() {
+        let vertices = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(2.0, 0.0),
+            Point::new(2.0, 2.0),
+            Point::new(0.0, 2.0),
+            Point::new(0.0, 1.0),
+        ];
+        let triangles = ear_clipping(&vertices, &[]);
+        assert!((triangles_area(&vertices, &triangles) - 4.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_degenerate_input() {
+        let vertices = vec![Point::new(0.0, 0.0), Point::new(1.0, 1.0)];
+        assert!(ear_clipping(&vertices, &[]).is_empty());
+        assert!(ear_clipping(&[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,768 @@
+use super::intersection::{SegmentIntersection, segment_intersection};
+use super::locate::{Location, locate_point_in_ring};
+use super::noding::node_segments_snap_rounded;
+use super::precision::PrecisionModel;
+use super::predicates::orient2d;
+use crate::geoms::line::{Line, ring_signed_area};
+use crate::geoms::multipolygon::MultiPolygon;
+use crate::geoms::point::{Point, PointKey};
+use crate::geoms::polygon::Polygon;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Kind of problem which makes a geometry invalid
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ValidityProblem {
+    /// a coordinate is NaN or infinite
+    NonFiniteCoordinate,
+    /// a point is repeated directly after itself
+    DuplicatePoint,
+    /// a line has fewer than two, a ring fewer than three distinct points
+    TooFewPoints,
+    /// the first and last point of a ring differ
+    RingNotClosed,
+    /// a ring crosses or overlaps itself or another ring of the polygon
+    SelfIntersection,
+    /// a hole lies outside of the exterior ring
+    HoleOutsideShell,
+    /// the exterior ring is not counter-clockwise or a hole is not clockwise
+    WrongOrientation,
+}
+
+impl ValidityProblem {
+    /// get a short description of the problem
+    pub fn description(&self) -> &'static str {
+        match self {
+            ValidityProblem::NonFiniteCoordinate => "Non-finite coordinate",
+            ValidityProblem::DuplicatePoint => "Consecutive duplicate point",
+            ValidityProblem::TooFewPoints => "Too few distinct points",
+            ValidityProblem::RingNotClosed => "Ring is not closed",
+            ValidityProblem::SelfIntersection => "Self-intersection",
+            ValidityProblem::HoleOutsideShell => "Hole lies outside shell",
+            ValidityProblem::WrongOrientation => "Wrong ring orientation",
+        }
+    }
+}
+
+/// A problem found while checking the validity of a geometry, with its location
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ValidityIssue {
+    /// kind of problem
+    problem: ValidityProblem,
+    /// location of the problem
+    location: Point,
+}
+
+impl ValidityIssue {
+    /// get the kind of problem
+    pub fn get_problem(&self) -> ValidityProblem {
+        self.problem
+    }
+
+    /// get the location of the problem
+    pub fn get_location(&self) -> Point {
+        self.location
+    }
+}
+
+impl fmt::Display for ValidityIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}",
+            self.problem.description(),
+            self.location.to_wkt()
+        )
+    }
+}
+
+/// collects issues, ignoring repeated reports of the same problem at the same location
+#[derive(Default)]
+struct Report {
+    issues: Vec<ValidityIssue>,
+}
+
+impl Report {
+    fn add(&mut self, problem: ValidityProblem, location: Point) {
+        let issue = ValidityIssue { problem, location };
+        if !self.issues.contains(&issue) {
+            self.issues.push(issue);
+        }
+    }
+}
+
+impl Line {
+    /// check if the line is valid, see [`Line::validity_report`]
+    pub fn is_valid(&self) -> bool {
+        self.validity_report().is_empty()
+    }
+
+    /// Check the line for non-finite coordinates, consecutive duplicate points and fewer than two distinct points.
+    /// Return every problem found with its location, an empty list means the line is valid.
+    pub fn validity_report(&self) -> Vec<ValidityIssue> {
+        let mut report = Report::default();
+        let points = self.get_points();
+        check_points(points, &mut report);
+        if distinct_points(points).len() < 2 {
+            report.add(ValidityProblem::TooFewPoints, self.get_start());
+        }
+        report.issues
+    }
+}
+
+impl Polygon {
+    /// check if the polygon is valid, see [`Polygon::validity_report`]
+    pub fn is_valid(&self) -> bool {
+        self.validity_report().is_empty()
+    }
+
+    /// Check the polygon for non-finite coordinates, consecutive duplicate points, rings with fewer than three
+    /// distinct points, rings that are not closed, self-intersections, holes outside the shell and wrong ring
+    /// orientation (the shell must be counter-clockwise, holes clockwise).
+    /// Return every problem found with its location, an empty list means the polygon is valid.
+    pub fn validity_report(&self) -> Vec<ValidityIssue> {
+        let mut report = Report::default();
+        let rings: Vec<&Line> = std::iter::once(self.get_exterior())
+            .chain(self.get_interiors())
+            .collect();
+
+        // rings which are usable for the geometric checks, as open rings without duplicates
+        let mut usable: Vec<Option<Vec<Point>>> = Vec::with_capacity(rings.len());
+        for (k, ring) in rings.iter().enumerate() {
+            let points = ring.get_points();
+            let finite = check_points(points, &mut report);
+            if ring.get_start() != ring.get_end() {
+                report.add(ValidityProblem::RingNotClosed, ring.get_end());
+            }
+            let open = distinct_points(points);
+            if open.len() < 3 {
+                report.add(ValidityProblem::TooFewPoints, ring.get_start());
+            }
+            if !finite || open.len() < 3 {
+                usable.push(None);
+                continue;
+            }
+            check_ring_self_intersection(&open, &mut report);
+            let orientation = ring_orientation(&open);
+            if (k == 0 && orientation < 0) || (k > 0 && orientation > 0) {
+                report.add(ValidityProblem::WrongOrientation, open[0]);
+            }
+            usable.push(Some(open));
+        }
+
+        // checks between rings
+        for i in 0..usable.len() {
+            let Some(ring_i) = &usable[i] else {
+                continue;
+            };
+            for ring_j in usable[(i + 1)..].iter().flatten() {
+                check_ring_crossings(ring_i, ring_j, &mut report);
+            }
+            if i > 0
+                && let Some(shell) = &usable[0]
+            {
+                let outside = ring_i
+                    .iter()
+                    .map(|p| (p, locate_point_in_ring(p, shell)))
+                    .find(|(_, location)| *location != Location::Boundary);
+                if let Some((p, Location::Exterior)) = outside {
+                    report.add(ValidityProblem::HoleOutsideShell, *p);
+                }
+            }
+        }
+        report.issues
+    }
+
+    /// Repair the polygon into a valid multipolygon.
+    ///
+    /// Non-finite coordinates and duplicate points are dropped and all rings are cut at their intersections.
+    /// The area is then rebuilt with the even-odd rule: a location belongs to the result if it is enclosed by an
+    /// odd number of rings. A self-intersecting shell like a bow-tie thus becomes two polygons, and a hole outside
+    /// of the shell becomes a polygon of its own. Valid polygons are returned unchanged.
+    pub fn make_valid(&self) -> MultiPolygon {
+        self.make_valid_with_precision(&PrecisionModel::floating())
+    }
+
+    /// Repair the polygon into a valid multipolygon like [`Polygon::make_valid`], with all coordinates of the
+    /// result on the grid of the precision model. The rings are cut with snap rounding, so parts narrower than the
+    /// grid size collapse instead of leaving slivers. Polygons which are valid after rounding are returned rounded.
+    pub fn make_valid_with_precision(&self, precision: &PrecisionModel) -> MultiPolygon {
+        let polygon = precision.snap_polygon(self);
+        if polygon.is_valid() {
+            return MultiPolygon::new(vec![polygon]);
+        }
+        let rings: Vec<Vec<Point>> = std::iter::once(polygon.get_exterior())
+            .chain(polygon.get_interiors())
+            .map(|ring| {
+                let finite: Vec<Point> = ring
+                    .get_points()
+                    .iter()
+                    .filter(|p| p.get_x().is_finite() && p.get_y().is_finite())
+                    .copied()
+                    .collect();
+                distinct_points(&finite)
+            })
+            .collect();
+        build_even_odd(&rings, precision)
+    }
+}
+
+/// report non-finite coordinates and consecutive duplicates, return false if a coordinate is non-finite
+fn check_points(points: &[Point], report: &mut Report) -> bool {
+    let mut finite = true;
+    for point in points {
+        if !point.get_x().is_finite() || !point.get_y().is_finite() {
+            report.add(ValidityProblem::NonFiniteCoordinate, *point);
+            finite = false;
+        }
+    }
+    for pair in points.windows(2) {
+        if pair[0] == pair[1] {
+            report.add(ValidityProblem::DuplicatePoint, pair[1]);
+        }
+    }
+    finite
+}
+
+/// remove consecutive duplicates and the closing point of a ring
+fn distinct_points(points: &[Point]) -> Vec<Point> {
+    let mut distinct = points.to_vec();
+    distinct.dedup();
+    if distinct.len() > 1 && distinct.first() == distinct.last() {
+        distinct.pop();
+    }
+    distinct
+}
+
+/// check if the distinct points of a ring are at least three and their segments do not touch or cross each other
+pub(crate) fn is_simple_ring(points: &[Point]) -> bool {
+    let ring = distinct_points(points);
+    if ring.len() < 3 {
+        return false;
+    }
+    let mut report = Report::default();
+    check_ring_self_intersection(&ring, &mut report);
+    report.issues.is_empty()
+}
+
+/// Orientation of an open ring: 1 for counter-clockwise, -1 for clockwise and 0 for rings without area. It is decided
+/// by [`Line::is_ccw`] at the lowest vertex with the exact orientation predicate, because the sign of the summed
+/// area of a sliver can be wrong.
+fn ring_orientation(ring: &[Point]) -> i8 {
+    let mut line = Line::new(ring.iter().chain(ring.first()).copied().collect());
+    if line.is_ccw() {
+        return 1;
+    }
+    line.reverse();
+    if line.is_ccw() { -1 } else { 0 }
+}
+
+/// report segments of an open ring which intersect anywhere but at their shared vertex
+fn check_ring_self_intersection(ring: &[Point], report: &mut Report) {
+    let n = ring.len();
+    for i in 0..n {
+        let (a, b) = (&ring[i], &ring[(i + 1) % n]);
+        for j in (i + 1)..n {
+            let (c, d) = (&ring[j], &ring[(j + 1) % n]);
+            let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+            match segment_intersection(a, b, c, d) {
+                SegmentIntersection::None => {}
+                SegmentIntersection::Point(p) => {
+                    if !adjacent {
+                        report.add(ValidityProblem::SelfIntersection, p);
+                    }
+                }
+                SegmentIntersection::Overlap(p, _) => {
+                    report.add(ValidityProblem::SelfIntersection, p);
+                }
+            }
+        }
+    }
+}
+
+/// report proper crossings and overlaps between two rings, touching in single points is allowed
+fn check_ring_crossings(ring_a: &[Point], ring_b: &[Point], report: &mut Report) {
+    let (n, m) = (ring_a.len(), ring_b.len());
+    for i in 0..n {
+        let (a, b) = (&ring_a[i], &ring_a[(i + 1) % n]);
+        for j in 0..m {
+            let (c, d) = (&ring_b[j], &ring_b[(j + 1) % m]);
+            match segment_intersection(a, b, c, d) {
+                SegmentIntersection::None => {}
+                SegmentIntersection::Point(p) => {
+                    if p != *a && p != *b && p != *c && p != *d {
+                        report.add(ValidityProblem::SelfIntersection, p);
+                    }
+                }
+                SegmentIntersection::Overlap(p, _) => {
+                    report.add(ValidityProblem::SelfIntersection, p);
+                }
+            }
+        }
+    }
+}
+
+/// Build the polygons enclosed by an odd number of the given open rings, noded at the given precision
+fn build_even_odd(rings: &[Vec<Point>], precision: &PrecisionModel) -> MultiPolygon {
+    let mut segments = Vec::new();
+    for ring in rings.iter().filter(|ring| ring.len() >= 3) {
+        for i in 0..ring.len() {
+            segments.push((ring[i], ring[(i + 1) % ring.len()]));
+        }
+    }
+    build_even_odd_from_segments(&segments, precision)
+}
+
+/// Build the polygons enclosed by an odd number of the given boundary segments, noded at the given precision.
+/// The segments must form closed paths, their direction does not matter.
+pub(crate) fn build_even_odd_from_segments(
+    segments: &[(Point, Point)],
+    precision: &PrecisionModel,
+) -> MultiPolygon {
+    // edges covered an odd number of times separate inside from outside
+    let mut vertices: Vec<Point> = Vec::new();
+    let mut vertex_ids: HashMap<PointKey, usize> = HashMap::new();
+    let mut edge_count: HashMap<(usize, usize), usize> = HashMap::new();
+    for (a, b) in node_segments_snap_rounded(segments, precision) {
+        let mut id = |p: Point| {
+            *vertex_ids.entry(p.key()).or_insert_with(|| {
+                vertices.push(p);
+                vertices.len() - 1
+            })
+        };
+        let (u, v) = (id(a), id(b));
+        if u != v {
+            *edge_count.entry((u.min(v), u.max(v))).or_insert(0) += 1;
+        }
+    }
+    let mut edges: Vec<(usize, usize)> = edge_count
+        .into_iter()
+        .filter(|(_, count)| count % 2 == 1)
+        .map(|(edge, _)| edge)
+        .collect();
+    edges.sort_unstable();
+
+    let graph = PlanarGraph::new(&vertices, &edges);
+    let inside = graph.even_odd_cycles(&vertices, &edges);
+
+    // split the inside cycles into simple loops: counter-clockwise loops are shells, clockwise loops holes
+    let mut shells: Vec<Vec<Point>> = Vec::new();
+    let mut holes: Vec<Vec<Point>> = Vec::new();
+    for (cycle, _) in graph
+        .cycles
+        .iter()
+        .zip(&inside)
+        .filter(|(_, inside)| **inside)
+    {
+        let cycle: Vec<usize> = cycle.iter().map(|&h| graph.origin[h]).collect();
+        for simple in split_at_repeated_vertices(&cycle) {
+            let ring: Vec<Point> = simple.iter().map(|&v| vertices[v]).collect();
+            let area = ring_signed_area(&ring);
+            if area > 0.0 {
+                shells.push(ring);
+            } else if area < 0.0 {
+                holes.push(ring);
+            }
+        }
+    }
+
+    // assign every hole to the smallest shell containing it
+    let shell_areas: Vec<f64> = shells.iter().map(|s| ring_signed_area(s)).collect();
+    let mut shell_holes: Vec<Vec<Vec<Point>>> = vec![Vec::new(); shells.len()];
+    for hole in holes {
+        let owner = (0..shells.len())
+            .filter(|&s| ring_inside_ring(&hole, &shells[s]))
+            .min_by(|&a, &b| shell_areas[a].total_cmp(&shell_areas[b]));
+        if let Some(s) = owner {
+            shell_holes[s].push(hole);
+        }
+    }
+
+    let polygons = shells
+        .into_iter()
+        .zip(shell_holes)
+        .map(|(shell, holes)| {
+            Polygon::new(
+                closed_line(shell),
+                holes.into_iter().map(closed_line).collect(),
+            )
+        })
+        .collect();
+    MultiPolygon::new(polygons)
+}
+
+/// check if a ring lies inside another ring, using a vertex or edge midpoint not on the other ring
+fn ring_inside_ring(inner: &[Point], outer: &[Point]) -> bool {
+    let n = inner.len();
+    let candidates = inner.iter().copied().chain((0..n).map(|i| {
+        let (a, b) = (&inner[i], &inner[(i + 1) % n]);
+        Point::new((a.get_x() + b.get_x()) / 2.0, (a.get_y() + b.get_y()) / 2.0)
+    }));
+    for point in candidates {
+        match locate_point_in_ring(&point, outer) {
+            Location::Interior => return true,
+            Location::Exterior => return false,
+            Location::Boundary => {}
+        }
+    }
+    false
+}
+
+fn closed_line(mut ring: Vec<Point>) -> Line {
+    ring.push(ring[0]);
+    Line::new(ring)
+}
+
+/// split a cycle of vertex ids which visits vertices more than once into simple cycles
+fn split_at_repeated_vertices(cycle: &[usize]) -> Vec<Vec<usize>> {
+    let mut loops = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    for &v in cycle {
+        if let Some(position) = stack.iter().position(|&w| w == v) {
+            let simple: Vec<usize> = stack.drain(position..).collect();
+            if simple.len() >= 3 {
+                loops.push(simple);
+            }
+        }
+        stack.push(v);
+    }
+    if stack.len() >= 3 {
+        loops.push(stack);
+    }
+    loops
+}
+
+/// Planar graph with its face boundary cycles
+struct PlanarGraph {
+    /// origin vertex of every half-edge, half-edges 2e and 2e + 1 are the two directions of edge e
+    origin: Vec<usize>,
+    /// cycle index of every half-edge
+    cycle_of: Vec<usize>,
+    /// half-edges of every cycle, the face of the cycle lies on its left
+    cycles: Vec<Vec<usize>>,
+    /// outgoing half-edges of every vertex sorted counter-clockwise by angle
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl PlanarGraph {
+    fn new(vertices: &[Point], edges: &[(usize, usize)]) -> PlanarGraph {
+        let mut origin = Vec::with_capacity(edges.len() * 2);
+        for &(u, v) in edges {
+            origin.push(u);
+            origin.push(v);
+        }
+        let target = |h: usize| origin[h ^ 1];
+        let angle = |h: usize| {
+            let (a, b) = (&vertices[origin[h]], &vertices[target(h)]);
+            (b.get_y() - a.get_y()).atan2(b.get_x() - a.get_x())
+        };
+
+        let mut outgoing = vec![Vec::new(); vertices.len()];
+        for h in 0..origin.len() {
+            outgoing[origin[h]].push(h);
+        }
+        let mut position = vec![0; origin.len()];
+        for list in outgoing.iter_mut() {
+            list.sort_by(|&a, &b| angle(a).total_cmp(&angle(b)));
+            for (k, &h) in list.iter().enumerate() {
+                position[h] = k;
+            }
+        }
+
+        // the next half-edge around a face is the one preceding the twin in counter-clockwise order
+        let next = |h: usize| {
+            let twin = h ^ 1;
+            let list = &outgoing[origin[twin]];
+            list[(position[twin] + list.len() - 1) % list.len()]
+        };
+        let mut cycle_of = vec![usize::MAX; origin.len()];
+        let mut cycles = Vec::new();
+        for start in 0..origin.len() {
+            if cycle_of[start] != usize::MAX {
+                continue;
+            }
+            let mut cycle = Vec::new();
+            let mut h = start;
+            while cycle_of[h] == usize::MAX {
+                cycle_of[h] = cycles.len();
+                cycle.push(h);
+                h = next(h);
+            }
+            cycles.push(cycle);
+        }
+        PlanarGraph {
+            origin,
+            cycle_of,
+            cycles,
+            outgoing,
+        }
+    }
+
+    /// decide for every cycle whether its face is inside by the even-odd rule
+    fn even_odd_cycles(&self, vertices: &[Point], edges: &[(usize, usize)]) -> Vec<bool> {
+        let mut inside: Vec<Option<bool>> = vec![None; self.cycles.len()];
+        for start in 0..self.cycles.len() {
+            if inside[start].is_some() {
+                continue;
+            }
+            // collect the connected component: cycles are connected through twin half-edges
+            let mut component = vec![start];
+            let mut seen = vec![false; self.cycles.len()];
+            seen[start] = true;
+            let mut k = 0;
+            while k < component.len() {
+                let c = component[k];
+                for &h in &self.cycles[c] {
+                    let other = self.cycle_of[h ^ 1];
+                    if !seen[other] {
+                        seen[other] = true;
+                        component.push(other);
+                    }
+                }
+                k += 1;
+            }
+
+            // the face left of the leftmost vertex is inside if a ray to the left crosses an odd number of edges
+            let leftmost = component
+                .iter()
+                .flat_map(|&c| self.cycles[c].iter().map(|&h| self.origin[h]))
+                .min_by(|&a, &b| {
+                    let (pa, pb) = (&vertices[a], &vertices[b]);
+                    pa.get_x()
+                        .total_cmp(&pb.get_x())
+                        .then(pa.get_y().total_cmp(&pb.get_y()))
+                })
+                .unwrap_or(0);
+            let anchor = &vertices[leftmost];
+            let crossings = edges
+                .iter()
+                .filter(|&&(u, v)| {
+                    let (a, b) = (&vertices[u], &vertices[v]);
+                    let side = orient2d(a, b, anchor);
+                    (a.get_y() > anchor.get_y()) != (b.get_y() > anchor.get_y())
+                        && ((b.get_y() > a.get_y() && side < 0.0)
+                            || (b.get_y() < a.get_y() && side > 0.0))
+                })
+                .count();
+            // the outgoing half-edge with the largest angle has the region to the left of the vertex on its left
+            let Some(&h) = self.outgoing[leftmost].last() else {
+                continue;
+            };
+
+            // propagate: crossing an edge switches between inside and outside
+            let mut stack = vec![(self.cycle_of[h], crossings % 2 == 1)];
+            while let Some((c, value)) = stack.pop() {
+                if inside[c].is_some() {
+                    continue;
+                }
+                inside[c] = Some(value);
+                for &h in &self.cycles[c] {
+                    let other = self.cycle_of[h ^ 1];
+                    if inside[other].is_none() {
+                        stack.push((other, !value));
+                    }
+                }
+            }
+        }
+        inside
+            .into_iter()
+            .map(|value| value.unwrap_or(false))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(issues: &[ValidityIssue]) -> Vec<ValidityProblem> {
+        issues.iter().map(|issue| issue.get_problem()).collect()
+    }
+
+    fn area(multipolygon: &MultiPolygon) -> f64 {
+        multipolygon
+            .get_polygons()
+            .iter()
+            .map(|polygon| {
+                let shell = distinct_points(polygon.get_exterior().get_points());
+                let holes: f64 = polygon
+                    .get_interiors()
+                    .iter()
+                    .map(|hole| ring_signed_area(&distinct_points(hole.get_points())))
+                    .sum();
+                ring_signed_area(&shell) + holes
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_valid_polygon() {
+        let polygon =
+            Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 8, 8 8, 8 2, 2 2))")
+                .unwrap();
+        assert!(polygon.is_valid());
+        assert_eq!(polygon.make_valid().get_number_of_polygons(), 1);
+    }
+
+    #[test]
+    fn test_line_problems() {
+        let line = Line::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(f64::NAN, 1.0),
+            Point::new(2.0, 2.0),
+            Point::new(2.0, 2.0),
+        ]);
+        let report = line.validity_report();
+        assert_eq!(
+            problems(&report),
+            vec![
+                ValidityProblem::NonFiniteCoordinate,
+                ValidityProblem::DuplicatePoint
+            ]
+        );
+        assert_eq!(report[1].get_location(), Point::new(2.0, 2.0));
+        assert!(!Line::new(vec![Point::new(1.0, 1.0), Point::new(1.0, 1.0)]).is_valid());
+        assert!(Line::new(vec![Point::new(1.0, 1.0), Point::new(1.0, 2.0)]).is_valid());
+    }
+
+    #[test]
+    fn test_ring_not_closed_and_too_few_points() {
+        let polygon = Polygon::from_wkt("POLYGON ((0 0, 1 0, 1 1))").unwrap();
+        assert_eq!(
+            problems(&polygon.validity_report()),
+            vec![ValidityProblem::RingNotClosed]
+        );
+        let polygon = Polygon::from_wkt("POLYGON ((0 0, 1 0, 0 0))").unwrap();
+        assert_eq!(
+            problems(&polygon.validity_report()),
+            vec![ValidityProblem::TooFewPoints]
+        );
+    }
+
+    #[test]
+    fn test_bow_tie() {
+        let polygon = Polygon::from_wkt("POLYGON ((0 0, 2 2, 2 0, 0 2, 0 0))").unwrap();
+        let report = polygon.validity_report();
+        assert_eq!(report[0].get_problem(), ValidityProblem::SelfIntersection);
+        assert_eq!(report[0].get_location(), Point::new(1.0, 1.0));
+        assert_eq!(report[0].to_string(), "Self-intersection at POINT (1 1)");
+
+        let repaired = polygon.make_valid();
+        assert_eq!(repaired.get_number_of_polygons(), 2);
+        assert!((area(&repaired) - 2.0).abs() < 1e-10);
+        assert!(repaired.get_polygons().iter().all(|p| p.is_valid()));
+    }
+
+    #[test]
+    fn test_wrong_orientation() {
+        let polygon =
+            Polygon::from_wkt("POLYGON ((0 0, 0 10, 10 10, 10 0, 0 0), (2 2, 8 2, 8 8, 2 8, 2 2))")
+                .unwrap();
+        assert_eq!(
+            problems(&polygon.validity_report()),
+            vec![
+                ValidityProblem::WrongOrientation,
+                ValidityProblem::WrongOrientation
+            ]
+        );
+        let repaired = polygon.make_valid();
+        assert_eq!(repaired.get_number_of_polygons(), 1);
+        assert!(repaired.get_polygons()[0].is_valid());
+        assert!((area(&repaired) - 64.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_orientation_of_sliver() {
+        // far from the origin the summed area of this sliver rounds to zero in both directions
+        let (base, ulp) = (2f64.powi(40), 2f64.powi(40 - 52));
+        let mut points = vec![
+            Point::new(base, base),
+            Point::new(base + 3.0, base + 3.0),
+            Point::new(base + 1.0, base + 1.0 + ulp),
+            Point::new(base, base),
+        ];
+        assert_eq!(ring_signed_area(&points), 0.0);
+        assert!(Polygon::new(Line::new(points.clone()), vec![]).is_valid());
+        points.reverse();
+        assert_eq!(
+            problems(&Polygon::new(Line::new(points), vec![]).validity_report()),
+            vec![ValidityProblem::WrongOrientation]
+        );
+    }
+
+    #[test]
+    fn test_hole_outside_shell() {
+        let polygon = Polygon::from_wkt(
+            "POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0), (10 10, 10 12, 12 12, 12 10, 10 10))",
+        )
+        .unwrap();
+        let report = polygon.validity_report();
+        assert_eq!(problems(&report), vec![ValidityProblem::HoleOutsideShell]);
+        assert_eq!(report[0].get_location(), Point::new(10.0, 10.0));
+
+        let repaired = polygon.make_valid();
+        assert_eq!(repaired.get_number_of_polygons(), 2);
+        assert!((area(&repaired) - 20.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_hole_crossing_shell() {
+        let polygon =
+            Polygon::from_wkt("POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0), (3 1, 3 3, 6 3, 6 1, 3 1))")
+                .unwrap();
+        assert!(problems(&polygon.validity_report()).contains(&ValidityProblem::SelfIntersection));
+        let repaired = polygon.make_valid();
+        // even-odd: the square minus the overlap plus the part of the hole outside the square
+        assert_eq!(repaired.get_number_of_polygons(), 2);
+        assert!((area(&repaired) - 18.0).abs() < 1e-10);
+        assert!(repaired.get_polygons().iter().all(|p| p.is_valid()));
+    }
+
+    #[test]
+    fn test_make_valid_drops_duplicates_and_non_finite() {
+        let polygon = Polygon::new(
+            Line::new(vec![
+                Point::new(0.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(2.0, 0.0),
+                Point::new(f64::INFINITY, 1.0),
+                Point::new(2.0, 2.0),
+                Point::new(0.0, 2.0),
+            ]),
+            vec![],
+        );
+        assert!(!polygon.is_valid());
+        let repaired = polygon.make_valid();
+        assert_eq!(repaired.get_number_of_polygons(), 1);
+        assert!(repaired.get_polygons()[0].is_valid());
+        assert!((area(&repaired) - 4.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_make_valid_with_precision() {
+        // a bow-tie whose right part has a sliver spike thinner than a millimetre
+        let polygon = Polygon::from_wkt(
+            "POLYGON ((0 0, 2.0004 2.0001, 2.0004 0, 6 0, 6.0002 2.0003, 4 2.0002, 4.0003 0.0001, 0 2, 0 0))",
+        )
+        .unwrap();
+        assert!(!polygon.is_valid());
+        let precision = PrecisionModel::fixed(0.001);
+        let repaired = polygon.make_valid_with_precision(&precision);
+        // the spike collapses onto the bottom edge and cuts the right part in two
+        assert_eq!(repaired.get_number_of_polygons(), 4);
+        assert!((area(&repaired) - area(&polygon.make_valid())).abs() < 0.01);
+        for polygon in repaired.get_polygons() {
+            assert!(polygon.is_valid());
+            for point in polygon.get_exterior().get_points() {
+                assert_eq!(precision.snap_point(point), *point);
+            }
+        }
+        // a valid polygon is only rounded
+        let valid = Polygon::from_wkt("POLYGON ((0 0, 1.00002 0, 1 1.0004, 0 0))").unwrap();
+        let rounded = valid.make_valid_with_precision(&precision);
+        assert_eq!(
+            rounded.get_polygons()[0].to_wkt(),
+            "POLYGON ((0 0, 1 0, 1 1, 0 0))"
+        );
+    }
+}
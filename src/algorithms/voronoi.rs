@@ -0,0 +1,178 @@
+use super::delaunay::DelaunayTriangulation;
+use crate::geoms::line::Line;
+use crate::geoms::point::{Point, PointKey};
+use crate::geoms::polygon::Polygon;
+use crate::geoms::rect::Rect;
+use std::collections::HashMap;
+
+/// A cell of a Voronoi diagram (Thiessen polygon)
+#[derive(Debug, Clone)]
+pub struct VoronoiCell {
+    /// index of the generating point
+    generator: usize,
+    /// area closer to the generating point than to any other point, clipped to the diagram bounds
+    polygon: Polygon,
+}
+
+impl VoronoiCell {
+    /// get the index of the generating point
+    pub fn get_generator(&self) -> usize {
+        self.generator
+    }
+
+    /// get the polygon of the cell
+    pub fn get_polygon(&self) -> &Polygon {
+        &self.polygon
+    }
+}
+
+impl DelaunayTriangulation {
+    /// Compute the Voronoi diagram, the dual of the Delaunay triangulation, clipped to `bounds`.
+    ///
+    /// The cell of a point is the bounding rectangle cut by the perpendicular bisectors to all of its
+    /// Delaunay neighbours. Cells that lie completely outside of `bounds` and duplicate points are skipped.
+    pub fn voronoi(&self, bounds: &Rect) -> Vec<VoronoiCell> {
+        let points = self.get_points();
+
+        // only the first of several identical points generates a cell
+        let mut first_index: HashMap<PointKey, usize> = HashMap::new();
+        let is_duplicate: Vec<bool> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| *first_index.entry(p.key()).or_insert(i) != i)
+            .collect();
+
+        let neighbors = if self.get_triangles().is_empty() {
+            collinear_neighbors(points, &is_duplicate)
+        } else {
+            self.all_point_neighbors()
+        };
+
+        let bounds_polygon = bounds.to_polygon();
+        let bounds_ring = &bounds_polygon.get_exterior().get_points()[..4];
+        let mut cells = Vec::new();
+        for (i, generator) in points.iter().enumerate() {
+            if is_duplicate[i] {
+                continue;
+            }
+            let mut cell = bounds_ring.to_vec();
+            for &j in &neighbors[i] {
+                cell = clip_half_plane(&cell, generator, &points[j]);
+                if cell.is_empty() {
+                    break;
+                }
+            }
+            if cell.len() < 3 {
+                continue;
+            }
+            cell.push(cell[0]);
+            cells.push(VoronoiCell {
+                generator: i,
+                polygon: Polygon::new(Line::new(cell), vec![]),
+            });
+        }
+        cells
+    }
+}
+
+/// neighbours of points which all lie on one line: the previous and next point along the line
+fn collinear_neighbors(points: &[Point], is_duplicate: &[bool]) -> Vec<Vec<usize>> {
+    let mut order: Vec<usize> = (0..points.len()).filter(|&i| !is_duplicate[i]).collect();
+    order.sort_by(|&a, &b| {
+        points[a]
+            .get_x()
+            .total_cmp(&points[b].get_x())
+            .then(points[a].get_y().total_cmp(&points[b].get_y()))
+    });
+    let mut neighbors = vec![Vec::new(); points.len()];
+    for pair in order.windows(2) {
+        neighbors[pair[0]].push(pair[1]);
+        neighbors[pair[1]].push(pair[0]);
+    }
+    neighbors
+}
+
+/// Clip a convex ring (without closing point) to the half-plane of points closer to `keep` than to `other`
+fn clip_half_plane(ring: &[Point], keep: &Point, other: &Point) -> Vec<Point> {
+    let mid_x = (keep.get_x() + other.get_x()) / 2.0;
+    let mid_y = (keep.get_y() + other.get_y()) / 2.0;
+    let dx = other.get_x() - keep.get_x();
+    let dy = other.get_y() - keep.get_y();
+    // signed distance (scaled) beyond the bisector, positive on the side of `other`
+    let side = |p: &Point| (p.get_x() - mid_x) * dx + (p.get_y() - mid_y) * dy;
+
+    let mut clipped = Vec::with_capacity(ring.len() + 1);
+    for (k, current) in ring.iter().enumerate() {
+        let next = &ring[(k + 1) % ring.len()];
+        let (s_current, s_next) = (side(current), side(next));
+        if s_current <= 0.0 {
+            clipped.push(*current);
+        }
+        if (s_current < 0.0 && s_next > 0.0) || (s_current > 0.0 && s_next < 0.0) {
+            let t = s_current / (s_current - s_next);
+            clipped.push(Point::new(
+                current.get_x() + t * (next.get_x() - current.get_x()),
+                current.get_y() + t * (next.get_y() - current.get_y()),
+            ));
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell_area(cell: &VoronoiCell) -> f64 {
+        let points = cell.get_polygon().get_exterior().get_points();
+        points
+            .windows(2)
+            .map(|w| w[0].get_x() * w[1].get_y() - w[1].get_x() * w[0].get_y())
+            .sum::<f64>()
+            / 2.0
+    }
+
+    #[test]
+    fn test_two_points_split_rect() {
+        let points = vec![Point::new(1.0, 1.0), Point::new(3.0, 1.0)];
+        let delaunay = DelaunayTriangulation::new(&points);
+        let cells = delaunay.voronoi(&Rect::new(0.0, 0.0, 4.0, 2.0));
+        assert_eq!(cells.len(), 2);
+        for cell in &cells {
+            assert!((cell_area(cell) - 4.0).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_cells_cover_bounds() {
+        let points = Point::generate_random_points(100, 0.0, 0.0, 10.0, 10.0, 3);
+        let bounds = Rect::new(-1.0, -1.0, 11.0, 11.0);
+        let cells = DelaunayTriangulation::new(&points).voronoi(&bounds);
+        assert_eq!(cells.len(), 100);
+        let total: f64 = cells.iter().map(cell_area).sum();
+        assert!((total - bounds.area()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_cell_contains_nearest_points() {
+        let points = Point::generate_random_points(50, 0.0, 0.0, 10.0, 10.0, 11);
+        let cells = DelaunayTriangulation::new(&points).voronoi(&Rect::new(0.0, 0.0, 10.0, 10.0));
+        for cell in &cells {
+            // every cell vertex is at least as close to the generator as to any other point
+            let generator = points[cell.get_generator()];
+            for vertex in cell.get_polygon().get_exterior().get_points() {
+                let own = vertex.distance2D(&generator);
+                assert!(points.iter().all(|p| vertex.distance2D(p) >= own - 1e-9));
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicates_and_single_point() {
+        let points = vec![Point::new(1.0, 1.0), Point::new(1.0, 1.0)];
+        let cells = DelaunayTriangulation::new(&points).voronoi(&Rect::new(0.0, 0.0, 2.0, 2.0));
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].get_generator(), 0);
+        assert!((cell_area(&cells[0]) - 4.0).abs() < 1e-10);
+    }
+}
@@ -1,8 +1,10 @@
 use super::point::Point;
+use crate::algorithms::predicates::orient2d;
+use crate::algorithms::validity::is_simple_ring;
 use core::f64;
 use regex::Regex;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 /// Struct for a 2D Line
 pub struct Line {
     /// points of the line
@@ -16,7 +18,12 @@ impl Line {
         if points.len() < 2 {
             panic!("A line must have at least two points.");
         }
-        Line { points: points }
+        Line { points }
+    }
+
+    /// get the points of the line
+    pub fn get_points(&self) -> &[Point] {
+        &self.points
     }
 
     /// get the number of points in the line
@@ -42,9 +49,103 @@ impl Line {
         self.points.last().copied().unwrap()
     }
 
+    /// check if another line has the same number of points, each within a distance of `eps` of the point at the
+    /// same position
+    pub fn approx_eq(&self, other: &Line, eps: f64) -> bool {
+        self.points.len() == other.points.len()
+            && self
+                .points
+                .iter()
+                .zip(&other.points)
+                .all(|(p, q)| p.approx_eq(q, eps))
+    }
+
+    /// check if another line has the same shape: it may run in reverse direction and, if both lines are closed,
+    /// start at a different vertex. Consecutive duplicate points are ignored.
+    pub fn topo_equals(&self, other: &Line) -> bool {
+        let mut a = self.points.clone();
+        let mut b = other.points.clone();
+        a.dedup();
+        b.dedup();
+        let is_closed = |points: &[Point]| points.len() > 2 && points.first() == points.last();
+        if a.len() != b.len() || is_closed(&a) != is_closed(&b) {
+            return false;
+        }
+        if !is_closed(&a) {
+            return a == b || a.iter().eq(b.iter().rev());
+        }
+        // compare the open rings for every start vertex in both directions
+        let n = a.len() - 1;
+        (0..n).filter(|&shift| b[shift] == a[0]).any(|shift| {
+            (0..n).all(|i| a[i] == b[(shift + i) % n])
+                || (0..n).all(|i| a[i] == b[(shift + n - i) % n])
+        })
+    }
+
+    /// check if the line is closed: it has more than two points and ends at its first point
+    pub fn is_closed(&self) -> bool {
+        self.points.len() > 2 && self.points.first() == self.points.last()
+    }
+
+    /// Check if the line is a ring: it is closed, has at least three distinct points and does not touch or cross
+    /// itself. Consecutive duplicate points are ignored.
+    pub fn is_ring(&self) -> bool {
+        self.is_closed() && is_simple_ring(&self.points)
+    }
+
+    /// Calculate the signed area enclosed by a closed line with the shoelace formula, positive for
+    /// counter-clockwise lines. Return None if the line is not closed.
+    pub fn signed_area(&self) -> Option<f64> {
+        self.is_closed().then(|| ring_signed_area(&self.points))
+    }
+
+    /// Check if a closed line runs counter-clockwise. The turn at the lowest vertex is decided with the exact
+    /// orientation predicate, so the result is reliable even for rings with almost no area.
+    /// Return false for lines which are not closed or enclose no area.
+    pub fn is_ccw(&self) -> bool {
+        if !self.is_closed() {
+            return false;
+        }
+        let ring = &self.points[..self.points.len() - 1];
+        let n = ring.len();
+        let lowest = (0..n)
+            .min_by(|&i, &j| {
+                let (p, q) = (&ring[i], &ring[j]);
+                p.get_y()
+                    .total_cmp(&q.get_y())
+                    .then(p.get_x().total_cmp(&q.get_x()))
+            })
+            .unwrap();
+        // the nearest vertices before and after the lowest one which differ from it
+        let previous = (1..n)
+            .map(|k| ring[(lowest + n - k) % n])
+            .find(|p| *p != ring[lowest]);
+        let next = (1..n)
+            .map(|k| ring[(lowest + k) % n])
+            .find(|p| *p != ring[lowest]);
+        let (Some(previous), Some(next)) = (previous, next) else {
+            return false;
+        };
+        let orientation = orient2d(&previous, &ring[lowest], &next);
+        if orientation != 0.0 {
+            orientation > 0.0
+        } else {
+            // the lowest vertex is the tip of a flat spike, fall back to the area
+            ring_signed_area(ring) > 0.0
+        }
+    }
+
+    /// reverse the order of the points
+    pub fn reverse(&mut self) {
+        self.points.reverse();
+    }
+
     /// calculate the minimum distance between a point and the line
     pub fn distance_to_point(&self, point: Point) -> f64 {
-        todo!();
+        self.points
+            .windows(2)
+            .map(|segment| distance_point_segment(&point, &segment[0], &segment[1]))
+            .fold(f64::INFINITY, f64::min)
     }
 
     /// get WKT (well-known text) representation of a 2D point
@@ -73,21 +174,7 @@ impl Line {
             .map(|m| m.as_str())
             .ok_or("Invalid WKT format: missing or malformed LINESTRING")?;
 
-        // Split by comma to get individual coordinate pairs
-        let mut points = Vec::new();
-        for pair_str in coords_str.split(',') {
-            let coords: Vec<&str> = pair_str.trim().split_whitespace().collect();
-            if coords.len() != 2 {
-                return Err("Malformed coordinate pair");
-            }
-            let x = coords[0]
-                .parse::<f64>()
-                .map_err(|_| "Invalid X coordinate")?;
-            let y = coords[1]
-                .parse::<f64>()
-                .map_err(|_| "Invalid Y coordinate")?;
-            points.push(Point::new(x, y));
-        }
+        let points = parse_wkt_coordinates(coords_str)?;
 
         if points.len() < 2 {
             return Err("A LINESTRING must have at least two points");
@@ -106,12 +193,30 @@ impl Line {
 
     /// calculate a point at a given distance from the start of the line
     pub fn interpolate_line_distance(&self, distance: f64) -> Option<Point> {
-        todo!()
+        if !(0.0..=self.get_length()).contains(&distance) {
+            return None;
+        }
+        let mut travelled: f64 = 0.0;
+        for segment in self.points.windows(2) {
+            let segment_length = segment[0].distance2D(&segment[1]);
+            if travelled + segment_length >= distance && segment_length > 0.0 {
+                let t = (distance - travelled) / segment_length;
+                return Some(Point::new(
+                    segment[0].get_x() + t * (segment[1].get_x() - segment[0].get_x()),
+                    segment[0].get_y() + t * (segment[1].get_y() - segment[0].get_y()),
+                ));
+            }
+            travelled += segment_length;
+        }
+        Some(self.get_end())
     }
 
     /// calculate a point at a given distance (in % between 0 and 1) from the start of the line
     pub fn interpolate_line_percentage(&self, percentange: f64) -> Option<Point> {
-        todo!()
+        if !(0.0..=1.0).contains(&percentange) {
+            return None;
+        }
+        self.interpolate_line_distance(percentange * self.get_length())
     }
 
     /// Calculate the bounding box of the line. Return a tuple of points, first containing minimum and the secod containing maximum values.
@@ -140,17 +245,17 @@ impl Line {
         (Point::new(min_x, min_y), Point::new(max_x, max_y))
     }
     /// Split the line into two parts at a given distance from the start
-    pub fn split_at_distance(&self, distance: f64) -> Option<(Line, Line)> {
+    pub fn split_at_distance(&self, _distance: f64) -> Option<(Line, Line)> {
         todo!()
     }
 
     /// check if intersects with other line
-    pub fn intersects_line(&self, other: Line) -> Self {
+    pub fn intersects_line(&self, _other: Line) -> Self {
         todo!()
     }
 
     /// return intersection point of two lines
-    pub fn intersection_line(&self, other: Line) -> Self {
+    pub fn intersection_line(&self, _other: Line) -> Self {
         todo!()
     }
 
@@ -164,13 +269,65 @@ impl Line {
             centroid_y += point.get_y();
         }
 
-        centroid_x = centroid_x / self.points.len() as f64;
-        centroid_y = centroid_y / self.points.len() as f64;
+        centroid_x /= self.points.len() as f64;
+        centroid_y /= self.points.len() as f64;
 
         Point::new(centroid_x, centroid_y)
     }
 }
 
+/// parse a comma separated list of WKT coordinate pairs, e.g. `1.0 2.0, 3.0 4.0`
+pub(crate) fn parse_wkt_coordinates(coords_str: &str) -> Result<Vec<Point>, &'static str> {
+    // Split by comma to get individual coordinate pairs
+    let mut points = Vec::new();
+    for pair_str in coords_str.split(',') {
+        let coords: Vec<&str> = pair_str.split_whitespace().collect();
+        if coords.len() != 2 {
+            return Err("Malformed coordinate pair");
+        }
+        let x = coords[0]
+            .parse::<f64>()
+            .map_err(|_| "Invalid X coordinate")?;
+        let y = coords[1]
+            .parse::<f64>()
+            .map_err(|_| "Invalid Y coordinate")?;
+        points.push(Point::new(x, y));
+    }
+    Ok(points)
+}
+
+/// signed area of a ring given open or closed, positive for counter-clockwise rings
+pub(crate) fn ring_signed_area(ring: &[Point]) -> f64 {
+    let n = ring.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (&ring[i], &ring[(i + 1) % n]);
+            a.get_x() * b.get_y() - b.get_x() * a.get_y()
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// calculate the minimum distance between a point and the segment from `a` to `b`
+pub(crate) fn distance_point_segment(point: &Point, a: &Point, b: &Point) -> f64 {
+    point.distance2D(&closest_point_on_segment(point, a, b))
+}
+
+/// get the point on the segment from `a` to `b` closest to the given point
+pub(crate) fn closest_point_on_segment(point: &Point, a: &Point, b: &Point) -> Point {
+    let dx = b.get_x() - a.get_x();
+    let dy = b.get_y() - a.get_y();
+    let length_squared = dx * dx + dy * dy;
+    if length_squared == 0.0 {
+        return *a;
+    }
+    // project the point onto the segment and clamp to its end points
+    let t = (((point.get_x() - a.get_x()) * dx + (point.get_y() - a.get_y()) * dy)
+        / length_squared)
+        .clamp(0.0, 1.0);
+    Point::new(a.get_x() + t * dx, a.get_y() + t * dy)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -410,4 +567,69 @@ mod tests {
         assert!((centroid.get_x() - 1.0).abs() < 1e-10);
         assert!((centroid.get_y() - 1.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_approx_eq() {
+        let line = Line::from_wkt("LINESTRING (0 0, 1 1, 2 0)").unwrap();
+        let close = Line::from_wkt("LINESTRING (0 0.000001, 1 1, 2 0)").unwrap();
+        assert!(line.approx_eq(&close, 1e-5));
+        assert!(!line.approx_eq(&close, 1e-7));
+        assert_ne!(line, close);
+        assert_eq!(line, line.clone());
+    }
+
+    #[test]
+    fn test_topo_equals() {
+        let line = Line::from_wkt("LINESTRING (0 0, 1 1, 2 0)").unwrap();
+        let reversed = Line::from_wkt("LINESTRING (2 0, 1 1, 1 1, 0 0)").unwrap();
+        assert!(line.topo_equals(&reversed));
+        let other = Line::from_wkt("LINESTRING (0 0, 1 1, 2 1)").unwrap();
+        assert!(!line.topo_equals(&other));
+
+        let ring = Line::from_wkt("LINESTRING (0 0, 1 0, 1 1, 0 1, 0 0)").unwrap();
+        let shifted = Line::from_wkt("LINESTRING (1 1, 0 1, 0 0, 1 0, 1 1)").unwrap();
+        let shifted_reversed = Line::from_wkt("LINESTRING (1 0, 0 0, 0 1, 1 1, 1 0)").unwrap();
+        let open = Line::from_wkt("LINESTRING (0 0, 1 0, 1 1, 0 1, 0 0.5)").unwrap();
+        assert!(ring.topo_equals(&shifted));
+        assert!(ring.topo_equals(&shifted_reversed));
+        assert!(!ring.topo_equals(&open));
+    }
+
+    #[test]
+    fn test_signed_area_and_orientation() {
+        let ring = Line::from_wkt("LINESTRING (0 0, 2 0, 2 2, 0 2, 0 0)").unwrap();
+        assert!(ring.is_closed());
+        assert_eq!(ring.signed_area(), Some(4.0));
+        assert!(ring.is_ccw());
+        let mut reversed = ring.clone();
+        reversed.reverse();
+        assert_eq!(reversed.get_start(), Point::new(0.0, 0.0));
+        assert_eq!(reversed.get_points()[1], Point::new(0.0, 2.0));
+        assert_eq!(reversed.signed_area(), Some(-4.0));
+        assert!(!reversed.is_ccw());
+
+        let open = Line::from_wkt("LINESTRING (0 0, 2 0, 2 2)").unwrap();
+        assert!(!open.is_closed());
+        assert_eq!(open.signed_area(), None);
+        assert!(!open.is_ccw());
+    }
+
+    #[test]
+    fn test_is_ring() {
+        let square = Line::from_wkt("LINESTRING (0 0, 1 0, 1 1, 1 1, 0 1, 0 0)").unwrap();
+        assert!(square.is_ring());
+        let bow_tie = Line::from_wkt("LINESTRING (0 0, 1 1, 1 0, 0 1, 0 0)").unwrap();
+        assert!(!bow_tie.is_ring());
+        let open = Line::from_wkt("LINESTRING (0 0, 1 0, 1 1, 0 1)").unwrap();
+        assert!(!open.is_ring());
+        let flat = Line::from_wkt("LINESTRING (0 0, 1 0, 0 0)").unwrap();
+        assert!(!flat.is_ring());
+    }
+
+    #[test]
+    fn test_is_ccw_with_flat_spike() {
+        // the lowest vertex is the tip of a spike along the x axis
+        let ring = Line::from_wkt("LINESTRING (0 0, 4 0, 2 0, 2 2, 0 2, 0 0)").unwrap();
+        assert!(ring.is_ccw());
+    }
 }
@@ -1,11 +1,79 @@
 /// Line struct
 pub mod line;
+/// MultiPolygon struct
+pub mod multipolygon;
 /// Point struct
 pub mod point;
-/* possibility to make all geometry types one datatype for functions
+/// Polygon struct
+pub mod polygon;
+/// Rect struct
+pub mod rect;
+/// Enum over all geometry types, for functions accepting any combination of geometries
+#[derive(Debug, Clone, PartialEq)]
 pub enum Geometry {
     /// A point geometry
     Point(point::Point),
     /// A line geometry
     Line(line::Line),
-} */
+    /// A polygon geometry
+    Polygon(polygon::Polygon),
+    /// A multipolygon geometry
+    MultiPolygon(multipolygon::MultiPolygon),
+}
+
+impl Geometry {
+    /// get the topological dimension: 0 for points, 1 for lines and 2 for polygons
+    pub fn dimension(&self) -> u8 {
+        match self {
+            Geometry::Point(_) => 0,
+            Geometry::Line(_) => 1,
+            Geometry::Polygon(_) | Geometry::MultiPolygon(_) => 2,
+        }
+    }
+
+    /// check if another geometry is of the same type and approximately equal, with every coordinate within a
+    /// distance of `eps` of the corresponding one
+    pub fn approx_eq(&self, other: &Geometry, eps: f64) -> bool {
+        match (self, other) {
+            (Geometry::Point(a), Geometry::Point(b)) => a.approx_eq(b, eps),
+            (Geometry::Line(a), Geometry::Line(b)) => a.approx_eq(b, eps),
+            (Geometry::Polygon(a), Geometry::Polygon(b)) => a.approx_eq(b, eps),
+            (Geometry::MultiPolygon(a), Geometry::MultiPolygon(b)) => a.approx_eq(b, eps),
+            _ => false,
+        }
+    }
+
+    /// get WKT (well-known text) representation of the geometry
+    pub fn to_wkt(&self) -> String {
+        match self {
+            Geometry::Point(point) => point.to_wkt(),
+            Geometry::Line(line) => line.to_wkt(),
+            Geometry::Polygon(polygon) => polygon.to_wkt(),
+            Geometry::MultiPolygon(multipolygon) => multipolygon.to_wkt(),
+        }
+    }
+}
+
+impl From<point::Point> for Geometry {
+    fn from(point: point::Point) -> Self {
+        Geometry::Point(point)
+    }
+}
+
+impl From<line::Line> for Geometry {
+    fn from(line: line::Line) -> Self {
+        Geometry::Line(line)
+    }
+}
+
+impl From<polygon::Polygon> for Geometry {
+    fn from(polygon: polygon::Polygon) -> Self {
+        Geometry::Polygon(polygon)
+    }
+}
+
+impl From<multipolygon::MultiPolygon> for Geometry {
+    fn from(multipolygon: multipolygon::MultiPolygon) -> Self {
+        Geometry::MultiPolygon(multipolygon)
+    }
+}
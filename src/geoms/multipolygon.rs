@@ -0,0 +1,94 @@
+use super::polygon::Polygon;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Struct for a collection of 2D polygons
+pub struct MultiPolygon {
+    /// polygons of the collection
+    polygons: Vec<Polygon>,
+}
+
+impl MultiPolygon {
+    /// create a multipolygon from a (possibly empty) list of polygons
+    pub fn new(polygons: Vec<Polygon>) -> Self {
+        MultiPolygon { polygons }
+    }
+
+    /// get the polygons of the collection
+    pub fn get_polygons(&self) -> &[Polygon] {
+        &self.polygons
+    }
+
+    /// get the number of polygons in the collection
+    pub fn get_number_of_polygons(&self) -> usize {
+        self.polygons.len()
+    }
+
+    /// check if the collection contains no polygons
+    pub fn is_empty(&self) -> bool {
+        self.polygons.is_empty()
+    }
+
+    /// check if another multipolygon has the same number of polygons and all are approximately equal, see
+    /// [`Polygon::approx_eq`]
+    pub fn approx_eq(&self, other: &MultiPolygon, eps: f64) -> bool {
+        self.polygons.len() == other.polygons.len()
+            && self
+                .polygons
+                .iter()
+                .zip(&other.polygons)
+                .all(|(polygon, other_polygon)| polygon.approx_eq(other_polygon, eps))
+    }
+
+    /// get WKT (well-known text) representation of a 2D multipolygon
+    pub fn to_wkt(&self) -> String {
+        if self.polygons.is_empty() {
+            return "MULTIPOLYGON EMPTY".to_string();
+        }
+        format!(
+            "MULTIPOLYGON ({})",
+            self.polygons
+                .iter()
+                .map(|polygon| polygon.wkt_rings())
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_wkt() {
+        let multipolygon = MultiPolygon::new(vec![
+            Polygon::from_wkt("POLYGON ((0 0, 1 0, 0 1, 0 0))").unwrap(),
+            Polygon::from_wkt("POLYGON ((5 5, 6 5, 5 6, 5 5))").unwrap(),
+        ]);
+        assert_eq!(multipolygon.get_number_of_polygons(), 2);
+        assert_eq!(
+            multipolygon.to_wkt(),
+            "MULTIPOLYGON (((0 0, 1 0, 0 1, 0 0)), ((5 5, 6 5, 5 6, 5 5)))"
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let multipolygon = MultiPolygon::new(vec![]);
+        assert!(multipolygon.is_empty());
+        assert_eq!(multipolygon.to_wkt(), "MULTIPOLYGON EMPTY");
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let a = MultiPolygon::new(vec![
+            Polygon::from_wkt("POLYGON ((0 0, 1 0, 0 1, 0 0))").unwrap(),
+        ]);
+        let b = MultiPolygon::new(vec![
+            Polygon::from_wkt("POLYGON ((0 0, 1.001 0, 0 1, 0 0))").unwrap(),
+        ]);
+        assert!(a.approx_eq(&b, 0.01));
+        assert!(!a.approx_eq(&b, 0.0001));
+        assert!(!a.approx_eq(&MultiPolygon::new(vec![]), 0.01));
+    }
+}
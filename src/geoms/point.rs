@@ -18,10 +18,53 @@ impl PartialEq for Point {
     }
 }
 
+/// Hashable and totally ordered key of a point, for use in `HashMap`s, `BTreeSet`s, sorting and dedup.
+/// Keys are ordered by x and then y. -0.0 and 0.0 give the same key, all NaN values give the same key
+/// and sort after every number.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PointKey {
+    /// order preserving bit pattern of the x coordinate
+    x: u64,
+    /// order preserving bit pattern of the y coordinate
+    y: u64,
+}
+
+impl PointKey {
+    /// get the point of the key, with canonical zero and NaN coordinates
+    pub fn to_point(&self) -> Point {
+        Point::new(from_ordered_bits(self.x), from_ordered_bits(self.y))
+    }
+}
+
+/// map a float to an integer with the same order, identifying -0.0 with 0.0 and all NaN values
+fn to_ordered_bits(value: f64) -> u64 {
+    if value.is_nan() {
+        return u64::MAX;
+    }
+    let bits = (value + 0.0).to_bits();
+    // negative values: flip all bits to reverse their order, positive values: set the sign bit to move them above
+    if bits >> 63 == 1 {
+        !bits
+    } else {
+        bits | (1 << 63)
+    }
+}
+
+/// inverse of `to_ordered_bits`
+fn from_ordered_bits(bits: u64) -> f64 {
+    if bits == u64::MAX {
+        f64::NAN
+    } else if bits >> 63 == 1 {
+        f64::from_bits(bits & !(1 << 63))
+    } else {
+        f64::from_bits(!bits)
+    }
+}
+
 impl Point {
     /// create a point with given coordinates
     pub fn new(x: f64, y: f64) -> Point {
-        Point { x: x, y: y }
+        Point { x, y }
     }
 
     /// create a default point with coordinates x: 0.0 and y: 0.0
@@ -95,7 +138,21 @@ impl Point {
         self.x = rotation_center.x + x_new;
         self.y = rotation_center.y + y_new;
     }
+    /// get a hashable and totally ordered key of the point
+    pub fn key(&self) -> PointKey {
+        PointKey {
+            x: to_ordered_bits(self.x),
+            y: to_ordered_bits(self.y),
+        }
+    }
+
+    /// check if another point lies within a distance of `eps`
+    pub fn approx_eq(&self, other: &Point, eps: f64) -> bool {
+        self.distance2D(other) <= eps
+    }
+
     /// calculate the 2D distance to another point
+    #[allow(non_snake_case)]
     pub fn distance2D(&self, another: &Point) -> f64 {
         ((self.x - another.x).powi(2) + (self.y - another.y).powi(2)).sqrt()
     }
@@ -104,20 +161,18 @@ impl Point {
     pub fn pairwise_distances(points_vector: &[Point]) -> Vec<Vec<f64>> {
         let n = points_vector.len();
         let mut results = vec![vec![0.0; n]; n];
-            
-        for i in 0..(n-1) {
+
+        for i in 0..n.saturating_sub(1) {
             let pt_i = &points_vector[i];
-            for j in (i+1)..n{
+            for j in (i + 1)..n {
                 let dist_temp = pt_i.distance2D(&points_vector[j]);
-                    results[i][j] = dist_temp;
-                    results[j][i] = dist_temp;
+                results[i][j] = dist_temp;
+                results[j][i] = dist_temp;
             }
         }
-         results
+        results
     }
 
-    
-
     /// Generate a vector of n (pseudo) random points given min/max values for x and y. Upper bounds are inclusive (..=).
     pub fn generate_random_points(
         n: i32,
@@ -145,6 +200,7 @@ impl Point {
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
     use super::*;
 
@@ -436,4 +492,53 @@ mod tests {
         // min_x > max_x triggers the panic
         Point::generate_random_points(120, 5.0, 0.0, 1.0, 10.0, 154);
     }
+
+    #[test]
+    fn test_approx_eq() {
+        let point = Point::new(1.0, 2.0);
+        assert!(point.approx_eq(&Point::new(1.0 + 1e-9, 2.0), 1e-8));
+        assert!(!point.approx_eq(&Point::new(1.1, 2.0), 1e-8));
+    }
+
+    #[test]
+    fn test_key_canonical_zero_and_nan() {
+        assert_eq!(Point::new(-0.0, 1.0).key(), Point::new(0.0, 1.0).key());
+        assert_eq!(
+            Point::new(f64::NAN, 1.0).key(),
+            Point::new(-f64::NAN, 1.0).key()
+        );
+        assert!(Point::new(f64::NAN, 0.0).key() > Point::new(f64::INFINITY, 0.0).key());
+        let point = Point::new(f64::NAN, -0.0).key().to_point();
+        assert!(point.get_x().is_nan());
+        assert!(point.get_y().to_bits() == 0);
+    }
+
+    #[test]
+    fn test_key_order_and_collections() {
+        let points = [
+            Point::new(1.0, -2.0),
+            Point::new(-3.5, 7.0),
+            Point::new(1.0, -3.0),
+            Point::new(f64::NEG_INFINITY, 0.0),
+            Point::new(-0.0, 0.0),
+            Point::new(0.0, 0.0),
+        ];
+        let set: std::collections::BTreeSet<PointKey> = points.iter().map(Point::key).collect();
+        let ordered: Vec<Point> = set.iter().map(PointKey::to_point).collect();
+        assert_eq!(
+            ordered,
+            vec![
+                Point::new(f64::NEG_INFINITY, 0.0),
+                Point::new(-3.5, 7.0),
+                Point::new(0.0, 0.0),
+                Point::new(1.0, -3.0),
+                Point::new(1.0, -2.0),
+            ]
+        );
+        let mut counts = std::collections::HashMap::new();
+        for point in &points {
+            *counts.entry(point.key()).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&Point::origin().key()], 2);
+    }
 }
@@ -0,0 +1,387 @@
+use super::line::{Line, parse_wkt_coordinates};
+use super::point::Point;
+use crate::algorithms::triangulation::ear_clipping;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use regex::Regex;
+
+#[derive(Debug, Clone, PartialEq)]
+/// Struct for a 2D Polygon with an exterior ring and optional holes
+pub struct Polygon {
+    /// exterior ring of the polygon
+    exterior: Line,
+    /// interior rings (holes) of the polygon
+    interiors: Vec<Line>,
+}
+
+impl Polygon {
+    /// create a polygon from an exterior ring and a (possibly empty) list of holes
+    pub fn new(exterior: Line, interiors: Vec<Line>) -> Self {
+        // check if every ring has at least three points
+        if exterior.get_number_of_points() < 3
+            || interiors.iter().any(|ring| ring.get_number_of_points() < 3)
+        {
+            panic!("A polygon ring must have at least three points.");
+        }
+        Polygon {
+            exterior,
+            interiors,
+        }
+    }
+
+    /// get the exterior ring of the polygon
+    pub fn get_exterior(&self) -> &Line {
+        &self.exterior
+    }
+
+    /// get the interior rings (holes) of the polygon
+    pub fn get_interiors(&self) -> &[Line] {
+        &self.interiors
+    }
+
+    /// get the number of interior rings (holes) of the polygon
+    pub fn get_number_of_interiors(&self) -> usize {
+        self.interiors.len()
+    }
+
+    /// check if another polygon has the same number of holes and all rings are approximately equal, see
+    /// [`Line::approx_eq`]
+    pub fn approx_eq(&self, other: &Polygon, eps: f64) -> bool {
+        self.interiors.len() == other.interiors.len()
+            && self
+                .rings()
+                .zip(other.rings())
+                .all(|(ring, other_ring)| ring.approx_eq(other_ring, eps))
+    }
+
+    /// get all vertices of the polygon: the exterior ring followed by every hole, each ring without its closing point.
+    /// The indices returned by [`Polygon::triangulate`] refer to this list.
+    pub fn vertices(&self) -> Vec<Point> {
+        let mut vertices = Vec::new();
+        for ring in self.rings() {
+            vertices.extend_from_slice(open_ring(ring));
+        }
+        vertices
+    }
+
+    /// triangulate the polygon by ear clipping. Holes are connected to the exterior through bridge edges.
+    /// Return counter-clockwise triangles as index triples into [`Polygon::vertices`].
+    pub fn triangulate(&self) -> Vec<[usize; 3]> {
+        let mut vertices = Vec::new();
+        let mut hole_starts = Vec::with_capacity(self.interiors.len());
+        for (k, ring) in self.rings().enumerate() {
+            if k > 0 {
+                hole_starts.push(vertices.len());
+            }
+            vertices.extend_from_slice(open_ring(ring));
+        }
+        ear_clipping(&vertices, &hole_starts)
+    }
+
+    /// triangulate the polygon and return every triangle as a closed polygon
+    pub fn triangles(&self) -> Vec<Polygon> {
+        let vertices = self.vertices();
+        self.triangulate()
+            .iter()
+            .map(|t| {
+                Polygon::new(
+                    Line::new(vec![
+                        vertices[t[0]],
+                        vertices[t[1]],
+                        vertices[t[2]],
+                        vertices[t[0]],
+                    ]),
+                    vec![],
+                )
+            })
+            .collect()
+    }
+
+    /// Generate a vector of n (pseudo) random points uniformly distributed inside the polygon.
+    /// A triangle is picked with a probability proportional to its area, then a point is sampled inside of it.
+    pub fn generate_random_points(&self, n: i32, seed: u64) -> Vec<Point> {
+        let vertices = self.vertices();
+        let triangles = self.triangulate();
+        if triangles.is_empty() {
+            panic!("The polygon has no area, points cannot be generated!")
+        }
+
+        // cumulative triangle areas to pick triangles weighted by area
+        let mut cumulative_areas = Vec::with_capacity(triangles.len());
+        let mut total_area = 0.0;
+        for t in &triangles {
+            total_area += triangle_area(&vertices[t[0]], &vertices[t[1]], &vertices[t[2]]);
+            cumulative_areas.push(total_area);
+        }
+
+        // use a pseudo random generator for reproducible results
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut vec_pts: Vec<Point> = Vec::with_capacity(n as usize);
+        for _ in 0..n {
+            let target = rng.random_range(0.0..total_area);
+            let index = cumulative_areas
+                .partition_point(|&area| area <= target)
+                .min(triangles.len() - 1);
+            let [a, b, c] = triangles[index].map(|i| vertices[i]);
+
+            // uniform sampling in a triangle, see Osada et al. "Shape Distributions"
+            let r1 = rng.random::<f64>().sqrt();
+            let r2 = rng.random::<f64>();
+            let x = (1.0 - r1) * a.get_x() + r1 * (1.0 - r2) * b.get_x() + r1 * r2 * c.get_x();
+            let y = (1.0 - r1) * a.get_y() + r1 * (1.0 - r2) * b.get_y() + r1 * r2 * c.get_y();
+            vec_pts.push(Point::new(x, y));
+        }
+        vec_pts
+    }
+
+    /// get WKT (well-known text) representation of a 2D polygon
+    pub fn to_wkt(&self) -> String {
+        format!("POLYGON {}", self.wkt_rings())
+    }
+
+    /// get the parenthesised list of rings used in the WKT representation
+    pub(crate) fn wkt_rings(&self) -> String {
+        format!(
+            "({})",
+            self.rings()
+                .map(|ring| {
+                    format!(
+                        "({})",
+                        ring.get_points()
+                            .iter()
+                            .map(|p| format!("{} {}", p.get_x(), p.get_y()))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+
+    /// create Polygon from WKT
+    pub fn from_wkt(wkt_string: &str) -> Result<Polygon, &'static str> {
+        // Example match: POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0), (1 1, 1 2, 2 2, 1 1))
+        let re =
+            Regex::new(r"(?i)^POLYGON\s*\(\s*(.+)\s*\)$").map_err(|_| "Failed to compile regex")?;
+        let rings_str = re
+            .captures(wkt_string.trim())
+            .and_then(|caps| caps.get(1))
+            .map(|m| m.as_str())
+            .ok_or("Invalid WKT format: missing or malformed POLYGON")?;
+
+        let ring_re = Regex::new(r"\(([^()]*)\)").map_err(|_| "Failed to compile regex")?;
+        let mut rings = Vec::new();
+        for caps in ring_re.captures_iter(rings_str) {
+            let points = parse_wkt_coordinates(&caps[1])?;
+            if points.len() < 3 {
+                return Err("A POLYGON ring must have at least three points");
+            }
+            rings.push(Line::new(points));
+        }
+        if rings.is_empty() {
+            return Err("Invalid WKT format: missing or malformed POLYGON");
+        }
+        let exterior = rings.remove(0);
+        Ok(Polygon {
+            exterior,
+            interiors: rings,
+        })
+    }
+
+    /// Bring the polygon into a canonical form: the exterior ring runs counter-clockwise and the holes clockwise,
+    /// every ring is closed and starts at its smallest vertex (by x, then y). Polygons describing the same area
+    /// with the same rings become equal after normalization.
+    pub fn normalize(&mut self) {
+        normalize_ring(&mut self.exterior, true);
+        for hole in self.interiors.iter_mut() {
+            normalize_ring(hole, false);
+        }
+    }
+
+    /// iterate over the exterior ring followed by the holes
+    fn rings(&self) -> impl Iterator<Item = &Line> {
+        std::iter::once(&self.exterior).chain(self.interiors.iter())
+    }
+}
+
+/// get the points of a ring without the closing point (if the ring is closed)
+fn open_ring(ring: &Line) -> &[Point] {
+    let points = ring.get_points();
+    if points.len() > 1 && points.first() == points.last() {
+        &points[..points.len() - 1]
+    } else {
+        points
+    }
+}
+
+/// close a ring, rotate it to start at its smallest vertex and orient it in the requested direction
+fn normalize_ring(ring: &mut Line, counter_clockwise: bool) {
+    let mut points = open_ring(ring).to_vec();
+    let start = (0..points.len()).min_by_key(|&i| points[i].key()).unwrap();
+    points.rotate_left(start);
+    points.push(points[0]);
+    let mut normalized = Line::new(points);
+    // a closed ring keeps its start vertex when reversed
+    if normalized.is_ccw() != counter_clockwise {
+        normalized.reverse();
+    }
+    *ring = normalized;
+}
+
+/// get the (unsigned) area of a triangle
+fn triangle_area(a: &Point, b: &Point, c: &Point) -> f64 {
+    ((b.get_x() - a.get_x()) * (c.get_y() - a.get_y())
+        - (c.get_x() - a.get_x()) * (b.get_y() - a.get_y()))
+    .abs()
+        / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square_with_hole() -> Polygon {
+        Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 8, 8 8, 8 2, 2 2))")
+            .unwrap()
+    }
+
+    fn total_area(polygon: &Polygon) -> f64 {
+        let vertices = polygon.vertices();
+        polygon
+            .triangulate()
+            .iter()
+            .map(|t| triangle_area(&vertices[t[0]], &vertices[t[1]], &vertices[t[2]]))
+            .sum()
+    }
+
+    #[test]
+    fn test_creation() {
+        let exterior = Line::new(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(0.0, 1.0),
+            Point::new(0.0, 0.0),
+        ]);
+        let polygon = Polygon::new(exterior, vec![]);
+        assert_eq!(polygon.get_exterior().get_number_of_points(), 4);
+        assert_eq!(polygon.get_number_of_interiors(), 0);
+        assert_eq!(polygon.vertices().len(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "A polygon ring must have at least three points.")]
+    fn test_creation_panics_on_short_ring() {
+        Polygon::new(
+            Line::new(vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0)]),
+            vec![],
+        );
+    }
+
+    #[test]
+    fn test_wkt_roundtrip() {
+        let wkt = "POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0), (2 2, 2 8, 8 8, 8 2, 2 2))";
+        let polygon = Polygon::from_wkt(wkt).unwrap();
+        assert_eq!(polygon.get_number_of_interiors(), 1);
+        assert_eq!(polygon.to_wkt(), wkt);
+    }
+
+    #[test]
+    fn test_from_wkt_fail() {
+        assert!(Polygon::from_wkt("POLYGON (0 0, 1 0, 1 1)").is_err());
+        assert!(Polygon::from_wkt("POLYGON ((0 0, 1 0))").is_err());
+        assert!(Polygon::from_wkt("LINESTRING (0 0, 1 0)").is_err());
+    }
+
+    #[test]
+    fn test_triangulate_concave() {
+        // L-shaped polygon with area 3
+        let polygon = Polygon::from_wkt("POLYGON ((0 0, 2 0, 2 1, 1 1, 1 2, 0 2, 0 0))").unwrap();
+        let triangles = polygon.triangulate();
+        assert_eq!(triangles.len(), 4);
+        assert!((total_area(&polygon) - 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_triangulate_with_hole() {
+        let polygon = square_with_hole();
+        assert_eq!(polygon.triangulate().len(), 8);
+        assert!((total_area(&polygon) - 64.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_triangles_as_polygons() {
+        let triangles = square_with_hole().triangles();
+        assert_eq!(triangles.len(), 8);
+        for triangle in triangles {
+            assert_eq!(triangle.get_exterior().get_number_of_points(), 4);
+            assert_eq!(
+                triangle.get_exterior().get_start(),
+                triangle.get_exterior().get_end()
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_random_points_inside() {
+        let polygon = square_with_hole();
+        let points = polygon.generate_random_points(500, 42);
+        assert_eq!(points.len(), 500);
+        for p in &points {
+            let in_square = (0.0..=10.0).contains(&p.get_x()) && (0.0..=10.0).contains(&p.get_y());
+            let in_hole = p.get_x() > 2.0 && p.get_x() < 8.0 && p.get_y() > 2.0 && p.get_y() < 8.0;
+            assert!(in_square && !in_hole, "point outside polygon: {:?}", p);
+        }
+        assert_eq!(points, polygon.generate_random_points(500, 42));
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let polygon = square_with_hole();
+        let mut shifted = polygon.clone();
+        for ring in std::iter::once(&mut shifted.exterior).chain(shifted.interiors.iter_mut()) {
+            *ring = Line::new(
+                ring.get_points()
+                    .iter()
+                    .map(|p| Point::new(p.get_x() + 1e-9, p.get_y()))
+                    .collect(),
+            );
+        }
+        assert!(polygon.approx_eq(&shifted, 1e-8));
+        assert!(!polygon.approx_eq(&shifted, 1e-10));
+        let without_hole = Polygon::new(polygon.get_exterior().clone(), vec![]);
+        assert!(!polygon.approx_eq(&without_hole, 1.0));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut polygon =
+            Polygon::from_wkt("POLYGON ((4 4, 0 4, 0 0, 4 0, 4 4), (1 2, 2 2, 2 1, 1 1, 1 2))")
+                .unwrap();
+        polygon.normalize();
+        assert_eq!(
+            polygon.to_wkt(),
+            "POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0), (1 1, 1 2, 2 2, 2 1, 1 1))"
+        );
+        assert!(polygon.get_exterior().is_ccw());
+        assert!(!polygon.get_interiors()[0].is_ccw());
+
+        // the same area given clockwise, starting elsewhere and without closing points
+        let mut other = Polygon::new(
+            Line::new(vec![
+                Point::new(4.0, 0.0),
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 4.0),
+                Point::new(4.0, 4.0),
+            ]),
+            vec![Line::new(vec![
+                Point::new(2.0, 2.0),
+                Point::new(2.0, 1.0),
+                Point::new(1.0, 1.0),
+                Point::new(1.0, 2.0),
+            ])],
+        );
+        other.normalize();
+        assert_eq!(other, polygon);
+    }
+}
@@ -0,0 +1,238 @@
+use super::line::Line;
+use super::point::Point;
+use super::polygon::Polygon;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+/// Struct for an axis-aligned 2D rectangle
+pub struct Rect {
+    /// corner with the minimum x and y coordinates
+    min: Point,
+    /// corner with the maximum x and y coordinates
+    max: Point,
+}
+
+impl Rect {
+    /// create a rectangle from its minimum and maximum coordinates
+    pub fn new(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Rect {
+        // check range validity
+        if min_x > max_x || min_y > max_y {
+            panic!("Range is not valid, min values cannot be greater than max values!")
+        }
+        Rect {
+            min: Point::new(min_x, min_y),
+            max: Point::new(max_x, max_y),
+        }
+    }
+
+    /// create the smallest rectangle containing all given points. Return None for an empty slice.
+    pub fn from_points(points: &[Point]) -> Option<Rect> {
+        let first = points.first()?;
+        let mut rect = Rect {
+            min: *first,
+            max: *first,
+        };
+        for point in &points[1..] {
+            rect.expand_to_point(point);
+        }
+        Some(rect)
+    }
+
+    /// get the corner with the minimum coordinates
+    pub fn get_min(&self) -> Point {
+        self.min
+    }
+    /// get the corner with the maximum coordinates
+    pub fn get_max(&self) -> Point {
+        self.max
+    }
+
+    /// get the width (extent along x) of the rectangle
+    pub fn get_width(&self) -> f64 {
+        self.max.get_x() - self.min.get_x()
+    }
+    /// get the height (extent along y) of the rectangle
+    pub fn get_height(&self) -> f64 {
+        self.max.get_y() - self.min.get_y()
+    }
+
+    /// get the area of the rectangle
+    pub fn area(&self) -> f64 {
+        self.get_width() * self.get_height()
+    }
+
+    /// get the center of the rectangle
+    pub fn center(&self) -> Point {
+        Point::new(
+            (self.min.get_x() + self.max.get_x()) / 2.0,
+            (self.min.get_y() + self.max.get_y()) / 2.0,
+        )
+    }
+
+    /// check if a point lies inside the rectangle or on its boundary
+    pub fn contains_point(&self, point: &Point) -> bool {
+        point.get_x() >= self.min.get_x()
+            && point.get_x() <= self.max.get_x()
+            && point.get_y() >= self.min.get_y()
+            && point.get_y() <= self.max.get_y()
+    }
+
+    /// check if two rectangles intersect, touching boundaries count as intersection
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.get_x() <= other.max.get_x()
+            && self.max.get_x() >= other.min.get_x()
+            && self.min.get_y() <= other.max.get_y()
+            && self.max.get_y() >= other.min.get_y()
+    }
+
+    /// grow the rectangle so that it contains the given point
+    pub fn expand_to_point(&mut self, point: &Point) {
+        self.min = Point::new(
+            self.min.get_x().min(point.get_x()),
+            self.min.get_y().min(point.get_y()),
+        );
+        self.max = Point::new(
+            self.max.get_x().max(point.get_x()),
+            self.max.get_y().max(point.get_y()),
+        );
+    }
+
+    /// get the smallest rectangle containing both rectangles
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            min: Point::new(
+                self.min.get_x().min(other.min.get_x()),
+                self.min.get_y().min(other.min.get_y()),
+            ),
+            max: Point::new(
+                self.max.get_x().max(other.max.get_x()),
+                self.max.get_y().max(other.max.get_y()),
+            ),
+        }
+    }
+
+    /// calculate the distance from a point to the rectangle, zero if the point lies inside
+    pub fn distance_to_point(&self, point: &Point) -> f64 {
+        let dx = (self.min.get_x() - point.get_x())
+            .max(point.get_x() - self.max.get_x())
+            .max(0.0);
+        let dy = (self.min.get_y() - point.get_y())
+            .max(point.get_y() - self.max.get_y())
+            .max(0.0);
+        dx.hypot(dy)
+    }
+
+    /// calculate the distance between two rectangles, zero if they intersect
+    pub fn distance_to_rect(&self, other: &Rect) -> f64 {
+        let dx = (self.min.get_x() - other.max.get_x())
+            .max(other.min.get_x() - self.max.get_x())
+            .max(0.0);
+        let dy = (self.min.get_y() - other.max.get_y())
+            .max(other.min.get_y() - self.max.get_y())
+            .max(0.0);
+        dx.hypot(dy)
+    }
+
+    /// get the rectangle grown by a distance on every side
+    pub fn expand_by(&self, distance: f64) -> Rect {
+        Rect::new(
+            self.min.get_x() - distance,
+            self.min.get_y() - distance,
+            self.max.get_x() + distance,
+            self.max.get_y() + distance,
+        )
+    }
+
+    /// get the rectangle as a closed counter-clockwise polygon
+    pub fn to_polygon(&self) -> Polygon {
+        let (min_x, min_y) = (self.min.get_x(), self.min.get_y());
+        let (max_x, max_y) = (self.max.get_x(), self.max.get_y());
+        Polygon::new(
+            Line::new(vec![
+                Point::new(min_x, min_y),
+                Point::new(max_x, min_y),
+                Point::new(max_x, max_y),
+                Point::new(min_x, max_y),
+                Point::new(min_x, min_y),
+            ]),
+            vec![],
+        )
+    }
+}
+
+/// convert the `(min, max)` tuple returned by [`Line::bounding_box`] into a rectangle
+impl From<(Point, Point)> for Rect {
+    fn from(corners: (Point, Point)) -> Self {
+        Rect::new(
+            corners.0.get_x(),
+            corners.0.get_y(),
+            corners.1.get_x(),
+            corners.1.get_y(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_creation() {
+        let rect = Rect::new(-1.0, 2.0, 3.0, 5.0);
+        assert_eq!(rect.get_min(), Point::new(-1.0, 2.0));
+        assert_eq!(rect.get_max(), Point::new(3.0, 5.0));
+        assert_eq!(rect.get_width(), 4.0);
+        assert_eq!(rect.get_height(), 3.0);
+        assert_eq!(rect.area(), 12.0);
+        assert_eq!(rect.center(), Point::new(1.0, 3.5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Range is not valid, min values cannot be greater than max values!")]
+    fn test_creation_panics_on_invalid_range() {
+        Rect::new(5.0, 0.0, 1.0, 10.0);
+    }
+
+    #[test]
+    fn test_from_points_and_bounding_box() {
+        let points = vec![
+            Point::new(-1.0, 2.0),
+            Point::new(74.5, -98.0),
+            Point::new(0.0, -4.0),
+        ];
+        let rect = Rect::from_points(&points).unwrap();
+        assert_eq!(rect, Rect::new(-1.0, -98.0, 74.5, 2.0));
+        assert_eq!(rect, Rect::from(Line::new(points).bounding_box()));
+        assert!(Rect::from_points(&[]).is_none());
+    }
+
+    #[test]
+    fn test_contains_and_intersects() {
+        let rect = Rect::new(0.0, 0.0, 2.0, 2.0);
+        assert!(rect.contains_point(&Point::new(1.0, 1.0)));
+        assert!(rect.contains_point(&Point::new(2.0, 0.0)));
+        assert!(!rect.contains_point(&Point::new(2.1, 0.0)));
+        assert!(rect.intersects(&Rect::new(2.0, 2.0, 3.0, 3.0)));
+        assert!(!rect.intersects(&Rect::new(2.5, 0.0, 3.0, 1.0)));
+    }
+
+    #[test]
+    fn test_union_and_distance() {
+        let rect = Rect::new(0.0, 0.0, 2.0, 2.0);
+        assert_eq!(
+            rect.union(&Rect::new(1.0, -1.0, 3.0, 1.0)),
+            Rect::new(0.0, -1.0, 3.0, 2.0)
+        );
+        assert_eq!(rect.distance_to_point(&Point::new(1.0, 1.0)), 0.0);
+        assert_eq!(rect.distance_to_point(&Point::new(1.0, 5.0)), 3.0);
+        assert_eq!(rect.distance_to_point(&Point::new(5.0, 6.0)), 5.0);
+        assert_eq!(rect.distance_to_rect(&Rect::new(1.0, 1.0, 4.0, 4.0)), 0.0);
+        assert_eq!(rect.distance_to_rect(&Rect::new(5.0, 6.0, 7.0, 7.0)), 5.0);
+        assert_eq!(rect.expand_by(1.0), Rect::new(-1.0, -1.0, 3.0, 3.0));
+    }
+
+    #[test]
+    fn test_to_polygon() {
+        let polygon = Rect::new(0.0, 0.0, 2.0, 1.0).to_polygon();
+        assert_eq!(polygon.to_wkt(), "POLYGON ((0 0, 2 0, 2 1, 0 1, 0 0))");
+    }
+}
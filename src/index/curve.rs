@@ -0,0 +1,232 @@
+use super::HasBoundingBox;
+use crate::geoms::point::Point;
+use crate::geoms::rect::Rect;
+
+/// number of bits per axis used when sorting
+const SORT_BITS: u32 = 16;
+
+impl Point {
+    /// Get the position of the point along a Hilbert curve filling the rectangle. Each axis of the rectangle is
+    /// split into 2^bits cells, points outside are clamped to the border cells. Points close along the curve are
+    /// close in the plane, without the jumps of the Morton order.
+    pub fn hilbert_key(&self, bounds: &Rect, bits: u32) -> u64 {
+        let (mut x, mut y) = grid_cell(self, bounds, bits);
+        let n = 1u64 << bits;
+        let mut key = 0;
+        let mut s = n / 2;
+        while s > 0 {
+            let rx = (x & s > 0) as u64;
+            let ry = (y & s > 0) as u64;
+            key += s * s * ((3 * rx) ^ ry);
+            // rotate the quadrant so the curve inside it starts and ends at the right corners
+            if ry == 0 {
+                if rx == 1 {
+                    x = n - 1 - x;
+                    y = n - 1 - y;
+                }
+                std::mem::swap(&mut x, &mut y);
+            }
+            s /= 2;
+        }
+        key
+    }
+
+    /// Get the position of the point along a Morton (Z-order) curve filling the rectangle, which interleaves the
+    /// bits of the cell coordinates with x in the lower bit of each pair. Cells are computed like in
+    /// [`Point::hilbert_key`].
+    pub fn morton_key(&self, bounds: &Rect, bits: u32) -> u64 {
+        let (x, y) = grid_cell(self, bounds, bits);
+        spread_bits(x) | (spread_bits(y) << 1)
+    }
+}
+
+/// Sort objects along a Hilbert curve through the centers of their envelopes, which keeps nearby objects together.
+/// Empty geometries, which have no envelope, are moved to the end in their original order.
+pub fn sort_by_hilbert<T: HasBoundingBox>(items: &mut [T]) {
+    sort_by_curve(items, Point::hilbert_key);
+}
+
+/// sort objects along a Morton curve through the centers of their envelopes, empty geometries are moved to the end
+/// like in [`sort_by_hilbert`]
+pub fn sort_by_morton<T: HasBoundingBox>(items: &mut [T]) {
+    sort_by_curve(items, Point::morton_key);
+}
+
+fn sort_by_curve<T, F>(items: &mut [T], key: F)
+where
+    T: HasBoundingBox,
+    F: Fn(&Point, &Rect, u32) -> u64,
+{
+    let centers: Vec<Point> = items
+        .iter()
+        .filter_map(|item| item.checked_envelope())
+        .map(|envelope| envelope.center())
+        .collect();
+    let Some(bounds) = Rect::from_points(&centers) else {
+        return;
+    };
+    // the stable sort keeps the empty objects in their order
+    items.sort_by_cached_key(|item| match item.checked_envelope() {
+        Some(envelope) => (false, key(&envelope.center(), &bounds, SORT_BITS)),
+        None => (true, 0),
+    });
+}
+
+/// get the column and row of the grid cell containing a point
+fn grid_cell(point: &Point, bounds: &Rect, bits: u32) -> (u64, u64) {
+    if bits == 0 || bits > 32 {
+        panic!("Number of bits must be between 1 and 32.");
+    }
+    let cells = (1u64 << bits) as f64;
+    let cell = |value: f64, min: f64, size: f64| {
+        if size <= 0.0 {
+            return 0;
+        }
+        // the float to integer cast saturates, which clamps values below the minimum to 0
+        let cell = ((value - min) / size * cells) as u64;
+        cell.min((1u64 << bits) - 1)
+    };
+    let min = bounds.get_min();
+    (
+        cell(point.get_x(), min.get_x(), bounds.get_width()),
+        cell(point.get_y(), min.get_y(), bounds.get_height()),
+    )
+}
+
+/// move the lower 32 bits of a number to the even bit positions
+fn spread_bits(value: u64) -> u64 {
+    let mut v = value & 0xFFFF_FFFF;
+    v = (v | (v << 16)) & 0x0000_FFFF_0000_FFFF;
+    v = (v | (v << 8)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    v = (v | (v << 2)) & 0x3333_3333_3333_3333;
+    (v | (v << 1)) & 0x5555_5555_5555_5555
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geoms::Geometry;
+    use crate::geoms::line::Line;
+    use crate::geoms::multipolygon::MultiPolygon;
+    use crate::geoms::polygon::Polygon;
+
+    /// centers of the cells of a 2^bits x 2^bits grid over the unit square
+    fn cell_centers(bits: u32) -> Vec<Point> {
+        let n = 1 << bits;
+        let size = 1.0 / n as f64;
+        (0..n)
+            .flat_map(|i| {
+                (0..n).map(move |j| Point::new((i as f64 + 0.5) * size, (j as f64 + 0.5) * size))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hilbert_key() {
+        let unit = Rect::new(0.0, 0.0, 1.0, 1.0);
+        let keys: Vec<u64> = [(0.25, 0.25), (0.25, 0.75), (0.75, 0.75), (0.75, 0.25)]
+            .iter()
+            .map(|&(x, y)| Point::new(x, y).hilbert_key(&unit, 1))
+            .collect();
+        assert_eq!(keys, vec![0, 1, 2, 3]);
+        // consecutive keys belong to neighbouring cells and every key is used once
+        let mut points = cell_centers(4);
+        points.sort_by_key(|p| p.hilbert_key(&unit, 4));
+        for (key, point) in points.iter().enumerate() {
+            assert_eq!(point.hilbert_key(&unit, 4), key as u64);
+        }
+        for pair in points.windows(2) {
+            assert!((pair[0].distance2D(&pair[1]) - 1.0 / 16.0).abs() < 1e-12);
+        }
+        // points outside the rectangle are clamped
+        assert_eq!(Point::new(-5.0, -5.0).hilbert_key(&unit, 32), 0);
+        assert_eq!(Point::new(5.0, -5.0).hilbert_key(&unit, 1), 3);
+        assert_eq!(
+            Point::new(5.0, 5.0).hilbert_key(&unit, 32),
+            Point::new(1.0, 1.0).hilbert_key(&unit, 32)
+        );
+    }
+
+    #[test]
+    fn test_morton_key() {
+        let unit = Rect::new(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(Point::new(0.75, 0.25).morton_key(&unit, 1), 1);
+        assert_eq!(Point::new(0.25, 0.75).morton_key(&unit, 1), 2);
+        // cell (5, 3) = (101, 011) interleaves to 011011
+        assert_eq!(
+            Point::new(5.5 / 8.0, 3.5 / 8.0).morton_key(&unit, 3),
+            0b011011
+        );
+        assert_eq!(Point::new(1.0, 1.0).morton_key(&unit, 32), u64::MAX);
+        let mut keys: Vec<u64> = cell_centers(3)
+            .iter()
+            .map(|p| p.morton_key(&unit, 3))
+            .collect();
+        keys.sort();
+        assert_eq!(keys, (0..64).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn test_sort() {
+        let mut points = vec![
+            Point::new(10.0, 0.0),
+            Point::new(0.0, 10.0),
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 10.0),
+        ];
+        sort_by_hilbert(&mut points);
+        assert_eq!(
+            points,
+            vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 10.0),
+                Point::new(10.0, 10.0),
+                Point::new(10.0, 0.0)
+            ]
+        );
+        sort_by_morton(&mut points);
+        assert_eq!(points[1], Point::new(10.0, 0.0));
+        let mut rects = vec![
+            Rect::new(8.0, 0.0, 10.0, 2.0),
+            Rect::new(0.0, 0.0, 2.0, 2.0),
+            Rect::new(0.0, 8.0, 2.0, 10.0),
+        ];
+        sort_by_hilbert(&mut rects);
+        assert_eq!(rects[0], Rect::new(0.0, 0.0, 2.0, 2.0));
+        assert_eq!(rects[2], Rect::new(8.0, 0.0, 10.0, 2.0));
+        sort_by_hilbert::<Point>(&mut []);
+    }
+
+    #[test]
+    fn test_sort_geometries() {
+        let mut geometries: Vec<Geometry> = vec![
+            Polygon::from_wkt("POLYGON ((9 0, 10 0, 10 1, 9 1, 9 0))")
+                .unwrap()
+                .into(),
+            Point::new(0.0, 10.0).into(),
+            Line::from_wkt("LINESTRING (0 0, 1 1)").unwrap().into(),
+            MultiPolygon::new(vec![
+                Polygon::from_wkt("POLYGON ((9 9, 10 9, 10 10, 9 10, 9 9))").unwrap(),
+            ])
+            .into(),
+        ];
+        sort_by_hilbert(&mut geometries);
+        let dimensions: Vec<u8> = geometries.iter().map(Geometry::dimension).collect();
+        assert_eq!(dimensions, vec![1, 0, 2, 2]);
+        assert!(matches!(geometries[2], Geometry::MultiPolygon(_)));
+        geometries.insert(1, MultiPolygon::new(vec![]).into());
+        sort_by_morton(&mut geometries);
+        assert_eq!(geometries[4], MultiPolygon::new(vec![]).into());
+        sort_by_hilbert(&mut geometries);
+        let dimensions: Vec<u8> = geometries.iter().map(Geometry::dimension).collect();
+        assert_eq!(dimensions, vec![1, 0, 2, 2, 2]);
+        assert_eq!(geometries[4], MultiPolygon::new(vec![]).into());
+    }
+
+    #[test]
+    #[should_panic(expected = "Number of bits must be between 1 and 32.")]
+    fn test_too_many_bits() {
+        Point::new(0.0, 0.0).morton_key(&Rect::new(0.0, 0.0, 1.0, 1.0), 33);
+    }
+}
@@ -0,0 +1,317 @@
+use super::{CellCoverage, geometry_envelope, relate_grid_cell};
+use crate::geoms::Geometry;
+use crate::geoms::point::Point;
+use crate::geoms::rect::Rect;
+
+/// characters of the geohash alphabet, each one encodes five bits
+const BASE32: &[u8; 32] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+
+/// longest supported geohash, its cells are a few centimetres wide
+const MAX_PRECISION: usize = 12;
+
+impl Point {
+    /// Encode a point with longitude as x and latitude as y, both in degrees, as a geohash of the given number of
+    /// characters. Points on the border between two cells belong to the cell to the north or east.
+    pub fn to_geohash(&self, precision: usize) -> Result<String, &'static str> {
+        check_precision(precision)?;
+        let (lon, lat) = (self.get_x(), self.get_y());
+        if !(-180.0..=180.0).contains(&lon) || !(-90.0..=90.0).contains(&lat) {
+            return Err("Coordinates must be longitude and latitude in degrees");
+        }
+        let (mut lon_range, mut lat_range) = ((-180.0, 180.0), (-90.0, 90.0));
+        let mut hash = String::with_capacity(precision);
+        let mut bit = 0;
+        while hash.len() < precision {
+            let mut index = 0;
+            for _ in 0..5 {
+                // even bits split the longitude, odd bits the latitude
+                let (range, value) = if bit % 2 == 0 {
+                    (&mut lon_range, lon)
+                } else {
+                    (&mut lat_range, lat)
+                };
+                let mid = (range.0 + range.1) / 2.0;
+                index <<= 1;
+                if value >= mid {
+                    index |= 1;
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                bit += 1;
+            }
+            hash.push(BASE32[index] as char);
+        }
+        Ok(hash)
+    }
+
+    /// decode a geohash to the center of its cell, with longitude as x and latitude as y
+    pub fn from_geohash(hash: &str) -> Result<Point, &'static str> {
+        geohash_bounds(hash).map(|bounds| bounds.center())
+    }
+}
+
+/// get the cell of a geohash as a rectangle in longitude and latitude
+pub fn geohash_bounds(hash: &str) -> Result<Rect, &'static str> {
+    if hash.is_empty() {
+        return Err("Geohash must not be empty");
+    }
+    let (mut lon_range, mut lat_range) = ((-180.0, 180.0), (-90.0, 90.0));
+    let mut bit = 0;
+    for c in hash.bytes() {
+        let index = BASE32
+            .iter()
+            .position(|&b| b == c.to_ascii_lowercase())
+            .ok_or("Invalid geohash character")?;
+        for shift in (0..5).rev() {
+            let range = if bit % 2 == 0 {
+                &mut lon_range
+            } else {
+                &mut lat_range
+            };
+            let mid = (range.0 + range.1) / 2.0;
+            if index >> shift & 1 == 1 {
+                range.0 = mid;
+            } else {
+                range.1 = mid;
+            }
+            bit += 1;
+        }
+    }
+    Ok(Rect::new(
+        lon_range.0,
+        lat_range.0,
+        lon_range.1,
+        lat_range.1,
+    ))
+}
+
+/// Get the geohashes of the same length around a cell in the order north, north-east, east, south-east, south,
+/// south-west, west and north-west. Cells wrap around the antimeridian, cells beyond a pole are left out.
+pub fn geohash_neighbors(hash: &str) -> Result<Vec<String>, &'static str> {
+    let bounds = geohash_bounds(hash)?;
+    let center = bounds.center();
+    let (width, height) = (bounds.get_width(), bounds.get_height());
+    let mut neighbors = Vec::with_capacity(8);
+    for (dx, dy) in [
+        (0, 1),
+        (1, 1),
+        (1, 0),
+        (1, -1),
+        (0, -1),
+        (-1, -1),
+        (-1, 0),
+        (-1, 1),
+    ] {
+        let lat = center.get_y() + dy as f64 * height;
+        if !(-90.0..=90.0).contains(&lat) {
+            continue;
+        }
+        let lon = (center.get_x() + dx as f64 * width + 180.0).rem_euclid(360.0) - 180.0;
+        neighbors.push(Point::new(lon, lat).to_geohash(hash.len())?);
+    }
+    Ok(neighbors)
+}
+
+/// Get the sorted geohashes of the given length whose cells meet a geometry in longitude and latitude. A geometry
+/// on the border between two cells only meets the cell to the north or east, like a point encoded there, so the
+/// cover contains no cell which only touches the geometry. The cells are refined from the coarsest level down,
+/// only descending into cells which meet the geometry, and cells completely covered by a polygon are filled without
+/// further tests.
+pub fn geohash_cover(geometry: &Geometry, precision: usize) -> Result<Vec<String>, &'static str> {
+    cover(geometry, precision, false)
+}
+
+/// Get the smallest sorted set of geohashes of at most the given length covering the same area as
+/// [`geohash_cover`]: cells completely covered by a polygon are returned as a single shorter geohash instead of all
+/// their descendants.
+pub fn geohash_cover_compact(
+    geometry: &Geometry,
+    precision: usize,
+) -> Result<Vec<String>, &'static str> {
+    cover(geometry, precision, true)
+}
+
+fn cover(
+    geometry: &Geometry,
+    precision: usize,
+    compact: bool,
+) -> Result<Vec<String>, &'static str> {
+    check_precision(precision)?;
+    let mut cover = Vec::new();
+    if let Some(envelope) = geometry_envelope(geometry) {
+        let search = CoverSearch {
+            geometry,
+            envelope,
+            world: Rect::new(-180.0, -90.0, 180.0, 90.0),
+            precision,
+            compact,
+        };
+        search.cover_children(String::new(), &mut cover);
+    }
+    cover.sort();
+    Ok(cover)
+}
+
+/// parameters of a cover which stay the same during the refinement
+struct CoverSearch<'a> {
+    geometry: &'a Geometry,
+    envelope: Rect,
+    world: Rect,
+    precision: usize,
+    compact: bool,
+}
+
+impl CoverSearch<'_> {
+    /// add the cells below a geohash which meet the geometry
+    fn cover_children(&self, hash: String, cover: &mut Vec<String>) {
+        for &c in BASE32 {
+            let mut child = hash.clone();
+            child.push(c as char);
+            let bounds = geohash_bounds(&child).unwrap();
+            if !bounds.intersects(&self.envelope) {
+                continue;
+            }
+            match relate_grid_cell(self.geometry, &bounds, true, &self.world) {
+                CellCoverage::Outside => {}
+                CellCoverage::Full if self.compact => cover.push(child),
+                CellCoverage::Full => add_descendants(child, self.precision, cover),
+                CellCoverage::Partial if child.len() == self.precision => cover.push(child),
+                CellCoverage::Partial => self.cover_children(child, cover),
+            }
+        }
+    }
+}
+
+/// add all cells of the given length below a geohash
+fn add_descendants(hash: String, precision: usize, cover: &mut Vec<String>) {
+    if hash.len() == precision {
+        cover.push(hash);
+        return;
+    }
+    for &c in BASE32 {
+        let mut child = hash.clone();
+        child.push(c as char);
+        add_descendants(child, precision, cover);
+    }
+}
+
+fn check_precision(precision: usize) -> Result<(), &'static str> {
+    if precision == 0 || precision > MAX_PRECISION {
+        return Err("Geohash precision must be between 1 and 12");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::relate::relate;
+    use crate::geoms::line::Line;
+    use crate::geoms::polygon::Polygon;
+
+    #[test]
+    fn test_encode_and_decode() {
+        let point = Point::new(10.40744, 57.64911);
+        assert_eq!(point.to_geohash(11).unwrap(), "u4pruydqqvj");
+        assert_eq!(point.to_geohash(1).unwrap(), "u");
+        assert_eq!(Point::new(-5.6, 42.6).to_geohash(5).unwrap(), "ezs42");
+        let decoded = Point::from_geohash("u4pruydqqvj").unwrap();
+        assert!(decoded.approx_eq(&point, 1e-5));
+        assert_eq!(
+            geohash_bounds("s").unwrap(),
+            Rect::new(0.0, 0.0, 45.0, 45.0)
+        );
+        assert_eq!(Point::from_geohash("S").unwrap(), Point::new(22.5, 22.5));
+        assert!(Point::from_geohash("").is_err());
+        assert!(Point::from_geohash("ua").is_err());
+        assert!(point.to_geohash(13).is_err());
+        assert!(Point::new(200.0, 0.0).to_geohash(5).is_err());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let neighbors = geohash_neighbors("ezs42").unwrap();
+        assert_eq!(
+            neighbors,
+            vec![
+                "ezs48", "ezs49", "ezs43", "ezs41", "ezs40", "ezefp", "ezefr", "ezefx"
+            ]
+        );
+        let bounds = geohash_bounds("ezs42").unwrap();
+        let north = geohash_bounds(&neighbors[0]).unwrap();
+        assert_eq!(north.get_min().get_y(), bounds.get_max().get_y());
+        // across the antimeridian and at the north pole
+        let east_edge = Point::new(179.9, 0.1).to_geohash(3).unwrap();
+        let across = Point::new(-179.9, 0.1).to_geohash(3).unwrap();
+        assert_eq!(geohash_neighbors(&east_edge).unwrap()[2], across);
+        assert_eq!(geohash_neighbors("zzz").unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_cover() {
+        let line = Geometry::Line(Line::from_wkt("LINESTRING (1 1, 50 1)").unwrap());
+        assert_eq!(geohash_cover(&line, 1).unwrap(), vec!["s", "t"]);
+        let polygon: Geometry = Polygon::from_wkt("POLYGON ((1 1, 44 1, 44 44, 1 44, 1 1))")
+            .unwrap()
+            .into();
+        let cover = geohash_cover(&polygon, 2).unwrap();
+        assert_eq!(cover.len(), 32);
+        assert!(cover.iter().all(|hash| hash.starts_with('s')));
+        // every cell of the cover intersects the polygon and every point of the polygon is covered
+        let cover = geohash_cover(&polygon, 3).unwrap();
+        for hash in &cover {
+            let cell = geohash_bounds(hash).unwrap().to_polygon().into();
+            assert!(relate(&polygon, &cell).matches("T********"));
+        }
+        for point in Point::generate_random_points(200, 1.0, 1.0, 44.0, 44.0, 41) {
+            assert!(cover.contains(&point.to_geohash(3).unwrap()));
+        }
+        assert!(geohash_cover(&polygon, 0).is_err());
+    }
+
+    #[test]
+    fn test_cover_of_exact_cell() {
+        let cell: Geometry = Polygon::from_wkt("POLYGON ((0 0, 45 0, 45 45, 0 45, 0 0))")
+            .unwrap()
+            .into();
+        // neighbouring cells only touch the polygon and are left out
+        assert_eq!(geohash_cover(&cell, 1).unwrap(), vec!["s"]);
+        let cover = geohash_cover(&cell, 3).unwrap();
+        assert_eq!(cover.len(), 1024);
+        assert!(cover.iter().all(|hash| hash.starts_with('s')));
+        assert_eq!(geohash_cover_compact(&cell, 3).unwrap(), vec!["s"]);
+    }
+
+    #[test]
+    fn test_cover_on_cell_borders() {
+        // a point on a corner and a line on an edge go to the cells to the north and east, like encoding does
+        let point = Point::new(45.0, 45.0);
+        assert_eq!(
+            geohash_cover(&point.into(), 1).unwrap(),
+            vec![point.to_geohash(1).unwrap()]
+        );
+        let line = Geometry::Line(Line::from_wkt("LINESTRING (10 45, 30 45)").unwrap());
+        assert_eq!(geohash_cover(&line, 1).unwrap(), vec!["u"]);
+        // the edges of the world belong to the outermost cells
+        let pole = Geometry::Line(Line::from_wkt("LINESTRING (10 90, 30 90)").unwrap());
+        assert_eq!(geohash_cover(&pole, 1).unwrap(), vec!["u"]);
+    }
+
+    #[test]
+    fn test_compact_cover() {
+        let polygon: Geometry = Polygon::from_wkt("POLYGON ((0 0, 50 0, 50 45, 0 45, 0 0))")
+            .unwrap()
+            .into();
+        let compact = geohash_cover_compact(&polygon, 2).unwrap();
+        assert_eq!(compact[0], "s");
+        // the compact cover describes the same cells as the full one
+        let mut expanded = Vec::new();
+        for hash in &compact {
+            add_descendants(hash.clone(), 2, &mut expanded);
+        }
+        expanded.sort();
+        assert_eq!(expanded, geohash_cover(&polygon, 2).unwrap());
+        assert!(compact.len() < expanded.len());
+    }
+}
@@ -0,0 +1,291 @@
+use crate::geoms::line::Line;
+use crate::geoms::point::Point;
+use crate::geoms::polygon::Polygon;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Orientation of the hexagons of a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexOrientation {
+    /// a corner points up, rows of hexagons are horizontal
+    PointyTop,
+    /// an edge lies on top, columns of hexagons are vertical
+    FlatTop,
+}
+
+/// Hexagon of a grid in axial coordinates. The third cube coordinate is s = -q - r.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Hex {
+    q: i64,
+    r: i64,
+}
+
+/// steps to the six neighbours of a hexagon, counter-clockwise starting to the east (pointy) or north-east (flat)
+const DIRECTIONS: [(i64, i64); 6] = [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)];
+
+impl Hex {
+    /// create a hexagon from its axial coordinates
+    pub fn new(q: i64, r: i64) -> Hex {
+        Hex { q, r }
+    }
+
+    /// get the q coordinate
+    pub fn get_q(&self) -> i64 {
+        self.q
+    }
+
+    /// get the r coordinate
+    pub fn get_r(&self) -> i64 {
+        self.r
+    }
+
+    /// get the third cube coordinate
+    pub fn get_s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    /// get the number of steps between two hexagons
+    pub fn distance(&self, other: &Hex) -> i64 {
+        let (dq, dr) = (self.q - other.q, self.r - other.r);
+        (dq.abs() + dr.abs() + (dq + dr).abs()) / 2
+    }
+
+    /// get the six adjacent hexagons in counter-clockwise order
+    pub fn neighbors(&self) -> [Hex; 6] {
+        DIRECTIONS.map(|(dq, dr)| Hex::new(self.q + dq, self.r + dr))
+    }
+
+    /// get the hexagons at exactly k steps, 6k of them in counter-clockwise order, only the hexagon itself for k = 0
+    pub fn ring(&self, k: u32) -> Vec<Hex> {
+        if k == 0 {
+            return vec![*self];
+        }
+        let k = k as i64;
+        let (start_q, start_r) = DIRECTIONS[4];
+        let mut hex = Hex::new(self.q + start_q * k, self.r + start_r * k);
+        let mut ring = Vec::with_capacity(6 * k as usize);
+        for (dq, dr) in DIRECTIONS {
+            for _ in 0..k {
+                ring.push(hex);
+                hex = Hex::new(hex.q + dq, hex.r + dr);
+            }
+        }
+        ring
+    }
+
+    /// get all hexagons within k steps, ring by ring from the hexagon itself outwards
+    pub fn disk(&self, k: u32) -> Vec<Hex> {
+        (0..=k).flat_map(|i| self.ring(i)).collect()
+    }
+}
+
+/// Grid of regular hexagons covering the plane, with one hexagon centered at the origin.
+///
+/// `size` is the distance from the center of a hexagon to its corners, which equals the side length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HexGrid {
+    size: f64,
+    orientation: HexOrientation,
+}
+
+impl HexGrid {
+    /// create a grid of hexagons with the given size and orientation
+    pub fn new(size: f64, orientation: HexOrientation) -> HexGrid {
+        if size.is_nan() || size <= 0.0 || size.is_infinite() {
+            panic!("Hexagon size must be positive and finite.");
+        }
+        HexGrid { size, orientation }
+    }
+
+    /// get the distance from the center of a hexagon to its corners
+    pub fn get_size(&self) -> f64 {
+        self.size
+    }
+
+    /// get the orientation of the hexagons
+    pub fn get_orientation(&self) -> HexOrientation {
+        self.orientation
+    }
+
+    /// get the hexagon containing a point, points on an edge go to either hexagon
+    pub fn hex_of(&self, point: &Point) -> Hex {
+        let (x, y) = (point.get_x() / self.size, point.get_y() / self.size);
+        let sqrt3 = 3f64.sqrt();
+        let (q, r) = match self.orientation {
+            HexOrientation::PointyTop => (sqrt3 / 3.0 * x - y / 3.0, 2.0 / 3.0 * y),
+            HexOrientation::FlatTop => (2.0 / 3.0 * x, -x / 3.0 + sqrt3 / 3.0 * y),
+        };
+        // round the fractional cube coordinates and fix the one which changed most, so they add up to zero again
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Hex::new(rq as i64, rr as i64)
+    }
+
+    /// get the center of a hexagon
+    pub fn center(&self, hex: &Hex) -> Point {
+        let (q, r) = (hex.q as f64, hex.r as f64);
+        let sqrt3 = 3f64.sqrt();
+        let (x, y) = match self.orientation {
+            HexOrientation::PointyTop => (sqrt3 * q + sqrt3 / 2.0 * r, 1.5 * r),
+            HexOrientation::FlatTop => (1.5 * q, sqrt3 / 2.0 * q + sqrt3 * r),
+        };
+        Point::new(x * self.size, y * self.size)
+    }
+
+    /// get the boundary of a hexagon as a closed counter-clockwise polygon
+    pub fn boundary(&self, hex: &Hex) -> Polygon {
+        let center = self.center(hex);
+        let offset = match self.orientation {
+            HexOrientation::PointyTop => 30.0,
+            HexOrientation::FlatTop => 0.0,
+        };
+        let mut corners: Vec<Point> = (0..6)
+            .map(|i| {
+                let angle = (60.0 * i as f64 + offset) * PI / 180.0;
+                Point::new(
+                    center.get_x() + self.size * angle.cos(),
+                    center.get_y() + self.size * angle.sin(),
+                )
+            })
+            .collect();
+        corners.push(corners[0]);
+        Polygon::new(Line::new(corners), vec![])
+    }
+
+    /// count the points falling into each hexagon, hexagons without points are left out
+    pub fn hexbin_counts(&self, points: &[Point]) -> HashMap<Hex, usize> {
+        let mut counts = HashMap::new();
+        for point in points {
+            *counts.entry(self.hex_of(point)).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// sum up a value per point in each hexagon, hexagons without points are left out
+    pub fn hexbin_sums(&self, points: &[Point], values: &[f64]) -> HashMap<Hex, f64> {
+        if points.len() != values.len() {
+            panic!("Every point needs exactly one value.");
+        }
+        let mut sums = HashMap::new();
+        for (point, value) in points.iter().zip(values) {
+            *sums.entry(self.hex_of(point)).or_insert(0.0) += value;
+        }
+        sums
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::locate::{Location, locate_point_in_polygon};
+
+    #[test]
+    fn test_hex_coordinates() {
+        let hex = Hex::new(2, -1);
+        assert_eq!(hex.get_s(), -1);
+        assert_eq!(hex.distance(&Hex::new(-1, 1)), 3);
+        assert!(hex.neighbors().iter().all(|n| n.distance(&hex) == 1));
+        assert_eq!(hex.ring(0), vec![hex]);
+        for k in 1..4 {
+            let ring = hex.ring(k);
+            assert_eq!(ring.len(), 6 * k as usize);
+            assert!(ring.iter().all(|h| h.distance(&hex) == k as i64));
+            // consecutive hexagons of a ring are adjacent
+            assert!(ring.windows(2).all(|w| w[0].distance(&w[1]) == 1));
+        }
+        let mut disk = hex.disk(2);
+        assert_eq!(disk.len(), 19);
+        disk.sort();
+        disk.dedup();
+        assert_eq!(disk.len(), 19);
+    }
+
+    #[test]
+    fn test_point_to_hex() {
+        for orientation in [HexOrientation::PointyTop, HexOrientation::FlatTop] {
+            let grid = HexGrid::new(2.0, orientation);
+            for hex in Hex::new(0, 0).disk(3) {
+                assert_eq!(grid.hex_of(&grid.center(&hex)), hex);
+            }
+            // every point lies in its hexagon, which has the nearest center of the grid
+            for point in Point::generate_random_points(500, -20.0, -20.0, 20.0, 20.0, 51) {
+                let hex = grid.hex_of(&point);
+                let distance = point.distance2D(&grid.center(&hex));
+                for neighbor in hex.neighbors() {
+                    assert!(distance <= point.distance2D(&grid.center(&neighbor)) + 1e-9);
+                }
+                assert_ne!(
+                    locate_point_in_polygon(&point, &grid.boundary(&hex)),
+                    Location::Exterior
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_boundary() {
+        let grid = HexGrid::new(1.0, HexOrientation::FlatTop);
+        let boundary = grid.boundary(&Hex::new(0, 0));
+        let ring = boundary.get_exterior();
+        assert_eq!(ring.get_number_of_points(), 7);
+        assert!(ring.is_ccw());
+        assert!(ring.get_points()[0].approx_eq(&Point::new(1.0, 0.0), 1e-12));
+        let area = ring.signed_area().unwrap();
+        assert!((area - 1.5 * 3f64.sqrt()).abs() < 1e-12);
+        // the neighbours are in counter-clockwise order around the hexagon
+        let mut centers: Vec<Point> = Hex::new(0, 0)
+            .neighbors()
+            .iter()
+            .map(|hex| grid.center(hex))
+            .collect();
+        centers.push(centers[0]);
+        assert!(Line::new(centers).is_ccw());
+        // neighbouring hexagons share an edge
+        let pointy = HexGrid::new(1.0, HexOrientation::PointyTop);
+        let a = pointy.boundary(&Hex::new(0, 0));
+        let b = pointy.boundary(&Hex::new(1, 0));
+        let shared = a
+            .get_exterior()
+            .get_points()
+            .iter()
+            .take(6)
+            .filter(|p| {
+                b.get_exterior()
+                    .get_points()
+                    .iter()
+                    .any(|q| p.approx_eq(q, 1e-9))
+            })
+            .count();
+        assert_eq!(shared, 2);
+    }
+
+    #[test]
+    fn test_hexbin() {
+        let grid = HexGrid::new(1.0, HexOrientation::PointyTop);
+        let points = vec![
+            Point::new(0.1, 0.1),
+            Point::new(-0.2, 0.3),
+            Point::new(1.8, 0.0),
+            Point::new(0.0, -0.1),
+        ];
+        let counts = grid.hexbin_counts(&points);
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&Hex::new(0, 0)], 3);
+        assert_eq!(counts[&Hex::new(1, 0)], 1);
+        let sums = grid.hexbin_sums(&points, &[1.0, 2.0, 5.0, 0.5]);
+        assert_eq!(sums[&Hex::new(0, 0)], 3.5);
+        assert_eq!(sums[&Hex::new(1, 0)], 5.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Hexagon size must be positive and finite.")]
+    fn test_invalid_size() {
+        HexGrid::new(-1.0, HexOrientation::FlatTop);
+    }
+}
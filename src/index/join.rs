@@ -0,0 +1,302 @@
+use super::rtree::RTree;
+use super::{HasBoundingBox, geometry_envelope};
+use crate::algorithms::distance::distance;
+use crate::algorithms::relate::{contains, intersects, within};
+use crate::geoms::Geometry;
+use crate::geoms::rect::Rect;
+
+/// Condition under which a geometry of the left side is joined with a geometry of the right side
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JoinPredicate {
+    /// the geometries have at least one point in common
+    Intersects,
+    /// the left geometry lies within the right one
+    Within,
+    /// the left geometry contains the right one
+    Contains,
+    /// the geometries are at most the given distance apart, a negative or NaN distance matches nothing
+    WithinDistance(f64),
+    /// the right geometry is one of the k nearest to the left one, ties are broken by index
+    Nearest(usize),
+}
+
+/// geometry of the right side stored in the index together with its position
+struct IndexedGeometry<'a> {
+    index: usize,
+    geometry: &'a Geometry,
+    envelope: Rect,
+}
+
+impl HasBoundingBox for IndexedGeometry<'_> {
+    fn envelope(&self) -> Rect {
+        self.envelope
+    }
+}
+
+/// Join two collections of geometries and return the index pairs (left, right) which fulfil the predicate.
+///
+/// The right side is put into an R-tree, so only pairs with close envelopes are tested exactly. Pairs are ordered
+/// by the left index, then by the right index, or for nearest joins by the distance. Empty geometries match
+/// nothing.
+pub fn spatial_join(
+    left: &[Geometry],
+    right: &[Geometry],
+    predicate: JoinPredicate,
+) -> Vec<(usize, usize)> {
+    let tree = index_geometries(right);
+    let mut pairs = Vec::new();
+    for (i, geometry) in left.iter().enumerate() {
+        if let Some(envelope) = geometry_envelope(geometry) {
+            pairs.extend(
+                matches(&tree, geometry, envelope, predicate)
+                    .into_iter()
+                    .map(|j| (i, j)),
+            );
+        }
+    }
+    pairs
+}
+
+/// Join two collections of geometries like [`spatial_join`], but keep every left geometry: the ones without any
+/// match are paired with None
+pub fn left_spatial_join(
+    left: &[Geometry],
+    right: &[Geometry],
+    predicate: JoinPredicate,
+) -> Vec<(usize, Option<usize>)> {
+    let tree = index_geometries(right);
+    let mut pairs = Vec::new();
+    for (i, geometry) in left.iter().enumerate() {
+        let found = match geometry_envelope(geometry) {
+            Some(envelope) => matches(&tree, geometry, envelope, predicate),
+            None => Vec::new(),
+        };
+        if found.is_empty() {
+            pairs.push((i, None));
+        }
+        pairs.extend(found.into_iter().map(|j| (i, Some(j))));
+    }
+    pairs
+}
+
+fn index_geometries(geometries: &[Geometry]) -> RTree<IndexedGeometry<'_>> {
+    RTree::bulk_load(
+        geometries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, geometry)| {
+                geometry_envelope(geometry).map(|envelope| IndexedGeometry {
+                    index,
+                    geometry,
+                    envelope,
+                })
+            })
+            .collect(),
+    )
+}
+
+/// get the sorted indices of the right geometries matching one left geometry
+fn matches(
+    tree: &RTree<IndexedGeometry>,
+    geometry: &Geometry,
+    envelope: Rect,
+    predicate: JoinPredicate,
+) -> Vec<usize> {
+    let search = match predicate {
+        JoinPredicate::Nearest(k) => return k_nearest(tree, geometry, envelope, k),
+        JoinPredicate::WithinDistance(max_distance) => {
+            if max_distance.is_nan() || max_distance < 0.0 {
+                return Vec::new();
+            }
+            envelope.expand_by(max_distance)
+        }
+        _ => envelope,
+    };
+    let mut found: Vec<usize> = tree
+        .query(&search)
+        .into_iter()
+        .filter(|candidate| {
+            let other = candidate.geometry;
+            match predicate {
+                JoinPredicate::Intersects => intersects(geometry, other),
+                JoinPredicate::Within => within(geometry, other),
+                JoinPredicate::Contains => contains(geometry, other),
+                JoinPredicate::WithinDistance(max_distance) => {
+                    distance(geometry, other).is_some_and(|d| d <= max_distance)
+                }
+                JoinPredicate::Nearest(_) => unreachable!(),
+            }
+        })
+        .map(|candidate| candidate.index)
+        .collect();
+    found.sort();
+    found
+}
+
+/// get the indices of the k nearest right geometries, nearest first
+fn k_nearest(
+    tree: &RTree<IndexedGeometry>,
+    geometry: &Geometry,
+    envelope: Rect,
+    k: usize,
+) -> Vec<usize> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut found: Vec<(f64, usize)> = Vec::with_capacity(k.min(tree.len()));
+    let neighbors = tree.nearest_neighbors_by(
+        move |rect| rect.distance_to_rect(&envelope),
+        |c| distance(geometry, c.geometry).unwrap_or(f64::INFINITY),
+    );
+    // keep taking geometries as long as they tie with the k-th one, so ties can be broken by index
+    for (candidate, d) in neighbors {
+        if found.len() >= k && d > found[k - 1].0 {
+            break;
+        }
+        found.push((d, candidate.index));
+    }
+    found.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    found.truncate(k);
+    found.into_iter().map(|(_, index)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geoms::multipolygon::MultiPolygon;
+    use crate::geoms::point::Point;
+    use crate::geoms::polygon::Polygon;
+
+    fn zones() -> Vec<Geometry> {
+        vec![
+            Polygon::from_wkt("POLYGON ((0 0, 10 0, 10 10, 0 10, 0 0))")
+                .unwrap()
+                .into(),
+            Polygon::from_wkt("POLYGON ((10 0, 20 0, 20 10, 10 10, 10 0))")
+                .unwrap()
+                .into(),
+            Polygon::from_wkt("POLYGON ((30 30, 40 30, 40 40, 30 40, 30 30))")
+                .unwrap()
+                .into(),
+        ]
+    }
+
+    fn events() -> Vec<Geometry> {
+        vec![
+            Point::new(5.0, 5.0).into(),
+            Point::new(10.0, 5.0).into(),
+            Point::new(25.0, 25.0).into(),
+            Point::new(35.0, 31.0).into(),
+        ]
+    }
+
+    #[test]
+    fn test_predicate_joins() {
+        let (events, zones) = (events(), zones());
+        assert_eq!(
+            spatial_join(&events, &zones, JoinPredicate::Intersects),
+            vec![(0, 0), (1, 0), (1, 1), (3, 2)]
+        );
+        // points on a boundary are not within the polygon
+        assert_eq!(
+            spatial_join(&events, &zones, JoinPredicate::Within),
+            vec![(0, 0), (3, 2)]
+        );
+        assert_eq!(
+            spatial_join(&zones, &events, JoinPredicate::Contains),
+            vec![(0, 0), (2, 3)]
+        );
+        assert_eq!(
+            spatial_join(&events, &zones, JoinPredicate::WithinDistance(8.0)),
+            vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 2), (3, 2)]
+        );
+        assert_eq!(
+            spatial_join(&events, &events, JoinPredicate::WithinDistance(-1.0)),
+            vec![]
+        );
+        assert_eq!(
+            left_spatial_join(
+                &events[..1],
+                &zones,
+                JoinPredicate::WithinDistance(f64::NAN)
+            ),
+            vec![(0, None)]
+        );
+        assert_eq!(
+            left_spatial_join(&events, &zones, JoinPredicate::Within),
+            vec![(0, Some(0)), (1, None), (2, None), (3, Some(2))]
+        );
+    }
+
+    #[test]
+    fn test_nearest_joins() {
+        let (events, zones) = (events(), zones());
+        assert_eq!(
+            spatial_join(&events, &zones, JoinPredicate::Nearest(1)),
+            vec![(0, 0), (1, 0), (2, 2), (3, 2)]
+        );
+        assert_eq!(
+            spatial_join(&events, &zones, JoinPredicate::Nearest(2)),
+            vec![
+                (0, 0),
+                (0, 1),
+                (1, 0),
+                (1, 1),
+                (2, 2),
+                (2, 1),
+                (3, 2),
+                (3, 1)
+            ]
+        );
+        // k beyond the number of geometries takes all of them
+        assert_eq!(
+            spatial_join(&events[..1], &zones, JoinPredicate::Nearest(usize::MAX)),
+            vec![(0, 0), (0, 1), (0, 2)]
+        );
+        assert_eq!(
+            left_spatial_join(&events, &[], JoinPredicate::Nearest(1)),
+            vec![(0, None), (1, None), (2, None), (3, None)]
+        );
+        // empty geometries are skipped
+        let right = vec![MultiPolygon::new(vec![]).into(), zones[2].clone()];
+        assert_eq!(
+            spatial_join(&events[..1], &right, JoinPredicate::Nearest(3)),
+            vec![(0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_join_against_brute_force() {
+        let left: Vec<Geometry> = Point::generate_random_points(200, 0.0, 0.0, 100.0, 100.0, 31)
+            .into_iter()
+            .map(Geometry::from)
+            .collect();
+        let right: Vec<Geometry> = Point::generate_random_points(100, 0.0, 0.0, 95.0, 95.0, 32)
+            .into_iter()
+            .map(|p| Rect::new(p.get_x(), p.get_y(), p.get_x() + 5.0, p.get_y() + 5.0))
+            .map(|rect| rect.to_polygon().into())
+            .collect();
+        for predicate in [
+            JoinPredicate::Intersects,
+            JoinPredicate::Within,
+            JoinPredicate::WithinDistance(3.0),
+        ] {
+            let expected: Vec<(usize, usize)> = (0..left.len())
+                .flat_map(|i| (0..right.len()).map(move |j| (i, j)))
+                .filter(|&(i, j)| match predicate {
+                    JoinPredicate::Intersects => intersects(&left[i], &right[j]),
+                    JoinPredicate::Within => within(&left[i], &right[j]),
+                    _ => distance(&left[i], &right[j]).unwrap() <= 3.0,
+                })
+                .collect();
+            assert_eq!(spatial_join(&left, &right, predicate), expected);
+        }
+        for (i, j) in spatial_join(&left, &right, JoinPredicate::Nearest(1)) {
+            let nearest = right
+                .iter()
+                .map(|r| distance(&left[i], r).unwrap())
+                .fold(f64::INFINITY, f64::min);
+            assert_eq!(distance(&left[i], &right[j]).unwrap(), nearest);
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use crate::geoms::point::Point;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::ops::Range;
+
+/// KD-tree over a set of points for nearest neighbour and radius queries.
+///
+/// The tree is stored implicitly in a permutation of the point indices: every range of the permutation holds a
+/// subtree with its median point in the middle, splitting the points alternately by x and by y. Building takes
+/// O(n log n), a nearest neighbour query O(log n) on average.
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    points: Vec<Point>,
+    /// indices into `points` arranged as the implicit tree
+    order: Vec<usize>,
+}
+
+/// candidate of a k-nearest search, ordered by distance and then by index
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Neighbor {
+    distance_squared: f64,
+    index: usize,
+}
+
+impl Eq for Neighbor {}
+
+impl PartialOrd for Neighbor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Neighbor {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance_squared
+            .total_cmp(&other.distance_squared)
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+impl KdTree {
+    /// build the tree from a slice of points, queries return indices into this slice
+    pub fn new(points: &[Point]) -> KdTree {
+        let mut tree = KdTree {
+            points: points.to_vec(),
+            order: (0..points.len()).collect(),
+        };
+        tree.build(0..points.len(), 0);
+        tree
+    }
+
+    /// get the number of points in the tree
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// check if the tree has no points
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// get the points of the tree in their original order
+    pub fn get_points(&self) -> &[Point] {
+        &self.points
+    }
+
+    /// get the index of the point nearest to the query point and its distance, None if the tree is empty
+    pub fn nearest(&self, point: &Point) -> Option<(usize, f64)> {
+        self.k_nearest(point, 1).pop()
+    }
+
+    /// Get the indices of the `k` points nearest to the query point with their distances, nearest first.
+    /// Points at equal distance are ordered by index. Fewer than `k` points are returned if the tree is smaller.
+    pub fn k_nearest(&self, point: &Point, k: usize) -> Vec<(usize, f64)> {
+        let k = k.min(self.len());
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        self.search_nearest(0..self.order.len(), 0, point, k, &mut heap);
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|n| (n.index, n.distance_squared.sqrt()))
+            .collect()
+    }
+
+    /// get the indices of all points within a distance of the query point, boundary included, with their
+    /// distances, nearest first
+    pub fn within_radius(&self, point: &Point, radius: f64) -> Vec<(usize, f64)> {
+        if radius.is_nan() || radius < 0.0 {
+            return Vec::new();
+        }
+        let mut found = Vec::new();
+        self.search_radius(0..self.order.len(), 0, point, radius * radius, &mut found);
+        found.sort();
+        found
+            .into_iter()
+            .map(|n| (n.index, n.distance_squared.sqrt()))
+            .collect()
+    }
+
+    /// place the median of the range in its middle, with smaller coordinates before and larger ones after it
+    fn build(&mut self, range: Range<usize>, depth: usize) {
+        if range.len() <= 1 {
+            return;
+        }
+        let mid = range.len() / 2;
+        let points = &self.points;
+        self.order[range.clone()].select_nth_unstable_by(mid, |&a, &b| {
+            coordinate(&points[a], depth).total_cmp(&coordinate(&points[b], depth))
+        });
+        let mid = range.start + mid;
+        self.build(range.start..mid, depth + 1);
+        self.build(mid + 1..range.end, depth + 1);
+    }
+
+    /// split a range into its median and the subtrees on the side of the query point and on the far side.
+    /// Also return the distance of the query point to the splitting line.
+    fn split(
+        &self,
+        range: &Range<usize>,
+        depth: usize,
+        point: &Point,
+    ) -> (usize, Range<usize>, Range<usize>, f64) {
+        let mid = range.start + range.len() / 2;
+        let index = self.order[mid];
+        let offset = coordinate(point, depth) - coordinate(&self.points[index], depth);
+        let (lower, upper) = (range.start..mid, mid + 1..range.end);
+        if offset < 0.0 {
+            (index, lower, upper, offset)
+        } else {
+            (index, upper, lower, offset)
+        }
+    }
+
+    fn search_nearest(
+        &self,
+        range: Range<usize>,
+        depth: usize,
+        point: &Point,
+        k: usize,
+        heap: &mut BinaryHeap<Neighbor>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let (index, near, far, offset) = self.split(&range, depth, point);
+        let candidate = Neighbor {
+            distance_squared: distance_squared(point, &self.points[index]),
+            index,
+        };
+        if heap.len() < k {
+            heap.push(candidate);
+        } else if candidate < *heap.peek().unwrap() {
+            heap.pop();
+            heap.push(candidate);
+        }
+        self.search_nearest(near, depth + 1, point, k, heap);
+        // the far side can only hold better points if the splitting line is closer than the current k-th point
+        if heap.len() < k || offset * offset <= heap.peek().unwrap().distance_squared {
+            self.search_nearest(far, depth + 1, point, k, heap);
+        }
+    }
+
+    fn search_radius(
+        &self,
+        range: Range<usize>,
+        depth: usize,
+        point: &Point,
+        radius_squared: f64,
+        found: &mut Vec<Neighbor>,
+    ) {
+        if range.is_empty() {
+            return;
+        }
+        let (index, near, far, offset) = self.split(&range, depth, point);
+        let distance_squared = distance_squared(point, &self.points[index]);
+        if distance_squared <= radius_squared {
+            found.push(Neighbor {
+                distance_squared,
+                index,
+            });
+        }
+        self.search_radius(near, depth + 1, point, radius_squared, found);
+        if offset * offset <= radius_squared {
+            self.search_radius(far, depth + 1, point, radius_squared, found);
+        }
+    }
+}
+
+/// the coordinate by which the tree splits at the given depth: x at even depths, y at odd ones
+fn coordinate(point: &Point, depth: usize) -> f64 {
+    if depth.is_multiple_of(2) {
+        point.get_x()
+    } else {
+        point.get_y()
+    }
+}
+
+fn distance_squared(a: &Point, b: &Point) -> f64 {
+    let (dx, dy) = (a.get_x() - b.get_x(), a.get_y() - b.get_y());
+    dx * dx + dy * dy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// all points sorted by distance to the query point and index
+    fn brute_force(points: &[Point], query: &Point) -> Vec<(usize, f64)> {
+        let mut all: Vec<Neighbor> = points
+            .iter()
+            .enumerate()
+            .map(|(index, p)| Neighbor {
+                distance_squared: distance_squared(p, query),
+                index,
+            })
+            .collect();
+        all.sort();
+        all.into_iter()
+            .map(|n| (n.index, n.distance_squared.sqrt()))
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_and_k_nearest() {
+        let points = Point::generate_random_points(2000, 0.0, 0.0, 100.0, 100.0, 4);
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.len(), 2000);
+        for query in Point::generate_random_points(50, -10.0, -10.0, 110.0, 110.0, 5) {
+            let expected = brute_force(&points, &query);
+            assert_eq!(tree.nearest(&query), Some(expected[0]));
+            assert_eq!(tree.k_nearest(&query, 7), expected[..7]);
+        }
+        assert_eq!(tree.k_nearest(&Point::new(1.0, 1.0), 0), vec![]);
+        assert_eq!(tree.k_nearest(&Point::new(1.0, 1.0), 5000).len(), 2000);
+        assert_eq!(
+            tree.k_nearest(&Point::new(1.0, 1.0), usize::MAX).len(),
+            2000
+        );
+    }
+
+    #[test]
+    fn test_within_radius() {
+        let points = Point::generate_random_points(2000, 0.0, 0.0, 100.0, 100.0, 6);
+        let tree = KdTree::new(&points);
+        let query = Point::new(40.0, 60.0);
+        let expected: Vec<(usize, f64)> = brute_force(&points, &query)
+            .into_iter()
+            .filter(|(_, d)| *d <= 8.0)
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(tree.within_radius(&query, 8.0), expected);
+        assert_eq!(tree.within_radius(&Point::new(500.0, 500.0), 8.0), vec![]);
+        assert_eq!(tree.within_radius(&query, -8.0), vec![]);
+        assert_eq!(tree.within_radius(&query, f64::NAN), vec![]);
+    }
+
+    #[test]
+    fn test_duplicates_and_empty_tree() {
+        let points = vec![
+            Point::new(1.0, 1.0),
+            Point::new(1.0, 1.0),
+            Point::new(2.0, 1.0),
+            Point::new(1.0, 1.0),
+        ];
+        let tree = KdTree::new(&points);
+        assert_eq!(
+            tree.k_nearest(&Point::new(1.0, 1.0), 3),
+            vec![(0, 0.0), (1, 0.0), (3, 0.0)]
+        );
+        assert_eq!(
+            tree.within_radius(&Point::new(2.0, 1.0), 0.0),
+            vec![(2, 0.0)]
+        );
+        let empty = KdTree::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(&Point::new(0.0, 0.0)), None);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::algorithms::locate::{Location, locate_point_in_polygon};
+use crate::algorithms::relate::relate;
+use crate::geoms::Geometry;
+use crate::geoms::line::Line;
+use crate::geoms::multipolygon::MultiPolygon;
+use crate::geoms::point::Point;
+use crate::geoms::polygon::Polygon;
+use crate::geoms::rect::Rect;
+
+/// Hilbert and Morton space-filling curve keys and spatial sorting
+pub mod curve;
+/// Geohash encoding, neighbours and coverings
+pub mod geohash;
+/// Hexagonal grids and hexbin aggregation
+pub mod hexgrid;
+/// Spatial joins between collections of geometries
+pub mod join;
+/// KD-tree for nearest neighbour queries on points
+pub mod kdtree;
+/// Point-region quadtree with node capacity and depth limits
+pub mod quadtree;
+/// R-tree for any objects with a bounding box
+pub mod rtree;
+/// Uniform grid hashing moving points by id
+pub mod spatial_hash;
+/// Web map tile coordinates, quadkeys and tile covers
+pub mod tiles;
+
+/// Objects which can be stored in a spatial index
+pub trait HasBoundingBox {
+    /// get the smallest axis-aligned rectangle containing the object
+    fn envelope(&self) -> Rect;
+
+    /// get the envelope, or None for empty objects which have none. Defaults to [`HasBoundingBox::envelope`].
+    fn checked_envelope(&self) -> Option<Rect> {
+        Some(self.envelope())
+    }
+
+    /// calculate the distance from a point to the object. Defaults to the distance to the envelope, which is a lower
+    /// bound of the real distance.
+    fn distance_to_point(&self, point: &Point) -> f64 {
+        self.envelope().distance_to_point(point)
+    }
+}
+
+impl HasBoundingBox for Point {
+    fn envelope(&self) -> Rect {
+        Rect::new(self.get_x(), self.get_y(), self.get_x(), self.get_y())
+    }
+
+    fn distance_to_point(&self, point: &Point) -> f64 {
+        self.distance2D(point)
+    }
+}
+
+impl HasBoundingBox for Line {
+    fn envelope(&self) -> Rect {
+        Rect::from(self.bounding_box())
+    }
+
+    fn distance_to_point(&self, point: &Point) -> f64 {
+        Line::distance_to_point(self, *point)
+    }
+}
+
+impl HasBoundingBox for Polygon {
+    fn envelope(&self) -> Rect {
+        Rect::from(self.get_exterior().bounding_box())
+    }
+
+    /// zero for points inside the polygon, otherwise the distance to the nearest ring
+    fn distance_to_point(&self, point: &Point) -> f64 {
+        if locate_point_in_polygon(point, self) != Location::Exterior {
+            return 0.0;
+        }
+        std::iter::once(self.get_exterior())
+            .chain(self.get_interiors())
+            .map(|ring| ring.distance_to_point(*point))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+impl HasBoundingBox for Rect {
+    fn envelope(&self) -> Rect {
+        *self
+    }
+}
+
+/// Empty multipolygons have no envelope, so [`HasBoundingBox::envelope`] panics for them. Filter them out before
+/// putting multipolygons into an index.
+impl HasBoundingBox for MultiPolygon {
+    fn envelope(&self) -> Rect {
+        multipolygon_envelope(self).unwrap_or_else(|| panic!("An empty geometry has no envelope."))
+    }
+
+    fn checked_envelope(&self) -> Option<Rect> {
+        multipolygon_envelope(self)
+    }
+
+    /// zero for points inside one of the polygons, otherwise the distance to the nearest polygon
+    fn distance_to_point(&self, point: &Point) -> f64 {
+        self.get_polygons()
+            .iter()
+            .map(|polygon| polygon.distance_to_point(point))
+            .fold(f64::INFINITY, f64::min)
+    }
+}
+
+/// Like for multipolygons, [`HasBoundingBox::envelope`] panics for empty geometries.
+impl HasBoundingBox for Geometry {
+    fn envelope(&self) -> Rect {
+        geometry_envelope(self).unwrap_or_else(|| panic!("An empty geometry has no envelope."))
+    }
+
+    fn checked_envelope(&self) -> Option<Rect> {
+        geometry_envelope(self)
+    }
+
+    fn distance_to_point(&self, point: &Point) -> f64 {
+        match self {
+            Geometry::Point(p) => p.distance2D(point),
+            Geometry::Line(line) => line.distance_to_point(*point),
+            Geometry::Polygon(polygon) => polygon.distance_to_point(point),
+            Geometry::MultiPolygon(multipolygon) => multipolygon.distance_to_point(point),
+        }
+    }
+}
+
+/// get the envelope of a geometry, None if it is empty
+pub(crate) fn geometry_envelope(geometry: &Geometry) -> Option<Rect> {
+    match geometry {
+        Geometry::Point(point) => Some(point.envelope()),
+        Geometry::Line(line) => Rect::from_points(line.get_points()),
+        Geometry::Polygon(polygon) => Rect::from_points(polygon.get_exterior().get_points()),
+        Geometry::MultiPolygon(multipolygon) => multipolygon_envelope(multipolygon),
+    }
+}
+
+/// How a geometry meets a cell of a grid, see [`relate_grid_cell`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CellCoverage {
+    /// the interior of the geometry does not meet the cell
+    Outside,
+    /// the interior of the geometry meets the cell but does not cover it
+    Partial,
+    /// the cell lies completely inside the geometry
+    Full,
+}
+
+/// Relate the interior of a geometry to a cell of a grid which assigns points on the border between two cells to one
+/// of them, like encoding a point does: the cell owns its west edge and either its south edge (`owns_south`) or its
+/// north edge, but not the far ends of these edges. Edges on the border of the whole grid always belong to the cell.
+/// A geometry which only touches the cell with its boundary, like a polygon sharing an edge with it, does not meet it.
+pub(crate) fn relate_grid_cell(
+    geometry: &Geometry,
+    cell: &Rect,
+    owns_south: bool,
+    grid: &Rect,
+) -> CellCoverage {
+    let matrix = relate(&Geometry::Polygon(cell.to_polygon()), geometry);
+    if matrix.matches("T********") {
+        return if matrix.matches("**F**F***") {
+            CellCoverage::Full
+        } else {
+            CellCoverage::Partial
+        };
+    }
+    if !matrix.matches("***T*****") {
+        return CellCoverage::Outside;
+    }
+    // the interior of the geometry only meets the boundary of the cell, check the edges and corners it owns
+    let (min, max) = (cell.get_min(), cell.get_max());
+    let east = max.get_x() >= grid.get_max().get_x();
+    let south = owns_south || min.get_y() <= grid.get_min().get_y();
+    let north = !owns_south || max.get_y() >= grid.get_max().get_y();
+    let south_east = Point::new(max.get_x(), min.get_y());
+    let north_west = Point::new(min.get_x(), max.get_y());
+    // the interior of an edge is the edge without its end points
+    let meets_interior = |part: Geometry| relate(&part, geometry).matches("T********");
+    let owned_edge = |owned: bool, a: Point, b: Point| {
+        owned && meets_interior(Geometry::Line(Line::new(vec![a, b])))
+    };
+    let owned_corner =
+        |owned: bool, corner: Point| owned && meets_interior(Geometry::Point(corner));
+    let owned = owned_edge(true, min, north_west)
+        || owned_edge(east, south_east, max)
+        || owned_edge(south, min, south_east)
+        || owned_edge(north, north_west, max)
+        || owned_corner(south, min)
+        || owned_corner(south && east, south_east)
+        || owned_corner(north && east, max)
+        || owned_corner(north, north_west);
+    if owned {
+        CellCoverage::Partial
+    } else {
+        CellCoverage::Outside
+    }
+}
+
+fn multipolygon_envelope(multipolygon: &MultiPolygon) -> Option<Rect> {
+    let points: Vec<Point> = multipolygon
+        .get_polygons()
+        .iter()
+        .flat_map(|polygon| polygon.get_exterior().get_points())
+        .copied()
+        .collect();
+    Rect::from_points(&points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelopes_and_distances() {
+        let point = Point::new(1.0, 2.0);
+        assert_eq!(point.envelope(), Rect::new(1.0, 2.0, 1.0, 2.0));
+        let line = Line::from_wkt("LINESTRING (0 0, 4 0, 4 3)").unwrap();
+        assert_eq!(line.envelope(), Rect::new(0.0, 0.0, 4.0, 3.0));
+        assert_eq!(HasBoundingBox::distance_to_point(&line, &point), 2.0);
+        let polygon =
+            Polygon::from_wkt("POLYGON ((0 0, 4 0, 4 4, 0 4, 0 0), (1 1, 1 3, 3 3, 3 1, 1 1))")
+                .unwrap();
+        assert_eq!(polygon.distance_to_point(&Point::new(0.5, 0.5)), 0.0);
+        assert_eq!(polygon.distance_to_point(&Point::new(2.0, 2.5)), 0.5);
+        assert_eq!(polygon.distance_to_point(&Point::new(2.0, 7.0)), 3.0);
+        let rect = Rect::new(0.0, 0.0, 1.0, 1.0);
+        assert_eq!(rect.distance_to_point(&Point::new(4.0, 5.0)), 5.0);
+    }
+
+    #[test]
+    fn test_geometry_envelopes_and_distances() {
+        let multipolygon = MultiPolygon::new(vec![
+            Polygon::from_wkt("POLYGON ((0 0, 2 0, 2 2, 0 2, 0 0))").unwrap(),
+            Polygon::from_wkt("POLYGON ((5 0, 6 0, 6 1, 5 1, 5 0))").unwrap(),
+        ]);
+        assert_eq!(multipolygon.envelope(), Rect::new(0.0, 0.0, 6.0, 2.0));
+        assert_eq!(multipolygon.distance_to_point(&Point::new(1.0, 1.0)), 0.0);
+        assert_eq!(multipolygon.distance_to_point(&Point::new(4.0, 0.5)), 1.0);
+        let geometry = Geometry::MultiPolygon(multipolygon);
+        assert_eq!(geometry.envelope(), Rect::new(0.0, 0.0, 6.0, 2.0));
+        assert_eq!(geometry.distance_to_point(&Point::new(4.0, 0.5)), 1.0);
+        let geometry = Geometry::Point(Point::new(1.0, 2.0));
+        assert_eq!(geometry.envelope(), Rect::new(1.0, 2.0, 1.0, 2.0));
+        assert_eq!(geometry.distance_to_point(&Point::new(4.0, 6.0)), 5.0);
+        assert_eq!(
+            geometry.checked_envelope(),
+            Some(Rect::new(1.0, 2.0, 1.0, 2.0))
+        );
+        assert_eq!(
+            Geometry::MultiPolygon(MultiPolygon::new(vec![])).checked_envelope(),
+            None
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "An empty geometry has no envelope.")]
+    fn test_empty_geometry_envelope() {
+        Geometry::MultiPolygon(MultiPolygon::new(vec![])).envelope();
+    }
+}
@@ -0,0 +1,313 @@
+use crate::geoms::point::Point;
+use crate::geoms::rect::Rect;
+
+/// Node of a quadtree: a leaf holding items or an inner node with four children of equal size
+#[derive(Debug, Clone)]
+pub struct QuadNode<T> {
+    bounds: Rect,
+    depth: usize,
+    items: Vec<(Point, T)>,
+    /// children in the order south-west, south-east, north-west, north-east
+    children: Option<Box<[QuadNode<T>; 4]>>,
+}
+
+/// Point-region quadtree storing values at points inside a fixed rectangle.
+///
+/// A leaf is split into four equal quadrants as soon as it holds more items than the node capacity, unless it is
+/// already at the maximum depth. Leaves at the maximum depth keep any number of items, so many identical points do
+/// not subdivide the tree forever.
+#[derive(Debug, Clone)]
+pub struct QuadTree<T> {
+    root: QuadNode<T>,
+    capacity: usize,
+    max_depth: usize,
+    size: usize,
+}
+
+impl<T> QuadTree<T> {
+    /// create an empty tree covering the given rectangle, the root node has depth 0
+    pub fn new(bounds: Rect, capacity: usize, max_depth: usize) -> QuadTree<T> {
+        if capacity == 0 {
+            panic!("Node capacity must be positive.");
+        }
+        QuadTree {
+            root: QuadNode::new(bounds, 0),
+            capacity,
+            max_depth,
+            size: 0,
+        }
+    }
+
+    /// get the rectangle covered by the tree
+    pub fn get_bounds(&self) -> Rect {
+        self.root.bounds
+    }
+
+    /// get the number of items a leaf can hold before it is split
+    pub fn get_capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// get the maximum depth of a node
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// get the root node to walk the tree
+    pub fn get_root(&self) -> &QuadNode<T> {
+        &self.root
+    }
+
+    /// get the number of items in the tree
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// check if the tree has no items
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// insert a value at a point, returns false and drops the value if the point lies outside the tree bounds
+    pub fn insert(&mut self, point: Point, value: T) -> bool {
+        if !self.root.bounds.contains_point(&point) {
+            return false;
+        }
+        self.root
+            .insert(point, value, self.capacity, self.max_depth);
+        self.size += 1;
+        true
+    }
+
+    /// get all items whose point lies inside the rectangle or on its boundary
+    pub fn query(&self, rect: &Rect) -> Vec<(&Point, &T)> {
+        let mut found = Vec::new();
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            if !node.bounds.intersects(rect) {
+                continue;
+            }
+            match node.children.as_deref() {
+                Some(children) => stack.extend(children.iter()),
+                None => found.extend(
+                    node.items
+                        .iter()
+                        .filter(|(p, _)| rect.contains_point(p))
+                        .map(|(p, v)| (p, v)),
+                ),
+            }
+        }
+        found
+    }
+
+    /// get the item nearest to a point with its distance, None if the tree is empty
+    pub fn nearest(&self, point: &Point) -> Option<(&Point, &T, f64)> {
+        let mut best = None;
+        self.root.nearest(point, &mut best);
+        best
+    }
+
+    /// iterate depth-first over all nodes, parents before their children
+    pub fn nodes(&self) -> impl Iterator<Item = &QuadNode<T>> {
+        let mut stack = vec![&self.root];
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            if let Some(children) = node.children.as_deref() {
+                stack.extend(children.iter().rev());
+            }
+            Some(node)
+        })
+    }
+}
+
+impl<T> QuadNode<T> {
+    fn new(bounds: Rect, depth: usize) -> QuadNode<T> {
+        QuadNode {
+            bounds,
+            depth,
+            items: Vec::new(),
+            children: None,
+        }
+    }
+
+    /// get the rectangle covered by the node
+    pub fn get_bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    /// get the depth of the node, 0 for the root
+    pub fn get_depth(&self) -> usize {
+        self.depth
+    }
+
+    /// get the items of a leaf, inner nodes have none
+    pub fn get_items(&self) -> &[(Point, T)] {
+        &self.items
+    }
+
+    /// get the four children of an inner node in the order south-west, south-east, north-west, north-east
+    pub fn get_children(&self) -> Option<&[QuadNode<T>; 4]> {
+        self.children.as_deref()
+    }
+
+    /// check if the node has no children
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_none()
+    }
+
+    /// add an item to the leaf containing its point
+    fn insert(&mut self, point: Point, value: T, capacity: usize, max_depth: usize) {
+        match self.children.as_deref_mut() {
+            Some(children) => {
+                children[quadrant(&self.bounds, &point)].insert(point, value, capacity, max_depth)
+            }
+            None => {
+                self.items.push((point, value));
+                if self.items.len() > capacity && self.depth < max_depth {
+                    self.split(capacity, max_depth);
+                }
+            }
+        }
+    }
+
+    /// turn a leaf into an inner node and distribute its items, splitting children which overflow in turn
+    fn split(&mut self, capacity: usize, max_depth: usize) {
+        let (min, max, center) = (
+            self.bounds.get_min(),
+            self.bounds.get_max(),
+            self.bounds.center(),
+        );
+        let (x0, y0, x1, y1, cx, cy) = (
+            min.get_x(),
+            min.get_y(),
+            max.get_x(),
+            max.get_y(),
+            center.get_x(),
+            center.get_y(),
+        );
+        let depth = self.depth + 1;
+        let mut children = Box::new([
+            QuadNode::new(Rect::new(x0, y0, cx, cy), depth),
+            QuadNode::new(Rect::new(cx, y0, x1, cy), depth),
+            QuadNode::new(Rect::new(x0, cy, cx, y1), depth),
+            QuadNode::new(Rect::new(cx, cy, x1, y1), depth),
+        ]);
+        for (point, value) in self.items.drain(..) {
+            children[quadrant(&self.bounds, &point)]
+                .items
+                .push((point, value));
+        }
+        for child in children.iter_mut() {
+            if child.items.len() > capacity && depth < max_depth {
+                child.split(capacity, max_depth);
+            }
+        }
+        self.children = Some(children);
+    }
+
+    /// search the node for an item nearer than the best one so far, visiting the nearest children first
+    fn nearest<'a>(&'a self, point: &Point, best: &mut Option<(&'a Point, &'a T, f64)>) {
+        if let Some((_, _, distance)) = best
+            && self.bounds.distance_to_point(point) >= *distance
+        {
+            return;
+        }
+        match self.children.as_deref() {
+            Some(children) => {
+                let mut order: Vec<&QuadNode<T>> = children.iter().collect();
+                order.sort_by(|a, b| {
+                    a.bounds
+                        .distance_to_point(point)
+                        .total_cmp(&b.bounds.distance_to_point(point))
+                });
+                for child in order {
+                    child.nearest(point, best);
+                }
+            }
+            None => {
+                for (p, value) in &self.items {
+                    let distance = p.distance2D(point);
+                    if best.is_none_or(|(_, _, d)| distance < d) {
+                        *best = Some((p, value, distance));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// get the index of the child quadrant of a node containing a point, points on the center lines go north and east
+fn quadrant(bounds: &Rect, point: &Point) -> usize {
+    let center = bounds.center();
+    let east = point.get_x() >= center.get_x();
+    let north = point.get_y() >= center.get_y();
+    (north as usize) * 2 + east as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_query() {
+        let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 100.0, 100.0), 4, 6);
+        let points = Point::generate_random_points(500, 0.0, 0.0, 100.0, 100.0, 11);
+        for (i, point) in points.iter().enumerate() {
+            assert!(tree.insert(*point, i));
+        }
+        assert!(!tree.insert(Point::new(101.0, 50.0), 500));
+        assert_eq!(tree.len(), 500);
+        let rect = Rect::new(20.0, 30.0, 45.0, 80.0);
+        let mut found: Vec<usize> = tree.query(&rect).into_iter().map(|(_, &i)| i).collect();
+        found.sort();
+        let expected: Vec<usize> = (0..points.len())
+            .filter(|&i| rect.contains_point(&points[i]))
+            .collect();
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn test_nearest() {
+        let mut tree = QuadTree::new(Rect::new(-50.0, -50.0, 50.0, 50.0), 8, 10);
+        assert!(tree.nearest(&Point::new(0.0, 0.0)).is_none());
+        let points = Point::generate_random_points(1000, -50.0, -50.0, 50.0, 50.0, 12);
+        for (i, point) in points.iter().enumerate() {
+            tree.insert(*point, i);
+        }
+        for query in Point::generate_random_points(30, -60.0, -60.0, 60.0, 60.0, 13) {
+            let expected = points
+                .iter()
+                .map(|p| p.distance2D(&query))
+                .fold(f64::INFINITY, f64::min);
+            let (point, &i, distance) = tree.nearest(&query).unwrap();
+            assert_eq!(distance, expected);
+            assert_eq!(*point, points[i]);
+        }
+    }
+
+    #[test]
+    fn test_traversal_and_limits() {
+        let mut tree = QuadTree::new(Rect::new(0.0, 0.0, 8.0, 8.0), 1, 2);
+        tree.insert(Point::new(1.0, 1.0), 'a');
+        assert!(tree.get_root().is_leaf());
+        tree.insert(Point::new(7.0, 7.0), 'b');
+        let root = tree.get_root();
+        let children = root.get_children().unwrap();
+        assert_eq!(children[0].get_bounds(), Rect::new(0.0, 0.0, 4.0, 4.0));
+        assert_eq!(children[3].get_bounds(), Rect::new(4.0, 4.0, 8.0, 8.0));
+        assert_eq!(children[3].get_items()[0].1, 'b');
+        // identical points stop splitting at the maximum depth
+        for _ in 0..5 {
+            tree.insert(Point::new(1.0, 1.0), 'c');
+        }
+        assert_eq!(tree.nodes().map(|n| n.get_depth()).max(), Some(2));
+        let leaf = tree
+            .nodes()
+            .find(|n| n.is_leaf() && n.get_items().len() == 6)
+            .unwrap();
+        assert_eq!(leaf.get_bounds(), Rect::new(0.0, 0.0, 2.0, 2.0));
+        assert_eq!(tree.nodes().count(), 9);
+        assert_eq!(tree.nodes().next().unwrap().get_depth(), 0);
+        assert_eq!(tree.len(), 7);
+    }
+}
@@ -0,0 +1,530 @@
+use super::HasBoundingBox;
+use crate::geoms::point::Point;
+use crate::geoms::rect::Rect;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// maximum number of entries of a node, nodes with more entries are split
+const MAX_ENTRIES: usize = 16;
+
+/// Node of an R-tree, all leaves are on the same level
+#[derive(Debug, Clone)]
+struct Node<T> {
+    /// bounding box of all objects below the node
+    envelope: Rect,
+    entries: Entries<T>,
+}
+
+#[derive(Debug, Clone)]
+enum Entries<T> {
+    Leaf(Vec<T>),
+    Inner(Vec<Node<T>>),
+}
+
+/// R-tree spatial index over objects with a bounding box.
+///
+/// The objects are grouped into nodes of at most 16 entries with their bounding boxes, so queries only descend into
+/// nodes whose box is relevant. Build the tree with [`RTree::bulk_load`] when all objects are known in advance,
+/// this packs the nodes much tighter than repeated insertion.
+#[derive(Debug, Clone)]
+pub struct RTree<T: HasBoundingBox> {
+    root: Option<Node<T>>,
+    size: usize,
+}
+
+impl<T: HasBoundingBox> RTree<T> {
+    /// create an empty tree
+    pub fn new() -> Self {
+        RTree {
+            root: None,
+            size: 0,
+        }
+    }
+
+    /// Build a tree from all objects at once with Sort-Tile-Recursive packing: the objects are sorted into
+    /// vertical slices by the x coordinate of their center, every slice is sorted by y and cut into full nodes.
+    /// The nodes of each level are packed the same way until a single root is left. Panics for objects without an
+    /// envelope, like empty geometries.
+    pub fn bulk_load(items: Vec<T>) -> Self {
+        let size = items.len();
+        if items.is_empty() {
+            return RTree::new();
+        }
+        let mut nodes: Vec<Node<T>> = sort_tile(items, |item| item.envelope())
+            .into_iter()
+            .map(|items| Node::new(Entries::Leaf(items)))
+            .collect();
+        while nodes.len() > 1 {
+            nodes = sort_tile(nodes, |node| node.envelope)
+                .into_iter()
+                .map(|children| Node::new(Entries::Inner(children)))
+                .collect();
+        }
+        RTree {
+            root: nodes.pop(),
+            size,
+        }
+    }
+
+    /// get the number of objects in the tree
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// check if the tree is empty
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Insert an object. It is added to the leaf whose bounding box grows least, nodes which overflow are split in
+    /// half along the axis in which the centers of their entries spread most.
+    pub fn insert(&mut self, item: T) {
+        self.size += 1;
+        let envelope = item.envelope();
+        let Some(root) = self.root.as_mut() else {
+            self.root = Some(Node::new(Entries::Leaf(vec![item])));
+            return;
+        };
+        if let Some(sibling) = root.insert(item, &envelope) {
+            let old_root = self.root.take().unwrap();
+            self.root = Some(Node::new(Entries::Inner(vec![old_root, sibling])));
+        }
+    }
+
+    /// Remove an object equal to the given one and return it, or None if there is no such object
+    pub fn remove(&mut self, item: &T) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        let root = self.root.as_mut()?;
+        let removed = root.remove(item, &item.envelope())?;
+        self.size -= 1;
+        // drop an empty root and roots with a single child
+        loop {
+            match self.root.take() {
+                Some(Node {
+                    entries: Entries::Inner(mut children),
+                    ..
+                }) if children.len() == 1 => self.root = children.pop(),
+                Some(root) if root.is_empty() => return Some(removed),
+                root => {
+                    self.root = root;
+                    return Some(removed);
+                }
+            }
+        }
+    }
+
+    /// get all objects whose bounding box intersects the given rectangle, touching boxes included
+    pub fn query(&self, envelope: &Rect) -> Vec<&T> {
+        let mut found = Vec::new();
+        let mut stack: Vec<&Node<T>> = self.root.iter().collect();
+        while let Some(node) = stack.pop() {
+            if !node.envelope.intersects(envelope) {
+                continue;
+            }
+            match &node.entries {
+                Entries::Leaf(items) => found.extend(
+                    items
+                        .iter()
+                        .filter(|item| item.envelope().intersects(envelope)),
+                ),
+                Entries::Inner(children) => stack.extend(children),
+            }
+        }
+        found
+    }
+
+    /// iterate over all objects in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut items = Vec::with_capacity(self.size);
+        let mut stack: Vec<&Node<T>> = self.root.iter().collect();
+        while let Some(node) = stack.pop() {
+            match &node.entries {
+                Entries::Leaf(leaf) => items.extend(leaf),
+                Entries::Inner(children) => stack.extend(children),
+            }
+        }
+        items.into_iter()
+    }
+
+    /// Iterate over all objects ordered by their distance to a point, see [`HasBoundingBox::distance_to_point`].
+    /// Each object comes with its distance. The tree is searched lazily, so taking the first few objects is cheap.
+    pub fn nearest_neighbors(&self, point: &Point) -> NearestNeighbors<'_, T> {
+        let point = *point;
+        self.nearest_neighbors_by(
+            move |envelope| envelope.distance_to_point(&point),
+            move |item| item.distance_to_point(&point),
+        )
+    }
+
+    /// Iterate over all objects ordered by a custom distance, each object comes with its distance. `bound` must
+    /// return a lower bound of the distance of every object inside a bounding box, e.g. the distance between the
+    /// box and the envelope of a query geometry.
+    pub fn nearest_neighbors_by<'a, B, D>(
+        &'a self,
+        bound: B,
+        distance: D,
+    ) -> NearestNeighbors<'a, T>
+    where
+        B: Fn(&Rect) -> f64 + 'a,
+        D: Fn(&T) -> f64 + 'a,
+    {
+        let mut queue = BinaryHeap::new();
+        if let Some(root) = &self.root {
+            queue.push(Candidate {
+                distance: bound(&root.envelope),
+                entry: Entry::Node(root),
+            });
+        }
+        NearestNeighbors {
+            bound: Box::new(bound),
+            distance: Box::new(distance),
+            queue,
+        }
+    }
+
+    /// get the object nearest to a point, or None if the tree is empty
+    pub fn nearest_neighbor(&self, point: &Point) -> Option<&T> {
+        self.nearest_neighbors(point).next().map(|(item, _)| item)
+    }
+}
+
+impl<T: HasBoundingBox> Default for RTree<T> {
+    /// the default tree is empty
+    fn default() -> Self {
+        RTree::new()
+    }
+}
+
+impl<T: HasBoundingBox> Node<T> {
+    fn new(entries: Entries<T>) -> Self {
+        let mut node = Node {
+            envelope: Rect::new(0.0, 0.0, 0.0, 0.0),
+            entries,
+        };
+        node.update_envelope();
+        node
+    }
+
+    fn is_empty(&self) -> bool {
+        match &self.entries {
+            Entries::Leaf(items) => items.is_empty(),
+            Entries::Inner(children) => children.is_empty(),
+        }
+    }
+
+    /// recompute the bounding box from the entries, which must not be empty
+    fn update_envelope(&mut self) {
+        let mut envelopes: Box<dyn Iterator<Item = Rect>> = match &self.entries {
+            Entries::Leaf(items) => Box::new(items.iter().map(|item| item.envelope())),
+            Entries::Inner(children) => Box::new(children.iter().map(|child| child.envelope)),
+        };
+        if let Some(first) = envelopes.next() {
+            self.envelope = envelopes.fold(first, |a, b| a.union(&b));
+        }
+    }
+
+    /// insert an object below the node, return the new sibling if the node had to be split
+    fn insert(&mut self, item: T, envelope: &Rect) -> Option<Node<T>> {
+        self.envelope = self.envelope.union(envelope);
+        match &mut self.entries {
+            Entries::Leaf(items) => {
+                items.push(item);
+                if items.len() <= MAX_ENTRIES {
+                    return None;
+                }
+                let half = split_half(items, |item| item.envelope());
+                let sibling = Node::new(Entries::Leaf(half));
+                self.update_envelope();
+                Some(sibling)
+            }
+            Entries::Inner(children) => {
+                // the child whose box grows least, and the smaller one of equally good children
+                let best = (0..children.len())
+                    .min_by(|&a, &b| {
+                        let cost = |child: &Node<T>| {
+                            let area = child.envelope.area();
+                            (child.envelope.union(envelope).area() - area, area)
+                        };
+                        let (grow_a, area_a) = cost(&children[a]);
+                        let (grow_b, area_b) = cost(&children[b]);
+                        grow_a.total_cmp(&grow_b).then(area_a.total_cmp(&area_b))
+                    })
+                    .unwrap();
+                let sibling = children[best].insert(item, envelope)?;
+                children.push(sibling);
+                if children.len() <= MAX_ENTRIES {
+                    return None;
+                }
+                let half = split_half(children, |child| child.envelope);
+                let sibling = Node::new(Entries::Inner(half));
+                self.update_envelope();
+                Some(sibling)
+            }
+        }
+    }
+
+    /// remove an object equal to the given one from below the node, dropping nodes which become empty
+    fn remove(&mut self, item: &T, envelope: &Rect) -> Option<T>
+    where
+        T: PartialEq,
+    {
+        if !self.envelope.intersects(envelope) {
+            return None;
+        }
+        let removed = match &mut self.entries {
+            Entries::Leaf(items) => {
+                let position = items.iter().position(|other| other == item)?;
+                items.swap_remove(position)
+            }
+            Entries::Inner(children) => {
+                let (index, removed) = children
+                    .iter_mut()
+                    .enumerate()
+                    .find_map(|(index, child)| Some((index, child.remove(item, envelope)?)))?;
+                if children[index].is_empty() {
+                    children.swap_remove(index);
+                }
+                removed
+            }
+        };
+        self.update_envelope();
+        Some(removed)
+    }
+}
+
+/// Sort-Tile-Recursive grouping: sort into vertical slices by the center x, then cut every slice sorted by the center
+/// y into groups of at most [`MAX_ENTRIES`]
+fn sort_tile<E, F>(mut entries: Vec<E>, envelope: F) -> Vec<Vec<E>>
+where
+    F: Fn(&E) -> Rect,
+{
+    let groups = entries.len().div_ceil(MAX_ENTRIES);
+    let slices = (groups as f64).sqrt().ceil() as usize;
+    let slice_size = slices * MAX_ENTRIES;
+    let center_x = |e: &E| envelope(e).center().get_x();
+    let center_y = |e: &E| envelope(e).center().get_y();
+    entries.sort_by(|a, b| center_x(a).total_cmp(&center_x(b)));
+
+    let mut result = Vec::with_capacity(groups);
+    while !entries.is_empty() {
+        let rest = entries.split_off(slice_size.min(entries.len()));
+        let mut slice = std::mem::replace(&mut entries, rest);
+        slice.sort_by(|a, b| center_y(a).total_cmp(&center_y(b)));
+        while !slice.is_empty() {
+            let rest = slice.split_off(MAX_ENTRIES.min(slice.len()));
+            result.push(std::mem::replace(&mut slice, rest));
+        }
+    }
+    result
+}
+
+/// sort the entries along the axis in which their centers spread most and split off the upper half
+fn split_half<E, F>(entries: &mut Vec<E>, envelope: F) -> Vec<E>
+where
+    F: Fn(&E) -> Rect,
+{
+    let centers: Vec<Point> = entries.iter().map(|e| envelope(e).center()).collect();
+    let bounds = Rect::from_points(&centers).unwrap();
+    let by_x = bounds.get_width() >= bounds.get_height();
+    let coordinate = |e: &E| {
+        let center = envelope(e).center();
+        if by_x { center.get_x() } else { center.get_y() }
+    };
+    entries.sort_by(|a, b| coordinate(a).total_cmp(&coordinate(b)));
+    entries.split_off(entries.len() / 2)
+}
+
+/// Iterator over the objects of an [`RTree`] ordered by their distance, see [`RTree::nearest_neighbors`] and
+/// [`RTree::nearest_neighbors_by`]
+pub struct NearestNeighbors<'a, T> {
+    bound: Box<dyn Fn(&Rect) -> f64 + 'a>,
+    distance: Box<dyn Fn(&T) -> f64 + 'a>,
+    queue: BinaryHeap<Candidate<'a, T>>,
+}
+
+enum Entry<'a, T> {
+    Node(&'a Node<T>),
+    Item(&'a T),
+}
+
+/// entry of the search queue with a lower bound of the distance of everything below it
+struct Candidate<'a, T> {
+    distance: f64,
+    entry: Entry<'a, T>,
+}
+
+impl<T> PartialEq for Candidate<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Candidate<'_, T> {}
+
+impl<T> PartialOrd for Candidate<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Candidate<'_, T> {
+    /// the nearest candidate comes first out of the heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+impl<'a, T: HasBoundingBox> Iterator for NearestNeighbors<'a, T> {
+    type Item = (&'a T, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(Candidate { distance, entry }) = self.queue.pop() {
+            match entry {
+                Entry::Item(item) => return Some((item, distance)),
+                Entry::Node(node) => match &node.entries {
+                    Entries::Leaf(items) => self.queue.extend(items.iter().map(|item| Candidate {
+                        distance: (self.distance)(item),
+                        entry: Entry::Item(item),
+                    })),
+                    Entries::Inner(children) => {
+                        self.queue.extend(children.iter().map(|child| Candidate {
+                            distance: (self.bound)(&child.envelope),
+                            entry: Entry::Node(child),
+                        }))
+                    }
+                },
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geoms::line::Line;
+
+    fn brute_force_query<'a, T: HasBoundingBox>(items: &'a [T], envelope: &Rect) -> Vec<&'a T> {
+        items
+            .iter()
+            .filter(|item| item.envelope().intersects(envelope))
+            .collect()
+    }
+
+    fn sorted(mut points: Vec<&Point>) -> Vec<Point> {
+        points.sort_by_key(|p| p.key());
+        points.into_iter().copied().collect()
+    }
+
+    /// check that every node box contains its entries and all leaves are on the same level
+    fn check_structure<T: HasBoundingBox>(node: &Node<T>) -> usize {
+        let contains = |outer: &Rect, inner: &Rect| outer.union(inner) == *outer;
+        match &node.entries {
+            Entries::Leaf(items) => {
+                assert!(
+                    items
+                        .iter()
+                        .all(|item| contains(&node.envelope, &item.envelope()))
+                );
+                assert!(items.len() <= MAX_ENTRIES);
+                0
+            }
+            Entries::Inner(children) => {
+                assert!(children.len() <= MAX_ENTRIES);
+                let depths: Vec<usize> = children
+                    .iter()
+                    .inspect(|child| assert!(contains(&node.envelope, &child.envelope)))
+                    .map(check_structure)
+                    .collect();
+                assert!(depths.iter().all(|&d| d == depths[0]));
+                depths[0] + 1
+            }
+        }
+    }
+
+    #[test]
+    fn test_bulk_load_and_query() {
+        let points = Point::generate_random_points(1000, 0.0, 0.0, 100.0, 100.0, 1);
+        let tree = RTree::bulk_load(points.clone());
+        assert_eq!(tree.len(), 1000);
+        check_structure(tree.root.as_ref().unwrap());
+        for envelope in [
+            Rect::new(10.0, 10.0, 30.0, 20.0),
+            Rect::new(-5.0, 50.0, 5.0, 150.0),
+            Rect::new(200.0, 200.0, 300.0, 300.0),
+        ] {
+            assert_eq!(
+                sorted(tree.query(&envelope)),
+                sorted(brute_force_query(&points, &envelope))
+            );
+        }
+        assert_eq!(tree.iter().count(), 1000);
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let points = Point::generate_random_points(500, -50.0, -50.0, 50.0, 50.0, 2);
+        let mut tree = RTree::new();
+        for point in &points {
+            tree.insert(*point);
+        }
+        check_structure(tree.root.as_ref().unwrap());
+        let envelope = Rect::new(-10.0, -20.0, 25.0, 5.0);
+        assert_eq!(
+            sorted(tree.query(&envelope)),
+            sorted(brute_force_query(&points, &envelope))
+        );
+
+        for point in &points[..400] {
+            assert_eq!(tree.remove(point), Some(*point));
+        }
+        assert_eq!(tree.remove(&points[0]), None);
+        assert_eq!(tree.len(), 100);
+        check_structure(tree.root.as_ref().unwrap());
+        assert_eq!(
+            sorted(tree.query(&envelope)),
+            sorted(brute_force_query(&points[400..], &envelope))
+        );
+        for point in &points[400..] {
+            tree.remove(point);
+        }
+        assert!(tree.is_empty());
+        assert!(tree.root.is_none());
+    }
+
+    #[test]
+    fn test_nearest_neighbors() {
+        let points = Point::generate_random_points(300, 0.0, 0.0, 10.0, 10.0, 3);
+        let tree = RTree::bulk_load(points.clone());
+        let query = Point::new(4.0, 6.0);
+        let mut expected: Vec<f64> = points.iter().map(|p| p.distance2D(&query)).collect();
+        expected.sort_by(f64::total_cmp);
+        let distances: Vec<f64> = tree.nearest_neighbors(&query).map(|(_, d)| d).collect();
+        assert_eq!(distances, expected);
+        let nearest = tree.nearest_neighbor(&query).unwrap();
+        assert_eq!(nearest.distance2D(&query), expected[0]);
+        assert_eq!(RTree::<Point>::new().nearest_neighbor(&query), None);
+    }
+
+    #[test]
+    fn test_lines() {
+        let lines: Vec<Line> = (0..50)
+            .map(|i| {
+                let x = i as f64;
+                Line::new(vec![Point::new(x, 0.0), Point::new(x + 0.5, 10.0)])
+            })
+            .collect();
+        let tree = RTree::bulk_load(lines);
+        let found = tree.query(&Rect::new(10.2, 4.0, 12.1, 5.0));
+        assert_eq!(found.len(), 3);
+        // the nearest line by distance, not by bounding box
+        let (nearest, distance) = tree
+            .nearest_neighbors(&Point::new(20.0, -1.0))
+            .next()
+            .unwrap();
+        assert_eq!(nearest.get_start(), Point::new(20.0, 0.0));
+        assert_eq!(distance, 1.0);
+    }
+}
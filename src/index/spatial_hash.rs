@@ -0,0 +1,268 @@
+use crate::geoms::point::Point;
+use crate::geoms::rect::Rect;
+use std::collections::HashMap;
+
+/// Integer coordinates of a grid cell, cell (i, j) covers x in [i * size, (i + 1) * size) and likewise for y
+pub type Cell = (i64, i64);
+
+/// position of a stored point and where its id sits in the cell lists
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    point: Point,
+    cell: Cell,
+    slot: usize,
+}
+
+/// Uniform grid of square cells hashing points by id, meant for points which move a lot.
+///
+/// Inserting, moving and removing a point take constant time, so the index can be kept up to date every time the
+/// points change instead of being rebuilt. Radius queries only visit the cells overlapping the search circle.
+#[derive(Debug, Clone)]
+pub struct SpatialHash {
+    cell_size: f64,
+    cells: HashMap<Cell, Vec<usize>>,
+    entries: HashMap<usize, Entry>,
+}
+
+impl SpatialHash {
+    /// create an empty grid with the given side length of the cells
+    pub fn new(cell_size: f64) -> SpatialHash {
+        if cell_size.is_nan() || cell_size <= 0.0 || cell_size.is_infinite() {
+            panic!("Cell size must be positive and finite.");
+        }
+        SpatialHash {
+            cell_size,
+            cells: HashMap::new(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// get the side length of the cells
+    pub fn get_cell_size(&self) -> f64 {
+        self.cell_size
+    }
+
+    /// get the number of points in the grid
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// check if the grid has no points
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// get the position of the point with the given id
+    pub fn get(&self, id: usize) -> Option<Point> {
+        self.entries.get(&id).map(|entry| entry.point)
+    }
+
+    /// get the cell containing a position
+    pub fn cell_of(&self, point: &Point) -> Cell {
+        (
+            (point.get_x() / self.cell_size).floor() as i64,
+            (point.get_y() / self.cell_size).floor() as i64,
+        )
+    }
+
+    /// get the area covered by a cell
+    pub fn cell_bounds(&self, cell: Cell) -> Rect {
+        let (i, j) = (cell.0 as f64, cell.1 as f64);
+        Rect::new(
+            i * self.cell_size,
+            j * self.cell_size,
+            (i + 1.0) * self.cell_size,
+            (j + 1.0) * self.cell_size,
+        )
+    }
+
+    /// insert a point under an id, if the id is already present the point is moved and its old position returned
+    pub fn insert(&mut self, id: usize, point: Point) -> Option<Point> {
+        if self.entries.contains_key(&id) {
+            return self.move_point(id, point);
+        }
+        let cell = self.cell_of(&point);
+        let slot = self.add_to_cell(cell, id);
+        self.entries.insert(id, Entry { point, cell, slot });
+        None
+    }
+
+    /// move the point with the given id to a new position and return the old one, None if the id is not present
+    pub fn move_point(&mut self, id: usize, point: Point) -> Option<Point> {
+        let entry = *self.entries.get(&id)?;
+        let cell = self.cell_of(&point);
+        let slot = if cell == entry.cell {
+            entry.slot
+        } else {
+            self.remove_from_cell(entry.cell, entry.slot);
+            self.add_to_cell(cell, id)
+        };
+        self.entries.insert(id, Entry { point, cell, slot });
+        Some(entry.point)
+    }
+
+    /// remove the point with the given id and return its position, None if the id is not present
+    pub fn remove(&mut self, id: usize) -> Option<Point> {
+        let entry = self.entries.remove(&id)?;
+        self.remove_from_cell(entry.cell, entry.slot);
+        Some(entry.point)
+    }
+
+    /// get the ids of all points within a distance of the query point, boundary included, with their distances,
+    /// nearest first and ties ordered by id
+    pub fn within_radius(&self, point: &Point, radius: f64) -> Vec<(usize, f64)> {
+        if radius.is_nan() || radius < 0.0 {
+            return Vec::new();
+        }
+        let (min_i, min_j) =
+            self.cell_of(&Point::new(point.get_x() - radius, point.get_y() - radius));
+        let (max_i, max_j) =
+            self.cell_of(&Point::new(point.get_x() + radius, point.get_y() + radius));
+        let mut found = Vec::new();
+        let mut check = |ids: &[usize]| {
+            for &id in ids {
+                let distance = self.entries[&id].point.distance2D(point);
+                if distance <= radius {
+                    found.push((id, distance));
+                }
+            }
+        };
+        // for large circles it is cheaper to look at all occupied cells than at every cell in range. The count is
+        // computed in floats, as the cell coordinates of huge circles saturate and their difference overflows.
+        let span = |min: i64, max: i64| max as f64 - min as f64 + 1.0;
+        let cells_in_range = span(min_i, max_i) * span(min_j, max_j);
+        if cells_in_range > self.cells.len() as f64 {
+            for (&(i, j), ids) in &self.cells {
+                if (min_i..=max_i).contains(&i) && (min_j..=max_j).contains(&j) {
+                    check(ids);
+                }
+            }
+        } else {
+            for i in min_i..=max_i {
+                for j in min_j..=max_j {
+                    if let Some(ids) = self.cells.get(&(i, j)) {
+                        check(ids);
+                    }
+                }
+            }
+        }
+        found.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        found
+    }
+
+    /// iterate over the occupied cells with the ids of the points inside, in no particular order
+    pub fn cells(&self) -> impl Iterator<Item = (Cell, &[usize])> {
+        self.cells.iter().map(|(&cell, ids)| (cell, ids.as_slice()))
+    }
+
+    /// iterate over all ids with their positions, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Point)> + '_ {
+        self.entries.iter().map(|(&id, entry)| (id, entry.point))
+    }
+
+    /// append an id to the list of a cell and return its position in the list
+    fn add_to_cell(&mut self, cell: Cell, id: usize) -> usize {
+        let ids = self.cells.entry(cell).or_default();
+        ids.push(id);
+        ids.len() - 1
+    }
+
+    /// remove the id at a position of a cell list by moving the last id of the list into its place
+    fn remove_from_cell(&mut self, cell: Cell, slot: usize) {
+        let ids = self.cells.get_mut(&cell).unwrap();
+        ids.swap_remove(slot);
+        if let Some(&moved) = ids.get(slot) {
+            self.entries.get_mut(&moved).unwrap().slot = slot;
+        }
+        if ids.is_empty() {
+            self.cells.remove(&cell);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_move_remove() {
+        let mut grid = SpatialHash::new(10.0);
+        assert_eq!(grid.insert(1, Point::new(5.0, 5.0)), None);
+        assert_eq!(grid.insert(2, Point::new(7.0, 3.0)), None);
+        assert_eq!(grid.insert(3, Point::new(-5.0, 15.0)), None);
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.cell_of(&Point::new(-5.0, 15.0)), (-1, 1));
+        assert_eq!(grid.cell_bounds((-1, 1)), Rect::new(-10.0, 10.0, 0.0, 20.0));
+        assert_eq!(grid.cells().count(), 2);
+        // moving the first point out of the shared cell must keep the second one findable
+        assert_eq!(
+            grid.move_point(1, Point::new(25.0, 5.0)),
+            Some(Point::new(5.0, 5.0))
+        );
+        assert_eq!(grid.move_point(9, Point::new(0.0, 0.0)), None);
+        assert_eq!(
+            grid.within_radius(&Point::new(7.0, 3.0), 0.0),
+            vec![(2, 0.0)]
+        );
+        assert_eq!(
+            grid.insert(3, Point::new(26.0, 5.0)),
+            Some(Point::new(-5.0, 15.0))
+        );
+        let mut cells: Vec<(Cell, Vec<usize>)> = grid
+            .cells()
+            .map(|(cell, ids)| {
+                let mut ids = ids.to_vec();
+                ids.sort();
+                (cell, ids)
+            })
+            .collect();
+        cells.sort();
+        assert_eq!(cells, vec![((0, 0), vec![2]), ((2, 0), vec![1, 3])]);
+        assert_eq!(grid.remove(1), Some(Point::new(25.0, 5.0)));
+        assert_eq!(grid.remove(1), None);
+        assert_eq!(grid.get(3), Some(Point::new(26.0, 5.0)));
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn test_within_radius_against_brute_force() {
+        let mut points = Point::generate_random_points(1000, -50.0, -50.0, 50.0, 50.0, 8);
+        let mut grid = SpatialHash::new(3.0);
+        for (id, point) in points.iter().enumerate() {
+            grid.insert(id, *point);
+        }
+        // move every other point and remove every tenth
+        for id in (0..points.len()).step_by(2) {
+            points[id] = Point::new(points[id].get_y() * 0.5, points[id].get_x());
+            grid.move_point(id, points[id]);
+        }
+        for id in (0..points.len()).step_by(10) {
+            grid.remove(id);
+        }
+        for (query, radius) in [
+            (Point::new(0.0, 0.0), 7.5),
+            (Point::new(-48.0, 20.0), 2.0),
+            (Point::new(10.0, 10.0), 500.0),
+            // the cell range of these circles does not fit into the cell coordinates
+            (Point::new(10.0, 10.0), 1e19),
+            (Point::new(10.0, 10.0), f64::INFINITY),
+        ] {
+            let mut expected: Vec<(usize, f64)> = points
+                .iter()
+                .enumerate()
+                .filter(|(id, _)| id % 10 != 0)
+                .map(|(id, p)| (id, p.distance2D(&query)))
+                .filter(|(_, d)| *d <= radius)
+                .collect();
+            expected.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+            assert_eq!(grid.within_radius(&query, radius), expected);
+        }
+        assert_eq!(grid.len(), 900);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cell size must be positive and finite.")]
+    fn test_invalid_cell_size() {
+        SpatialHash::new(0.0);
+    }
+}
@@ -0,0 +1,410 @@
+use super::{CellCoverage, geometry_envelope, relate_grid_cell};
+use crate::geoms::Geometry;
+use crate::geoms::point::Point;
+use crate::geoms::rect::Rect;
+use std::f64::consts::PI;
+
+/// radius of the sphere used by Web Mercator in metres
+const EARTH_RADIUS: f64 = 6_378_137.0;
+
+/// highest supported zoom level, tile coordinates still fit into 32 bits
+const MAX_ZOOM: u8 = 30;
+
+/// latitude where the square Web Mercator map ends, north and south of it everything is clamped
+pub const MAX_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// Map tile in the XYZ scheme used by web maps: x grows to the east and y to the south, tile (0, 0) is the
+/// north-western one and each zoom level splits every tile into four
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Tile {
+    z: u8,
+    x: u32,
+    y: u32,
+}
+
+impl Tile {
+    /// create a tile from its zoom level and coordinates
+    pub fn new(x: u32, y: u32, z: u8) -> Tile {
+        if z > MAX_ZOOM {
+            panic!("Zoom level must be at most 30.");
+        }
+        if x as u64 >= 1 << z || y as u64 >= 1 << z {
+            panic!("Tile coordinates out of range for the zoom level.");
+        }
+        Tile { z, x, y }
+    }
+
+    /// get the x coordinate, counted from the west
+    pub fn get_x(&self) -> u32 {
+        self.x
+    }
+
+    /// get the y coordinate, counted from the north
+    pub fn get_y(&self) -> u32 {
+        self.y
+    }
+
+    /// get the zoom level
+    pub fn get_zoom(&self) -> u8 {
+        self.z
+    }
+
+    /// Get the tile containing a point with longitude as x and latitude as y in degrees. Latitudes beyond the
+    /// Web Mercator limit of ±85.05° are clamped to the northern- or southernmost row.
+    pub fn from_lon_lat(point: &Point, zoom: u8) -> Result<Tile, &'static str> {
+        if zoom > MAX_ZOOM {
+            return Err("Zoom level must be at most 30");
+        }
+        let (x, y) = tile_position(point, zoom)?;
+        let last = (1u64 << zoom) - 1;
+        Ok(Tile::new(
+            (x as u64).min(last) as u32,
+            (y as u64).min(last) as u32,
+            zoom,
+        ))
+    }
+
+    /// get the north-western corner of the tile with longitude as x and latitude as y
+    pub fn to_lon_lat(&self) -> Point {
+        corner_lon_lat(self.x as f64, self.y as f64, self.z)
+    }
+
+    /// get the area of the tile in longitude and latitude
+    pub fn bounds_lon_lat(&self) -> Rect {
+        let north_west = self.to_lon_lat();
+        let south_east = corner_lon_lat(self.x as f64 + 1.0, self.y as f64 + 1.0, self.z);
+        Rect::new(
+            north_west.get_x(),
+            south_east.get_y(),
+            south_east.get_x(),
+            north_west.get_y(),
+        )
+    }
+
+    /// get the area of the tile in Web Mercator coordinates (EPSG:3857) in metres
+    pub fn bounds_mercator(&self) -> Rect {
+        let extent = PI * EARTH_RADIUS;
+        let size = 2.0 * extent / (1u64 << self.z) as f64;
+        let min_x = -extent + self.x as f64 * size;
+        let max_y = extent - self.y as f64 * size;
+        Rect::new(min_x, max_y - size, min_x + size, max_y)
+    }
+
+    /// get the Bing Maps quadkey of the tile, one digit per zoom level and empty for zoom 0
+    pub fn to_quadkey(&self) -> String {
+        (1..=self.z)
+            .rev()
+            .map(|level| {
+                let bit = 1 << (level - 1);
+                let digit = (self.x & bit != 0) as u8 + 2 * (self.y & bit != 0) as u8;
+                (b'0' + digit) as char
+            })
+            .collect()
+    }
+
+    /// get the tile of a Bing Maps quadkey
+    pub fn from_quadkey(quadkey: &str) -> Result<Tile, &'static str> {
+        if quadkey.len() > MAX_ZOOM as usize {
+            return Err("Quadkey must have at most 30 digits");
+        }
+        let (mut x, mut y) = (0, 0);
+        for c in quadkey.bytes() {
+            let digit = match c {
+                b'0'..=b'3' => (c - b'0') as u32,
+                _ => return Err("Invalid quadkey digit"),
+            };
+            x = x << 1 | digit & 1;
+            y = y << 1 | digit >> 1;
+        }
+        Ok(Tile::new(x, y, quadkey.len() as u8))
+    }
+
+    /// get the four tiles of the next zoom level inside this one, None at the highest zoom level
+    pub fn children(&self) -> Option<[Tile; 4]> {
+        if self.z == MAX_ZOOM {
+            return None;
+        }
+        let (x, y, z) = (self.x * 2, self.y * 2, self.z + 1);
+        Some([
+            Tile::new(x, y, z),
+            Tile::new(x + 1, y, z),
+            Tile::new(x, y + 1, z),
+            Tile::new(x + 1, y + 1, z),
+        ])
+    }
+}
+
+/// convert a point with longitude as x and latitude as y in degrees to Web Mercator coordinates in metres
+pub fn lon_lat_to_mercator(point: &Point) -> Point {
+    let lat = point
+        .get_y()
+        .clamp(-MAX_LATITUDE, MAX_LATITUDE)
+        .to_radians();
+    Point::new(
+        EARTH_RADIUS * point.get_x().to_radians(),
+        EARTH_RADIUS * lat.tan().asinh(),
+    )
+}
+
+/// convert a point in Web Mercator coordinates in metres to longitude as x and latitude as y in degrees
+pub fn mercator_to_lon_lat(point: &Point) -> Point {
+    Point::new(
+        (point.get_x() / EARTH_RADIUS).to_degrees(),
+        (point.get_y() / EARTH_RADIUS).sinh().atan().to_degrees(),
+    )
+}
+
+/// Get all tiles of a zoom level which intersect a rectangle in longitude and latitude, ordered by x and then y.
+/// Like in [`tiles_covering`] an edge of the rectangle on the border between two tiles does not reach into the
+/// tile beyond it, and a rectangle without width or height follows the rule of [`Tile::from_lon_lat`].
+pub fn tiles_covering_rect(rect: &Rect, zoom: u8) -> Result<Vec<Tile>, &'static str> {
+    if zoom > MAX_ZOOM {
+        return Err("Zoom level must be at most 30");
+    }
+    let clamp = |x: f64, y: f64| Point::new(x.clamp(-180.0, 180.0), y.clamp(-90.0, 90.0));
+    let (min, max) = (rect.get_min(), rect.get_max());
+    let (west, north) = tile_position(&clamp(min.get_x(), max.get_y()), zoom)?;
+    let (east, south) = tile_position(&clamp(max.get_x(), min.get_y()), zoom)?;
+    let last = (1u64 << zoom) - 1;
+    // the tiles from the one containing the low end up to the last one the open interval reaches
+    let range = |low: f64, high: f64| {
+        let first = (low as u64).min(last);
+        let end = if high > low {
+            (high.ceil() as u64).saturating_sub(1)
+        } else {
+            high as u64
+        };
+        first as u32..=end.clamp(first, last) as u32
+    };
+    let (columns, rows) = (range(west, east), range(north, south));
+    Ok(columns
+        .flat_map(|x| rows.clone().map(move |y| Tile::new(x, y, zoom)))
+        .collect())
+}
+
+/// Get all tiles of a zoom level which intersect a geometry in longitude and latitude, sorted. A geometry on the
+/// border between two tiles only meets the tile which [`Tile::from_lon_lat`] assigns the border to, so a rectangle
+/// gets the same tiles as from [`tiles_covering_rect`]. Tiles are refined from zoom 0 down, only descending into
+/// tiles which meet the geometry, and tiles completely covered by a polygon are filled without further tests.
+pub fn tiles_covering(geometry: &Geometry, zoom: u8) -> Result<Vec<Tile>, &'static str> {
+    if zoom > MAX_ZOOM {
+        return Err("Zoom level must be at most 30");
+    }
+    let mut cover = Vec::new();
+    if let Some(envelope) = geometry_envelope(geometry) {
+        cover_tile(geometry, &envelope, Tile::new(0, 0, 0), zoom, &mut cover);
+    }
+    cover.sort();
+    Ok(cover)
+}
+
+/// add the tiles of the zoom level below a tile which meet the geometry
+fn cover_tile(geometry: &Geometry, envelope: &Rect, tile: Tile, zoom: u8, cover: &mut Vec<Tile>) {
+    // the outermost rows also hold everything beyond the latitude limit, like in Tile::from_lon_lat
+    let bounds = tile.bounds_lon_lat();
+    let last = (1u64 << tile.z) - 1;
+    let south = if tile.y as u64 == last {
+        -90.0
+    } else {
+        bounds.get_min().get_y()
+    };
+    let north = if tile.y == 0 {
+        90.0
+    } else {
+        bounds.get_max().get_y()
+    };
+    let bounds = Rect::new(
+        bounds.get_min().get_x(),
+        south,
+        bounds.get_max().get_x(),
+        north,
+    );
+    if !bounds.intersects(envelope) {
+        return;
+    }
+    let world = Rect::new(-180.0, -90.0, 180.0, 90.0);
+    match relate_grid_cell(geometry, &bounds, false, &world) {
+        CellCoverage::Outside => {}
+        _ if tile.z == zoom => cover.push(tile),
+        CellCoverage::Full => {
+            let scale = 1 << (zoom - tile.z);
+            for x in tile.x * scale..(tile.x + 1) * scale {
+                for y in tile.y * scale..(tile.y + 1) * scale {
+                    cover.push(Tile::new(x, y, zoom));
+                }
+            }
+        }
+        CellCoverage::Partial => {
+            for child in tile.children().unwrap() {
+                cover_tile(geometry, envelope, child, zoom, cover);
+            }
+        }
+    }
+}
+
+/// get the fractional tile coordinates of a point with longitude as x and latitude as y, with clamped latitude
+fn tile_position(point: &Point, zoom: u8) -> Result<(f64, f64), &'static str> {
+    let (lon, lat) = (point.get_x(), point.get_y());
+    if !(-180.0..=180.0).contains(&lon) || !(-90.0..=90.0).contains(&lat) {
+        return Err("Coordinates must be longitude and latitude in degrees");
+    }
+    let n = (1u64 << zoom) as f64;
+    let lat = lat.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    let x = (lon + 180.0) / 360.0 * n;
+    let y = ((1.0 - lat.tan().asinh() / PI) / 2.0 * n).max(0.0);
+    Ok((x, y))
+}
+
+/// get the longitude and latitude of a tile corner given in fractional tile coordinates
+fn corner_lon_lat(x: f64, y: f64, z: u8) -> Point {
+    let n = (1u64 << z) as f64;
+    let lat = (PI * (1.0 - 2.0 * y / n)).sinh().atan();
+    Point::new(x / n * 360.0 - 180.0, lat.to_degrees())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geoms::line::Line;
+    use crate::geoms::polygon::Polygon;
+
+    #[test]
+    fn test_lon_lat_to_tile() {
+        let berlin = Point::new(13.4, 52.5);
+        assert_eq!(
+            Tile::from_lon_lat(&berlin, 10).unwrap(),
+            Tile::new(550, 335, 10)
+        );
+        assert_eq!(
+            Tile::from_lon_lat(&Point::new(-122.4194, 37.7749), 12).unwrap(),
+            Tile::new(655, 1583, 12)
+        );
+        assert_eq!(
+            Tile::from_lon_lat(&Point::new(0.0, 0.0), 1).unwrap(),
+            Tile::new(1, 1, 1)
+        );
+        // the edges of the map and the poles stay inside the tile range
+        assert_eq!(
+            Tile::from_lon_lat(&Point::new(180.0, 90.0), 3).unwrap(),
+            Tile::new(7, 0, 3)
+        );
+        assert_eq!(
+            Tile::from_lon_lat(&Point::new(-180.0, -90.0), 3).unwrap(),
+            Tile::new(0, 7, 3)
+        );
+        assert!(Tile::from_lon_lat(&Point::new(181.0, 0.0), 3).is_err());
+        assert!(Tile::from_lon_lat(&berlin, 31).is_err());
+        let tile = Tile::from_lon_lat(&berlin, 15).unwrap();
+        assert!(tile.bounds_lon_lat().contains_point(&berlin));
+        let center = tile.bounds_lon_lat().center();
+        assert_eq!(Tile::from_lon_lat(&center, 15).unwrap(), tile);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let world = Tile::new(0, 0, 0);
+        let bounds = world.bounds_lon_lat();
+        assert_eq!(bounds.get_min().get_x(), -180.0);
+        assert!((bounds.get_max().get_y() - MAX_LATITUDE).abs() < 1e-9);
+        let extent = PI * EARTH_RADIUS;
+        assert_eq!(
+            world.bounds_mercator(),
+            Rect::new(-extent, -extent, extent, extent)
+        );
+        let tile = Tile::new(3, 5, 4);
+        let mercator = tile.bounds_mercator();
+        let converted = lon_lat_to_mercator(&tile.bounds_lon_lat().get_min());
+        assert!(converted.approx_eq(&mercator.get_min(), 1e-6));
+        assert!(mercator_to_lon_lat(&converted).approx_eq(&tile.bounds_lon_lat().get_min(), 1e-9));
+    }
+
+    #[test]
+    fn test_quadkey() {
+        let tile = Tile::new(3, 5, 3);
+        assert_eq!(tile.to_quadkey(), "213");
+        assert_eq!(Tile::from_quadkey("213").unwrap(), tile);
+        assert_eq!(Tile::new(0, 0, 0).to_quadkey(), "");
+        assert_eq!(Tile::from_quadkey("").unwrap(), Tile::new(0, 0, 0));
+        let deep = Tile::new(123_456, 654_321, 20);
+        assert_eq!(Tile::from_quadkey(&deep.to_quadkey()).unwrap(), deep);
+        assert!(Tile::from_quadkey("0124").is_err());
+    }
+
+    #[test]
+    fn test_children() {
+        assert_eq!(
+            Tile::new(1, 2, 3).children(),
+            Some([
+                Tile::new(2, 4, 4),
+                Tile::new(3, 4, 4),
+                Tile::new(2, 5, 4),
+                Tile::new(3, 5, 4)
+            ])
+        );
+        assert_eq!(Tile::new(0, 0, MAX_ZOOM).children(), None);
+    }
+
+    #[test]
+    fn test_cover_of_tile_aligned_rect() {
+        // the rectangle only touches the western tiles and the tile south of the equator
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+        let expected = vec![Tile::new(1, 0, 1)];
+        assert_eq!(tiles_covering_rect(&rect, 1).unwrap(), expected);
+        assert_eq!(
+            tiles_covering(&rect.to_polygon().into(), 1).unwrap(),
+            expected
+        );
+        // a line on the equator belongs to the tiles south of it, like a point there
+        let equator: Geometry = Line::from_wkt("LINESTRING (10 0, 20 0)").unwrap().into();
+        assert_eq!(
+            tiles_covering(&equator, 1).unwrap(),
+            vec![Tile::new(1, 1, 1)]
+        );
+        assert_eq!(
+            tiles_covering_rect(&Rect::new(10.0, 0.0, 20.0, 0.0), 1).unwrap(),
+            vec![Tile::new(1, 1, 1)]
+        );
+        // a polygon exactly filling a tile gets only that tile
+        let tile = Tile::new(5, 9, 4);
+        let polygon: Geometry = tile.bounds_lon_lat().to_polygon().into();
+        let cover = tiles_covering(&polygon, 6).unwrap();
+        assert_eq!(cover.len(), 16);
+        assert!(
+            cover
+                .iter()
+                .all(|t| t.get_x() / 4 == 5 && t.get_y() / 4 == 9)
+        );
+        let point: Geometry = Point::new(0.0, 0.0).into();
+        assert_eq!(tiles_covering(&point, 1).unwrap(), vec![Tile::new(1, 1, 1)]);
+    }
+
+    #[test]
+    fn test_covers() {
+        let rect = Rect::new(-10.0, -10.0, 10.0, 10.0);
+        assert_eq!(
+            tiles_covering_rect(&rect, 1).unwrap(),
+            vec![
+                Tile::new(0, 0, 1),
+                Tile::new(0, 1, 1),
+                Tile::new(1, 0, 1),
+                Tile::new(1, 1, 1)
+            ]
+        );
+        let line: Geometry = Line::from_wkt("LINESTRING (1 1, 100 1)").unwrap().into();
+        assert_eq!(
+            tiles_covering(&line, 2).unwrap(),
+            vec![Tile::new(2, 1, 2), Tile::new(3, 1, 2)]
+        );
+        let polygon: Geometry = Polygon::from_wkt("POLYGON ((1 1, 30 1, 30 30, 1 30, 1 1))")
+            .unwrap()
+            .into();
+        let cover = tiles_covering(&polygon, 6).unwrap();
+        let envelope = geometry_envelope(&polygon).unwrap();
+        let candidates = tiles_covering_rect(&envelope, 6).unwrap();
+        // an axis-aligned rectangle covers the same tiles as its envelope
+        assert_eq!(cover, candidates);
+        for point in Point::generate_random_points(100, 1.0, 1.0, 30.0, 30.0, 61) {
+            assert!(cover.contains(&Tile::from_lon_lat(&point, 6).unwrap()));
+        }
+    }
+}
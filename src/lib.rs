@@ -1,2 +1,6 @@
+/// Geometric algorithms
+pub mod algorithms;
 /// Geometric data types
 pub mod geoms;
+/// Spatial indexes
+pub mod index;
@@ -1,7 +1,5 @@
-mod geoms;
 //use geoms::Point;
-use geoms::line::Line;
-use geoms::point::Point;
+use geome::geoms::point::Point;
 use std::time::Instant;
 
 fn main() {
@@ -11,8 +9,8 @@ fn main() {
     println!("{0}", a.get_x());
     let b = Point::from_wkt("POINT (-65 7.5875)");
     let b_point = b.unwrap();
-    let x = b_point.get_x();
-    let y = b_point.get_y();
+    let _x = b_point.get_x();
+    let _y = b_point.get_y();
     println!("{}", b_point.to_wkt());
     //let gh = Li
 
@@ -32,7 +30,7 @@ fn main() {
         println!("{:?}", point);
     }
     let start = Instant::now();
-    let results = Point::pairwise_distances(&random_points);
+    let _results = Point::pairwise_distances(&random_points);
     let duration = start.elapsed();
     println!("Execution time distance matrix: {:?}", duration);
     //println!("{:?}", results);